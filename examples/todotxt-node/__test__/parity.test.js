@@ -0,0 +1,39 @@
+const assert = require("assert");
+const { execFileSync } = require("child_process");
+const fs = require("fs");
+const path = require("path");
+const { test } = require("node:test");
+const { formatTask, parseTasks } = require("..");
+
+const fixture = path.resolve(__dirname, "..", "..", "..", "fixtures", "todo.txt");
+const input = fs.readFileSync(fixture, "utf8");
+
+test("parseTasks matches the serde output of the Rust crate", () => {
+  const expected = execFileSync("cargo", ["run", "--quiet", "-p", "todotxt-to-json"], {
+    input,
+    encoding: "utf8",
+  });
+
+  assert.deepStrictEqual(parseTasks(input), JSON.parse(expected));
+});
+
+test("formatTask produces a line that parses into the same task", () => {
+  for (const task of parseTasks(input)) {
+    assert.deepStrictEqual(parseTasks(formatTask(task)), [task]);
+  }
+});
+
+test("formatTask formats tasks without tags", () => {
+  const task = {
+    creation_date: "2011-03-01",
+    description: "Call Mom",
+    priority: "A",
+    type: "INCOMPLETE",
+  };
+
+  assert.strictEqual(formatTask(task), "(A) 2011-03-01 Call Mom");
+});
+
+test("formatTask rejects invalid tasks", () => {
+  assert.throws(() => formatTask({ description: "Call Mom", priority: "A", type: "COMPLETE" }));
+});
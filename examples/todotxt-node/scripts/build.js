@@ -0,0 +1,16 @@
+const { execFileSync } = require("child_process");
+const fs = require("fs");
+const path = require("path");
+
+const root = path.resolve(__dirname, "..");
+const libraries = {
+  darwin: "libtodotxt_node.dylib",
+  linux: "libtodotxt_node.so",
+  win32: "todotxt_node.dll",
+};
+
+execFileSync("cargo", ["build", "--release"], { cwd: root, stdio: "inherit" });
+fs.copyFileSync(
+  path.join(root, "..", "..", "target", "release", libraries[process.platform]),
+  path.join(root, "index.node"),
+);
@@ -0,0 +1,26 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+use serde_json::Value;
+use todotxt::prelude::*;
+
+/// Parses the tasks in `text` into plain objects with the same shape as the
+/// serde output of the `todotxt` crate.
+#[napi]
+pub fn parse_tasks(text: String) -> Result<Value> {
+    let tasks: Vec<Task<'_>> = text.tasks().collect();
+
+    serde_json::to_value(tasks).map_err(invalid_arg)
+}
+
+/// Formats a plain object produced by `parseTasks` as a line of a todo.txt
+/// file.
+#[napi]
+pub fn format_task(task: Value) -> Result<String> {
+    let task: Task<'static> = serde_json::from_value(task).map_err(invalid_arg)?;
+
+    Ok(task.to_string())
+}
+
+fn invalid_arg(error: serde_json::Error) -> Error {
+    Error::new(Status::InvalidArg, error.to_string())
+}
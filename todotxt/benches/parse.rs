@@ -1,40 +1,191 @@
-#![feature(test)]
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use std::{env, fmt::Write, io::Cursor};
+use todotxt::{chrono::NaiveDate, prelude::*, TaskStats};
 
-extern crate test;
+static A: &str = include_str!("../../fixtures/todo.txt");
+static B: &str = "x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github";
+static C: &str = "(A) Thank Mom for the meatballs @phone";
 
+/// Generates `count` lines of realistic todo.txt data. The output is
+/// deterministic so that results are comparable between runs.
+fn generate(count: usize) -> String {
+    const WORDS: &[&str] = &[
+        "call",
+        "email",
+        "review",
+        "schedule",
+        "write",
+        "fix",
+        "plan",
+        "buy",
+        "the",
+        "report",
+        "meeting",
+        "groceries",
+        "invoice",
+        "parser",
+        "garden",
+    ];
+    const TAGS: &[&str] = &[
+        "@phone",
+        "@home",
+        "@work",
+        "+GarageSale",
+        "+todo.txt",
+        "due:2011-03-01",
+        "t:2011-02-25",
+    ];
 
-use test::Bencher;
-use todotxt::prelude::*;
+    let mut data = String::new();
 
-static A: &str = include_str!("../../fixtures/todo.txt");
-static B: &str = "x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github";
+    for index in 0..count {
+        match index % 4 {
+            0 => {
+                let _ = write!(data, "({}) ", (b'A' + (index % 26) as u8) as char);
+            }
+            1 => data.push_str("2011-03-02 "),
+            2 => data.push_str("x 2011-03-03 2011-03-01 "),
+            _ => {}
+        }
+
+        for offset in 0..(4 + index % 6) {
+            data.push_str(WORDS[(index * 7 + offset * 3) % WORDS.len()]);
+            data.push(' ');
+        }
 
-#[bench]
-fn list(bench: &mut Bencher) {
-    bench.iter(|| {
-        assert!(A.tasks().count() > 0);
+        data.push_str(TAGS[index % TAGS.len()]);
+        data.push(' ');
+        data.push_str(TAGS[(index / 3) % TAGS.len()]);
+        data.push('\n');
+    }
+
+    data
+}
+
+/// The synthetic input. Set `TODOTXT_BENCH_LINES` to benchmark inputs that are
+/// too large to commit, e.g. 20000000 for ~1GB.
+fn large_input() -> String {
+    let lines = env::var("TODOTXT_BENCH_LINES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(100_000);
+
+    generate(lines)
+}
+
+fn parse(c: &mut Criterion) {
+    let large = large_input();
+    let mut group = c.benchmark_group("parse");
+
+    for (name, data) in &[("fixture", A), ("synthetic", large.as_str())] {
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_function(format!("{}/bytes", name), |b| {
+            b.iter(|| black_box(data).tasks().count())
+        });
+
+        group.throughput(Throughput::Elements(data.tasks().count() as u64));
+        group.bench_function(format!("{}/tasks", name), |b| {
+            b.iter(|| black_box(data).tasks().count())
+        });
+    }
+
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("task", |b| b.iter(|| black_box(B).tasks().next()));
+    group.bench_function("priority", |b| b.iter(|| black_box(C).tasks().next()));
+
+    // Compares parsing every task with scanning backward to the last one.
+    #[allow(clippy::double_ended_iterator_last)]
+    group.bench_function("last", |b| b.iter(|| black_box(&large).tasks().last()));
+    group.bench_function("last_task", |b| {
+        b.iter(|| black_box(&large).tasks().last_task())
     });
+
+    group.finish();
 }
 
-#[bench]
-fn task(bench: &mut Bencher) {
-    bench.iter(|| {
-        assert!(B.tasks().count() > 0);
+fn tags(c: &mut Criterion) {
+    let tasks: Vec<Task> = A.tasks().collect();
+    let count = tasks.iter().map(|task| task.tags().count()).sum::<usize>();
+    let mut group = c.benchmark_group("tags");
+
+    group.throughput(Throughput::Elements(count as u64));
+    group.bench_function("fixture", |b| {
+        b.iter(|| {
+            black_box(&tasks)
+                .iter()
+                .map(|task| task.tags().count())
+                .sum::<usize>()
+        })
     });
+
+    // A long description with a single tag at the end, where nearly all of the
+    // time is spent classifying words that are not tags.
+    let data = format!("{} @phone", "call mom about the meatballs ".repeat(20));
+    let task = data.tasks().next().unwrap();
+
+    group.throughput(Throughput::Bytes(task.description().len() as u64));
+    group.bench_function("long_untagged", |b| {
+        b.iter(|| black_box(&task).tags().count())
+    });
+
+    group.finish();
 }
 
-#[bench]
-#[cfg(feature = "rayon")]
-fn par_list(bench: &mut Bencher) {
-    bench.iter(|| {
-        assert!(A.par_tasks().count() > 0);
+fn display(c: &mut Criterion) {
+    let tasks: Vec<Task> = A.tasks().collect();
+    let mut group = c.benchmark_group("display");
+
+    group.throughput(Throughput::Elements(tasks.len() as u64));
+    group.bench_function("fixture", |b| {
+        let mut output = String::new();
+
+        b.iter(|| {
+            output.clear();
+
+            for task in black_box(&tasks) {
+                let _ = writeln!(output, "{}", task);
+            }
+
+            output.len()
+        })
+    });
+
+    group.finish();
+}
+
+fn stats(c: &mut Criterion) {
+    let large = large_input();
+    let today = NaiveDate::from_ymd_opt(2011, 3, 10).unwrap();
+    let mut group = c.benchmark_group("stats");
+
+    // Memory use of `from_reader` is flat in the size of the input, see
+    // `tests/stats.rs`.
+    group.throughput(Throughput::Bytes(large.len() as u64));
+    group.bench_function("from_reader", |b| {
+        b.iter(|| TaskStats::from_reader(Cursor::new(black_box(&large)), today).unwrap())
     });
+
+    group.finish();
 }
 
-#[bench]
 #[cfg(feature = "rayon")]
-fn par_task(bench: &mut Bencher) {
-    bench.iter(|| {
-        assert!(B.par_tasks().count() > 0);
+fn parallel(c: &mut Criterion) {
+    let large = large_input();
+    let mut group = c.benchmark_group("parallel");
+
+    group.throughput(Throughput::Bytes(large.len() as u64));
+    group.bench_function("lines", |b| {
+        b.iter(|| black_box(&large).par_tasks().count())
     });
+    group.bench_function("chunked", |b| {
+        b.iter(|| black_box(&large).par_tasks_chunked(1 << 20).count())
+    });
+
+    group.finish();
 }
+
+#[cfg(not(feature = "rayon"))]
+fn parallel(_: &mut Criterion) {}
+
+criterion_group!(benches, parse, tags, display, stats, parallel);
+criterion_main!(benches);
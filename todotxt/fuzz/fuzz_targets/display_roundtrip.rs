@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use todotxt::prelude::*;
+
+fuzz_target!(|task: Task<'static>| {
+    let line = task.to_string();
+    let parsed = line.tasks().next();
+
+    assert_eq!(parsed.as_ref(), Some(&task), "line: {:?}", line);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use todotxt::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+
+    for task in input.tasks() {
+        let once = task.to_string();
+        let twice = once.tasks().next().map(|task| task.to_string());
+
+        assert_eq!(twice.as_ref(), Some(&once), "input: {:?}", input);
+    }
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use todotxt::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+
+    for task in input.tasks().chain(input.tasks().rev()) {
+        let description = task.description();
+
+        let _ = task.completion_date();
+        let _ = task.creation_date();
+        let _ = task.is_complete();
+        let _ = task.priority();
+        let _ = task.state();
+        let _ = format!("{:?}", task);
+
+        for tag in task.tags() {
+            let _ = &description[tag];
+        }
+    }
+});
@@ -0,0 +1,91 @@
+use crate::{
+    date::Date,
+    priority::Priority,
+    task::{State, Task},
+};
+use std::borrow::Cow;
+
+/// Builds an incomplete task programmatically rather than parsing it.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{Date, TaskBuilder};
+///
+/// let today = Date::from_ymd_opt(2011, 3, 1).unwrap();
+/// let task = TaskBuilder::new("Call Mom @phone")
+///     .priority(Priority::A)
+///     .created_on(today)
+///     .build();
+///
+/// assert_eq!(task.to_string(), "(A) 2011-03-01 Call Mom @phone");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TaskBuilder {
+    creation_date: Option<Date>,
+    description: String,
+    priority: Option<Priority>,
+}
+
+impl TaskBuilder {
+    /// Returns a builder for a task with the provided description. Leading and
+    /// trailing whitespace is removed from the description.
+    pub fn new<T: Into<String>>(description: T) -> Self {
+        TaskBuilder {
+            creation_date: None,
+            description: description.into(),
+            priority: None,
+        }
+    }
+
+    /// Consumes the builder and returns the task.
+    pub fn build(self) -> Task<'static> {
+        let TaskBuilder {
+            creation_date,
+            mut description,
+            priority,
+        } = self;
+        let start = description.len() - description.trim_start().len();
+
+        description.truncate(description.trim_end().len());
+        description.drain(..start);
+
+        Task::from_parts(
+            State::Incomplete(priority, creation_date),
+            Cow::Owned(description),
+        )
+    }
+
+    /// Sets the creation date of the task to `today`.
+    ///
+    /// This is the deterministic counterpart of [`created_today`], for use in
+    /// tests or when "today" is not the current UTC date.
+    ///
+    /// [`created_today`]: #method.created_today
+    pub fn created_on(mut self, today: Date) -> Self {
+        self.creation_date = Some(today);
+        self
+    }
+
+    /// Sets the creation date of the task to the current date in UTC.
+    ///
+    /// Use [`created_on`] to supply a different date.
+    ///
+    /// [`created_on`]: #method.created_on
+    #[cfg(feature = "dates")]
+    pub fn created_today(self) -> Self {
+        self.created_on(crate::time::today_utc())
+    }
+
+    /// Sets the priority of the task.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    priority::Priority,
+    tags::Tag,
+    task::{split_special, write_without_controls, Task},
+};
+use std::{
+    env,
+    fmt::{self, Display, Formatter},
+};
+
+/// The ANSI styles used to color the parts of a task. See
+/// [`Task::display_colored`].
+///
+/// Each style is the parameter of an SGR escape sequence, such as `"31"` for
+/// red or `"1;34"` for bold blue. An empty style leaves that part of the task
+/// uncolored.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::ColorScheme;
+///
+/// let scheme = ColorScheme::ansi().context("35");
+/// let task = "(B) Call Mom @phone +Family".tasks().next().unwrap();
+///
+/// assert_eq!(
+///     task.display_colored(&scheme).to_string(),
+///     "\x1b[33m(B)\x1b[0m Call Mom \x1b[35m@phone\x1b[0m \x1b[34m+Family\x1b[0m"
+/// );
+/// # }
+/// ```
+///
+/// [`Task::display_colored`]: struct.Task.html#method.display_colored
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColorScheme {
+    complete: &'static str,
+    context: &'static str,
+    high_priority: &'static str,
+    overdue: &'static str,
+    priority: &'static str,
+    project: &'static str,
+}
+
+/// A task that is formatted with the styles of a color scheme.
+pub(crate) struct Colored<'t, 's> {
+    scheme: &'s ColorScheme,
+    task: &'t Task<'t>,
+}
+
+/// Formats text without the control characters that a task omits.
+struct Clean<'a>(&'a str);
+
+impl ColorScheme {
+    /// Returns the default color scheme, or a scheme without colors if the
+    /// `NO_COLOR` environment variable is set to a non-empty value. See
+    /// <https://no-color.org>.
+    pub fn new() -> Self {
+        match env::var_os("NO_COLOR") {
+            Some(value) if !value.is_empty() => ColorScheme::plain(),
+            _ => ColorScheme::ansi(),
+        }
+    }
+
+    /// Returns the default color scheme, regardless of the environment.
+    ///
+    /// Priority `(A)` is red and other priorities are yellow, projects are
+    /// blue, contexts are green, complete tasks are dim, and the `due:` tags
+    /// of overdue tasks are bold red.
+    pub fn ansi() -> Self {
+        ColorScheme {
+            complete: "2",
+            context: "32",
+            high_priority: "31",
+            overdue: "1;31",
+            priority: "33",
+            project: "34",
+        }
+    }
+
+    /// Returns a color scheme without colors, which formats tasks the same way
+    /// as their `Display` implementation.
+    pub fn plain() -> Self {
+        ColorScheme {
+            complete: "",
+            context: "",
+            high_priority: "",
+            overdue: "",
+            priority: "",
+            project: "",
+        }
+    }
+
+    /// Sets the style of complete tasks, which applies to the whole line.
+    pub fn complete(mut self, style: &'static str) -> Self {
+        self.complete = style;
+        self
+    }
+
+    /// Sets the style of contexts such as `@phone`.
+    pub fn context(mut self, style: &'static str) -> Self {
+        self.context = style;
+        self
+    }
+
+    /// Sets the style of the priority `(A)`.
+    pub fn high_priority(mut self, style: &'static str) -> Self {
+        self.high_priority = style;
+        self
+    }
+
+    /// Sets the style of the `due:` tags of overdue tasks. See
+    /// [`Task::is_overdue`].
+    ///
+    /// [`Task::is_overdue`]: struct.Task.html#method.is_overdue
+    pub fn overdue(mut self, style: &'static str) -> Self {
+        self.overdue = style;
+        self
+    }
+
+    /// Sets the style of priorities other than `(A)`.
+    pub fn priority(mut self, style: &'static str) -> Self {
+        self.priority = style;
+        self
+    }
+
+    /// Sets the style of projects such as `+GarageSale`.
+    pub fn project(mut self, style: &'static str) -> Self {
+        self.project = style;
+        self
+    }
+}
+
+impl<'t, 's> Colored<'t, 's> {
+    pub(crate) fn new(task: &'t Task<'t>, scheme: &'s ColorScheme) -> Self {
+        Colored { scheme, task }
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        ColorScheme::new()
+    }
+}
+
+impl<'a> Display for Clean<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_without_controls(f, self.0)
+    }
+}
+
+impl<'t, 's> Display for Colored<'t, 's> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Colored { scheme, task } = *self;
+
+        if task.is_complete() {
+            return paint(f, scheme.complete, task);
+        }
+
+        // An incomplete task has at most a priority and a creation date before
+        // its description.
+        let mut separator = "";
+
+        if let Some(priority) = task.priority() {
+            let style = match priority {
+                Priority::A => scheme.high_priority,
+                _ => scheme.priority,
+            };
+
+            paint(f, style, priority)?;
+            separator = " ";
+        }
+
+        if let Some(creation_date) = task.creation_date() {
+            write!(f, "{}{}", separator, creation_date)?;
+            separator = " ";
+        }
+
+        let description = task.description();
+
+        if description.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str(separator)?;
+
+        let overdue = is_overdue(task);
+        let mut end = 0;
+
+        for tag in task.tags() {
+            let style = match tag {
+                Tag::Context { .. } => scheme.context,
+                Tag::Project { .. } => scheme.project,
+                Tag::Special { .. } if overdue => match split_special(&description[tag]) {
+                    ("due", _) => scheme.overdue,
+                    _ => continue,
+                },
+                Tag::Special { .. } => continue,
+            };
+
+            write_without_controls(f, &description[end..tag.start()])?;
+            paint(f, style, Clean(&description[tag]))?;
+            end = tag.end();
+        }
+
+        write_without_controls(f, &description[end..])
+    }
+}
+
+#[cfg(feature = "dates")]
+fn is_overdue(task: &Task<'_>) -> bool {
+    task.is_overdue()
+}
+
+/// Overdue tasks are not highlighted without the `dates` feature, since the
+/// current date is not available.
+#[cfg(not(feature = "dates"))]
+fn is_overdue(_: &Task<'_>) -> bool {
+    false
+}
+
+/// Writes `value` wrapped in the escape sequences of `style`, unless the style
+/// is empty.
+fn paint<T: Display>(f: &mut Formatter<'_>, style: &str, value: T) -> fmt::Result {
+    if style.is_empty() {
+        write!(f, "{}", value)
+    } else {
+        write!(f, "\x1b[{}m{}\x1b[0m", style, value)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    date::Date,
+    priority::Priority,
+    task::{State, Task},
+};
+#[cfg(feature = "dates")]
+use chrono::Datelike;
+use std::borrow::Cow;
+
+/// A task that owns its description and stores its state in as few bytes as
+/// possible, for applications that keep many tasks in memory.
+///
+/// A compact task is 32 bytes on 64-bit targets, compared to 40 bytes for a
+/// [`Task`], and its description is a `Box<str>` without spare capacity. The
+/// state is stored as two bytes of flags and priority, and each date as the
+/// number of days since 1970-01-01.
+///
+/// Convert a task with [`Task::to_compact_repr`] or `From`, and convert it back
+/// with `From` to read it.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::CompactTask;
+///
+/// let data = "
+///     (A) 2011-03-01 Call Mom @phone
+///     x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+/// ";
+/// let compact: Vec<CompactTask> = data.tasks().map(CompactTask::from).collect();
+/// let tasks: Vec<Task> = compact.into_iter().map(Task::from).collect();
+///
+/// assert_eq!(tasks, data.tasks().collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// [`Task`]: struct.Task.html
+/// [`Task::to_compact_repr`]: struct.Task.html#method.to_compact_repr
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CompactTask {
+    completion_date: u32,
+    creation_date: u32,
+    description: Box<str>,
+    /// Whether the task is complete, and the index of its priority plus one.
+    flags: [u8; 2],
+}
+
+/// The bias added to the number of days since 1970-01-01, so that every date in
+/// range is positive and 0 can represent a missing date.
+const BIAS: i64 = 1 << 31;
+
+impl CompactTask {
+    /// Returns the description of the task.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl<'a> From<Task<'a>> for CompactTask {
+    fn from(task: Task<'a>) -> Self {
+        let (state, text) = task.into_parts();
+        let flags = match state {
+            State::Complete(_, _) => [1, 0],
+            State::Incomplete(priority, _) => [0, priority.map_or(0, |p| p as u8 + 1)],
+        };
+
+        CompactTask {
+            completion_date: encode(state.completion_date()),
+            creation_date: encode(state.creation_date()),
+            description: text.into_owned().into_boxed_str(),
+            flags,
+        }
+    }
+}
+
+impl From<CompactTask> for Task<'static> {
+    fn from(task: CompactTask) -> Self {
+        let creation_date = decode(task.creation_date);
+        let state = match task.flags {
+            [1, _] => State::Complete(decode(task.completion_date), creation_date),
+            [_, index] => State::Incomplete(
+                index.checked_sub(1).and_then(Priority::from_index),
+                creation_date,
+            ),
+        };
+
+        Task::from_parts(state, Cow::Owned(task.description.into_string()))
+    }
+}
+
+/// Returns the number of days from 1970-01-01 to `year`, `month`, and `day` in
+/// the proleptic Gregorian calendar.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn decode(value: u32) -> Option<Date> {
+    if value == 0 {
+        return None;
+    }
+
+    let (year, month, day) = civil_from_days(i64::from(value) - BIAS);
+
+    Date::from_ymd_opt(year as i32, month as u32, day as u32)
+}
+
+fn encode(date: Option<Date>) -> u32 {
+    date.map_or(0, |date| {
+        let days = days_from_civil(
+            i64::from(date.year()),
+            i64::from(date.month()),
+            i64::from(date.day()),
+        );
+
+        // Every supported date is within about 100 million days of 1970.
+        (days + BIAS) as u32
+    })
+}
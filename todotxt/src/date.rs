@@ -0,0 +1,414 @@
+use crate::parser::ParseErrorKind;
+#[cfg(all(feature = "dates", feature = "time"))]
+use chrono::Datelike;
+#[cfg(feature = "dates")]
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+#[cfg(feature = "time")]
+use std::convert::TryFrom;
+#[cfg(not(feature = "dates"))]
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// The date of a task. When the `dates` feature is enabled (the default),
+/// this is [`chrono::NaiveDate`].
+///
+/// [`chrono::NaiveDate`]: https://docs.rs/chrono/0.4/chrono/naive/struct.NaiveDate.html
+#[cfg(feature = "dates")]
+pub use chrono::NaiveDate as Date;
+
+/// Conversions between [`Date`] and the date types of other crates.
+///
+/// This trait allows the dates of a task to be read as another type with
+/// methods such as [`Task::creation_date_as`]. It is implemented for [`Date`]
+/// itself and, when the `time` feature is enabled, for [`time::Date`].
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{ConvertDate, Date};
+///
+/// let task = "2011-03-01 Call Mom".tasks().next().unwrap();
+///
+/// assert_eq!(task.creation_date_as::<Date>(), task.creation_date());
+/// # }
+/// ```
+///
+/// [`Date`]: crate::Date
+/// [`Task::creation_date_as`]: struct.Task.html#method.creation_date_as
+/// [`time::Date`]: https://docs.rs/time/0.3/time/struct.Date.html
+pub trait ConvertDate: Sized {
+    /// Converts `date` to `Self`, or returns `None` if it is out of the range
+    /// of `Self`.
+    fn from_date(date: Date) -> Option<Self>;
+
+    /// Converts `self` to a [`Date`], or returns `None` if it is out of the
+    /// range of `Date`.
+    ///
+    /// [`Date`]: crate::Date
+    fn into_date(self) -> Option<Date>;
+}
+
+/// The value of a `due:` tag, which may include a time of day, e.g.
+/// `due:2011-03-01` or `due:2011-03-01T17:00`.
+///
+/// This type is returned by [`Task::due_datetime`].
+///
+/// [`Task::due_datetime`]: struct.Task.html#method.due_datetime
+#[cfg(feature = "dates")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DueDate {
+    /// A task that is due at some point during the day.
+    Date(NaiveDate),
+    /// A task that is due at a specific time of day.
+    DateTime(NaiveDateTime),
+}
+
+/// The date of a task.
+///
+/// When the `dates` feature is disabled, dates are stored in this type rather
+/// than [`chrono::NaiveDate`] so that chrono is not compiled. The methods of
+/// this type are a subset of the methods of `NaiveDate` with the same
+/// signatures, so code that only constructs, compares, and formats dates
+/// compiles either way.
+///
+/// Note that enabling `dates` changes the type of every date in the public
+/// API. Libraries that are used alongside other crates should not disable it.
+///
+/// [`chrono::NaiveDate`]: https://docs.rs/chrono/0.4/chrono/naive/struct.NaiveDate.html
+#[cfg(not(feature = "dates"))]
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    year: i16,
+    month: u8,
+    day: u8,
+}
+
+#[cfg(not(feature = "dates"))]
+impl Date {
+    /// Returns the date with the provided year, month, and day, or `None` if
+    /// the date is invalid or the year is not between 0 and 9999.
+    pub fn from_ymd_opt(year: i32, month: u32, day: u32) -> Option<Date> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        if !(0..=9999).contains(&year) || day < 1 || day > days {
+            return None;
+        }
+
+        Some(Date {
+            year: year as i16,
+            month: month as u8,
+            day: day as u8,
+        })
+    }
+
+    /// Returns the day of the month, starting from 1.
+    pub fn day(&self) -> u32 {
+        u32::from(self.day)
+    }
+
+    /// Returns the month, starting from 1.
+    pub fn month(&self) -> u32 {
+        u32::from(self.month)
+    }
+
+    /// Returns the year.
+    pub fn year(&self) -> i32 {
+        i32::from(self.year)
+    }
+}
+
+#[cfg(feature = "dates")]
+impl DueDate {
+    /// Returns the day that the task is due, without the time.
+    pub fn date(&self) -> NaiveDate {
+        match *self {
+            DueDate::Date(date) => date,
+            DueDate::DateTime(datetime) => datetime.date(),
+        }
+    }
+}
+
+#[cfg(not(feature = "dates"))]
+impl Debug for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(not(feature = "dates"))]
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl ConvertDate for Date {
+    fn from_date(date: Date) -> Option<Self> {
+        Some(date)
+    }
+
+    fn into_date(self) -> Option<Date> {
+        Some(self)
+    }
+}
+
+/// The range of `time::Date` is the years -9999 to 9999. Without the `dates`
+/// feature, the range of `Date` is the years 0 to 9999.
+#[cfg(feature = "time")]
+impl ConvertDate for ::time::Date {
+    fn from_date(date: Date) -> Option<Self> {
+        let month = ::time::Month::try_from(date.month() as u8).ok()?;
+
+        ::time::Date::from_calendar_date(date.year(), month, date.day() as u8).ok()
+    }
+
+    fn into_date(self) -> Option<Date> {
+        Date::from_ymd_opt(
+            self.year(),
+            u8::from(self.month()).into(),
+            self.day().into(),
+        )
+    }
+}
+
+/// The parts of the value of a `due:` tag. See [`parse_due`].
+pub(crate) struct DueValue {
+    pub(crate) date: Date,
+    /// The offset from UTC in seconds.
+    pub(crate) offset: Option<i32>,
+    /// The hour, minute, and second.
+    pub(crate) time: Option<(u32, u32, u32)>,
+}
+
+/// Returns the reason that `token`, a word in the position of a date, is
+/// almost a date but not one, e.g. `2024/01/01` or `2024-1-1`. `None` is
+/// returned for valid dates and for words that do not look like dates at all.
+///
+/// The problems are checked in the order that they should be fixed, so
+/// `24/1/1` is reported as having the wrong separators.
+pub(crate) fn diagnose(token: &str) -> Option<ParseErrorKind> {
+    let mut groups = token.split(|c: char| !c.is_ascii_digit());
+    let (year, month, day) = (groups.next()?, groups.next()?, groups.next()?);
+
+    if groups.next().is_some()
+        || !matches!(year.len(), 2 | 4)
+        || !matches!(month.len(), 1 | 2)
+        || !matches!(day.len(), 1 | 2)
+    {
+        return None;
+    }
+
+    // Each group is followed by a single character, or the token would not
+    // have split into exactly three groups of digits.
+    let bytes = token.as_bytes();
+    let separators = [bytes[year.len()], bytes[year.len() + 1 + month.len()]];
+
+    if token.len() != year.len() + month.len() + day.len() + 2
+        || !separators.iter().all(|c| matches!(c, b'-' | b'/' | b'.'))
+    {
+        return None;
+    }
+
+    let (number, month_number, day_number): (i32, u32, u32) =
+        (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+
+    if separators != [b'-', b'-'] {
+        Some(ParseErrorKind::InvalidDateSeparator)
+    } else if year.len() == 2 {
+        Some(ParseErrorKind::TwoDigitYear)
+    } else if month.len() == 1 || day.len() == 1 {
+        Some(ParseErrorKind::UnpaddedDate)
+    } else if !(1..=12).contains(&month_number) {
+        Some(ParseErrorKind::InvalidMonth)
+    } else if Date::from_ymd_opt(number, month_number, day_number).is_none() {
+        Some(ParseErrorKind::InvalidDay)
+    } else {
+        None
+    }
+}
+
+/// Parses the value of a special tag such as `est:90m` as a duration.
+#[cfg(feature = "dates")]
+pub(crate) fn parse_duration(value: &str) -> Option<Duration> {
+    let (amount, minutes) = match *value.as_bytes().last()? {
+        b'm' => (&value[..value.len() - 1], 1),
+        b'h' => (&value[..value.len() - 1], 60),
+        b'd' => (&value[..value.len() - 1], 60 * 24),
+        b'w' => (&value[..value.len() - 1], 60 * 24 * 7),
+        _ => (value, 1),
+    };
+
+    if amount.is_empty() || !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let amount: i64 = amount.parse().ok()?;
+
+    Duration::try_minutes(amount.checked_mul(minutes)?)
+}
+
+/// Parses the value of a special tag such as `due:2011-03-01` as a date. The
+/// whole value must be a `YYYY-MM-DD` date, so `2011-3-1` is rejected whether
+/// or not the `dates` feature is enabled.
+pub(crate) fn parse_exact(value: &str) -> Option<Date> {
+    use crate::parser::Parse;
+
+    match Date::parse(value) {
+        Ok(("", date)) => Some(date),
+        _ => None,
+    }
+}
+
+/// Parses the value of a special tag as a date like `parse_exact`, returning
+/// the reason that chrono gives when the value is invalid.
+#[cfg(feature = "dates")]
+pub(crate) fn try_parse_exact(value: &str) -> Result<Date, chrono::ParseError> {
+    match parse_exact(value) {
+        Some(date) => Ok(date),
+        // chrono accepts dates that are not padded, such as `2011-3-1`, which
+        // are reported as invalid instead.
+        None => {
+            Date::parse_from_str(value, "%Y-%m-%d").and_then(|_| Date::parse_from_str("x", "%Y"))
+        }
+    }
+}
+
+/// Parses the value of a `due:` tag such as `2011-03-01`, `2011-03-01T17:00`,
+/// or `2011-03-01T17:00:30+02:00`. Both `T` and `_` separate the date from
+/// the time. The time may be followed by `Z` or an offset from UTC.
+pub(crate) fn parse_due(value: &str) -> Option<DueValue> {
+    let (date, time) = match value.as_bytes().get(10) {
+        Some(b'T') | Some(b'_') => (&value[..10], Some(&value[11..])),
+        _ => (value, None),
+    };
+    let mut due = DueValue {
+        date: parse_exact(date)?,
+        offset: None,
+        time: None,
+    };
+
+    if let Some(time) = time {
+        let (time, offset) = split_offset(time);
+
+        due.time = Some(parse_time(time)?);
+
+        if let Some(offset) = offset {
+            due.offset = Some(parse_offset(offset)?);
+        }
+    }
+
+    Some(due)
+}
+
+/// Parses an offset from UTC in the format `Z` or `+HH:MM`, returning the
+/// offset in seconds.
+fn parse_offset(value: &str) -> Option<i32> {
+    let (sign, hour, minute) = match *value.as_bytes() {
+        [b'Z'] => return Some(0),
+        [b'+', h0, h1, b':', m0, m1] => (1, two_digits(h0, h1, 24)?, two_digits(m0, m1, 60)?),
+        [b'-', h0, h1, b':', m0, m1] => (-1, two_digits(h0, h1, 24)?, two_digits(m0, m1, 60)?),
+        _ => return None,
+    };
+
+    Some(sign * (hour * 3600 + minute * 60) as i32)
+}
+
+/// Parses a time of day in the format `HH:MM` or `HH:MM:SS`, returning the
+/// hour, minute, and second.
+fn parse_time(value: &str) -> Option<(u32, u32, u32)> {
+    let (hour, minute, second) = match *value.as_bytes() {
+        [h0, h1, b':', m0, m1] => (two_digits(h0, h1, 24)?, two_digits(m0, m1, 60)?, 0),
+        [h0, h1, b':', m0, m1, b':', s0, s1] => (
+            two_digits(h0, h1, 24)?,
+            two_digits(m0, m1, 60)?,
+            two_digits(s0, s1, 60)?,
+        ),
+        _ => return None,
+    };
+
+    Some((hour, minute, second))
+}
+
+/// Splits a time such as `17:00+02:00` into the time of day and the offset
+/// from UTC, if any.
+fn split_offset(value: &str) -> (&str, Option<&str>) {
+    let bytes = value.as_bytes();
+    let start = match bytes.last() {
+        Some(b'Z') => bytes.len() - 1,
+        _ if bytes.len() >= 6 && matches!(bytes[bytes.len() - 6], b'+' | b'-') => bytes.len() - 6,
+        _ => return (value, None),
+    };
+
+    (&value[..start], Some(&value[start..]))
+}
+
+/// Parses two ASCII digits as a number that is less than `max`.
+fn two_digits(tens: u8, ones: u8, max: u32) -> Option<u32> {
+    if !tens.is_ascii_digit() || !ones.is_ascii_digit() {
+        return None;
+    }
+
+    let value = u32::from(tens - b'0') * 10 + u32::from(ones - b'0');
+
+    if value < max {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[cfg(all(feature = "serde", not(feature = "dates")))]
+mod serialization {
+    use super::{parse_exact, Date};
+    use serde::{
+        de::{self, Deserializer, Visitor},
+        Deserialize, Serialize, Serializer,
+    };
+    use std::fmt::{self, Formatter};
+
+    impl<'de> Deserialize<'de> for Date {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct DateVisitor;
+
+            impl<'de> Visitor<'de> for DateVisitor {
+                type Value = Date;
+
+                fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.write_str("a date in the format YYYY-MM-DD")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    parse_exact(value).ok_or_else(|| E::custom("invalid date"))
+                }
+            }
+
+            deserializer.deserialize_str(DateVisitor)
+        }
+    }
+
+    impl Serialize for Date {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+}
@@ -31,18 +31,64 @@
 //! }
 //! ```
 //!
+//! ## Dates
+//!
+//! Dates are represented with [chrono] by default. Disabling the default
+//! `dates` feature removes the dependency on chrono, which is useful when
+//! binary size matters, e.g. when compiling to WebAssembly. Without it, dates
+//! are stored as a small [`Date`] type and the methods that depend on the
+//! current date, such as [`Task::is_available`], and [`TaskStats`] are not
+//! available. Those methods read the current date through the [`time`]
+//! module, which allows tests to fix it.
+//!
+//! Enabling the `time` feature allows the dates of a task to be read as
+//! [`time::Date`] through the [`ConvertDate`] trait, e.g. with
+//! [`Task::creation_date_as`].
+//!
+//! ## Threads
+//!
+//! Tasks, their tags, and the iterators that parse them are `Send` and
+//! `Sync`, so a [`Task<'static>`] can be moved to another thread, and tasks
+//! that borrow from their input can be shared with scoped threads. The same
+//! goes for [`TodoList`], [`TaskBuilder`], [`CompactTask`], and the errors of
+//! strict mode. A [`ProgressIter`] is the exception, since its callback is not
+//! required to be `Send`.
+//!
+//! [chrono]: https://docs.rs/chrono
+//! [`ConvertDate`]: crate::ConvertDate
+//! [`Date`]: crate::Date
+//! [`CompactTask`]: struct.CompactTask.html
+//! [`ProgressIter`]: parser/struct.ProgressIter.html
+//! [`Task<'static>`]: struct.Task.html
+//! [`TaskBuilder`]: struct.TaskBuilder.html
+//! [`TodoList`]: struct.TodoList.html
+//! [`Task::is_available`]: struct.Task.html#method.is_available
+//! [`Task::creation_date_as`]: struct.Task.html#method.creation_date_as
+//! [`TaskStats`]: struct.TaskStats.html
+//! [`time`]: time/index.html
+//! [`time::Date`]: https://docs.rs/time/0.3/time/struct.Date.html
 //! [todo.txt]: http://todotxt.org/
 
 #![deny(missing_docs)]
 
-#[macro_use]
-extern crate nom;
-
-mod priority;
+mod builder;
+#[cfg(feature = "color")]
+mod color;
+mod compact;
+mod date;
+mod list;
+mod recurrence;
+mod sort;
+#[cfg(feature = "dates")]
+mod stats;
 mod tags;
 mod task;
 
 pub mod parser;
+pub mod priority;
+pub mod report;
+#[cfg(feature = "dates")]
+pub mod time;
 
 #[cfg(not(feature = "rayon"))]
 pub mod prelude {
@@ -54,7 +100,12 @@ pub mod prelude {
     //! use todotxt::prelude::*;
     //! ```
 
-    pub use crate::{parser::Input, priority::Priority, tags::Tag, task::Task};
+    pub use crate::{
+        parser::{Input, ReadInput},
+        priority::Priority,
+        tags::Tag,
+        task::Task,
+    };
 }
 
 #[cfg(feature = "rayon")]
@@ -68,7 +119,7 @@ pub mod prelude {
     //! ```
 
     pub use crate::{
-        parser::{Input, ParallelInput},
+        parser::{Input, ParallelInput, ReadInput},
         priority::Priority,
         tags::Tag,
         task::Task,
@@ -76,9 +127,29 @@ pub mod prelude {
     pub use rayon::iter::ParallelIterator;
 }
 
+#[cfg(feature = "color")]
+pub use crate::color::ColorScheme;
+#[cfg(feature = "serde")]
+pub use crate::task::{Case, WithCase};
 pub use crate::{
+    builder::TaskBuilder,
+    compact::CompactTask,
+    date::{ConvertDate, Date},
+    list::{LineEnding, TodoList, WriteOptions},
     priority::Priority,
+    recurrence::{Recurrence, RecurrenceUnit},
+    sort::{SortKey, ValueKind},
     tags::{Tag, Tags},
-    task::{State, Task},
+    task::{InvalidState, State, Task},
+};
+#[cfg(feature = "dates")]
+pub use crate::{
+    date::DueDate,
+    list::default_scorer,
+    stats::{DurationSum, TaskStats},
 };
+#[cfg(feature = "dates")]
 pub use chrono;
+pub use nom;
+#[cfg(feature = "regex")]
+pub use regex;
@@ -0,0 +1,916 @@
+use crate::{
+    date::Date,
+    parser::{decode_lossy, parse_line},
+    sort::SortKey,
+    task::Task,
+};
+#[cfg(feature = "dates")]
+use crate::{stats::TaskStats, time};
+use memchr::memchr_iter;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    iter::FromIterator,
+    ops::{Deref, DerefMut},
+    path::Path,
+    slice, vec,
+};
+
+/// The line break that separates the lines of a todo.txt file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum LineEnding {
+    /// `\r\n`, as written by most Windows tools.
+    CrLf,
+    /// `\n`, which is the default.
+    #[default]
+    Lf,
+}
+
+/// An ordered list of tasks.
+///
+/// A todo list dereferences to a slice of tasks, so the methods of [`slice`]
+/// such as `len`, `get`, and `iter` are available as well as the methods
+/// defined below.
+///
+/// A list that is read from a file remembers the line ending of the file and
+/// whether it ends with a line break, so that writing the list back with
+/// [`write_todo_txt`] keeps them. Other lists are written with `\n` after
+/// every task. It also remembers the lines of the file as they were written,
+/// which [`WriteOptions::keep_unmodified_lines`] writes back in place of the
+/// tasks that were not modified.
+///
+/// Two lists are equal if their tasks are equal, regardless of how they are
+/// written.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::TodoList;
+///
+/// let data = "
+///     (A) Thank Mom for the meatballs @phone
+///     (B) Schedule Goodwill pickup +GarageSale @phone
+/// ";
+/// let list: TodoList = data.tasks().collect();
+///
+/// assert_eq!(list.len(), 2);
+/// assert_eq!(list[1].priority(), Some(Priority::B));
+/// # }
+/// ```
+///
+/// [`WriteOptions::keep_unmodified_lines`]: struct.WriteOptions.html#method.keep_unmodified_lines
+/// [`slice`]: https://doc.rust-lang.org/std/primitive.slice.html
+/// [`write_todo_txt`]: #method.write_todo_txt
+#[derive(Clone, Debug)]
+pub struct TodoList<'a> {
+    line_ending: LineEnding,
+    /// The lines of the file that the list was read from, keyed by how their
+    /// tasks are formatted.
+    raw_lines: HashMap<String, VecDeque<String>>,
+    tasks: Vec<Task<'a>>,
+    trailing_newline: bool,
+}
+
+/// Options that override how [`TodoList::write_todo_txt_with`] writes a list.
+/// By default, the line ending and trailing line break of the list are used,
+/// and every task is formatted as it is by its `Display` implementation.
+///
+/// ## Example
+///
+/// ```no_run
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() -> std::io::Result<()> {
+/// use std::path::Path;
+/// use todotxt::{LineEnding, TodoList, WriteOptions};
+///
+/// let list = TodoList::read_todo_txt(Path::new("todo.txt"))?;
+/// let options = WriteOptions::new().line_ending(LineEnding::Lf);
+///
+/// list.write_todo_txt_with(Path::new("todo.txt"), options)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`TodoList::write_todo_txt_with`]: struct.TodoList.html#method.write_todo_txt_with
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WriteOptions {
+    keep_unmodified_lines: bool,
+    line_ending: Option<LineEnding>,
+    trailing_newline: Option<bool>,
+}
+
+impl LineEnding {
+    /// Returns the line ending that most of the lines of `data` end with.
+    ///
+    /// A file with both line endings is written back with the one that most
+    /// of its lines use, so that the fewest lines change. Ties, and data
+    /// without any line breaks, are detected as `\n`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # fn main() {
+    /// use todotxt::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::detect("Call Mom\r\nPay rent\r\n"), LineEnding::CrLf);
+    /// assert_eq!(LineEnding::detect("Call Mom\r\nPay rent\nFile taxes\n"), LineEnding::Lf);
+    /// assert_eq!(LineEnding::detect("Call Mom"), LineEnding::Lf);
+    /// # }
+    /// ```
+    pub fn detect(data: &str) -> LineEnding {
+        let bytes = data.as_bytes();
+        let (mut crlf, mut lf) = (0usize, 0usize);
+
+        for index in memchr_iter(b'\n', bytes) {
+            if index > 0 && bytes[index - 1] == b'\r' {
+                crlf += 1;
+            } else {
+                lf += 1;
+            }
+        }
+
+        if crlf > lf {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// Returns the line break as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Lf => "\n",
+        }
+    }
+}
+
+impl<'a> TodoList<'a> {
+    /// Creates an empty todo list.
+    pub fn new() -> Self {
+        TodoList::from(Vec::new())
+    }
+
+    /// Appends `tasks` to the todo.txt file at `path` without rewriting the
+    /// tasks that it already contains, creating the file if it does not
+    /// exist. Each task is written on its own line, and a line break is added
+    /// first if the file does not end with one.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::path::Path;
+    /// use todotxt::TodoList;
+    ///
+    /// let tasks: Vec<Task> = "(A) Call Mom @phone".tasks().collect();
+    ///
+    /// TodoList::append_todo_txt(Path::new("todo.txt"), &tasks)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn append_todo_txt(path: &Path, tasks: &[Task<'_>]) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .read(true)
+            .open(path)?;
+        let mut last = [b'\n'];
+
+        if file.metadata()?.len() > 0 {
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+        }
+
+        let mut writer = BufWriter::new(file);
+
+        if last != *b"\n" {
+            writer.write_all(b"\n")?;
+        }
+
+        for task in tasks {
+            writeln!(writer, "{}", task)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Groups the tasks in the list by context, mapping each context, such as
+    /// `@phone`, to the indices of the tasks that have it, in the order of the
+    /// list. Contexts are compared case-sensitively and the map is sorted by
+    /// context.
+    ///
+    /// A task with several contexts appears in the group of each, but only
+    /// once in a group that it has more than once. Tasks without a context do
+    /// not appear in any group.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::TodoList;
+    ///
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     Pick up milk @GroceryStore @errands
+    ///     Post signs around the neighborhood +GarageSale
+    ///     Schedule Goodwill pickup @phone @errands
+    /// ";
+    /// let list: TodoList = data.tasks().collect();
+    /// let groups = list.by_context();
+    ///
+    /// assert_eq!(groups["@phone"], [0, 3]);
+    /// assert_eq!(groups["@errands"], [1, 3]);
+    /// assert_eq!(groups.keys().collect::<Vec<_>>(), ["@GroceryStore", "@errands", "@phone"]);
+    /// # }
+    /// ```
+    pub fn by_context(&self) -> BTreeMap<String, Vec<usize>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+
+        for (index, task) in self.tasks.iter().enumerate() {
+            for context in task.contexts_iter() {
+                let group = groups.entry(context.to_owned()).or_default();
+
+                if group.last() != Some(&index) {
+                    group.push(index);
+                }
+            }
+        }
+
+        groups
+    }
+
+    /// Deserializes a list from a JSON array of tasks in the format produced
+    /// by [`to_json_array`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::TodoList;
+    ///
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    /// ";
+    /// let list: TodoList = data.tasks().collect();
+    /// let json = list.to_json_array().unwrap();
+    ///
+    /// assert_eq!(TodoList::from_json_array(&json).unwrap(), list);
+    /// assert!(TodoList::from_json_array("{}").is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`to_json_array`]: #method.to_json_array
+    #[cfg(feature = "serde")]
+    pub fn from_json_array(json: &str) -> serde_json::Result<TodoList<'static>> {
+        serde_json::from_str::<Vec<Task<'static>>>(json).map(TodoList::from)
+    }
+
+    /// Returns a new occurrence of each template task in the list, created on
+    /// `date`. See [`Task::instantiate`] and [`Task::is_template`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{Date, TodoList};
+    ///
+    /// let data = "
+    ///     Water the plants h:1 rec:1w @home
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Pay rent h:1 rec:+1m
+    /// ";
+    /// let list: TodoList = data.tasks().collect();
+    /// let tasks: Vec<String> = list
+    ///     .instantiate_templates(Date::from_ymd_opt(2011, 3, 1).unwrap())
+    ///     .iter()
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(tasks, ["2011-03-01 Water the plants @home", "(B) 2011-03-01 Pay rent"]);
+    /// # }
+    /// ```
+    ///
+    /// [`Task::instantiate`]: struct.Task.html#method.instantiate
+    /// [`Task::is_template`]: struct.Task.html#method.is_template
+    pub fn instantiate_templates(&self, date: Date) -> Vec<Task<'static>> {
+        self.templates()
+            .map(|task| task.instantiate(date))
+            .collect()
+    }
+
+    /// Consumes the list and splits it into an iterator over the complete tasks
+    /// and a list of the remaining incomplete tasks. Both preserve the
+    /// original order of the tasks.
+    ///
+    /// This is useful when archiving complete tasks to a done.txt file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::TodoList;
+    ///
+    /// let data = "
+    ///     x 2011-03-03 Call Mom
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     x Review Tim's pull request +TodoTxtTouch @github
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    /// let list: TodoList = data.tasks().collect();
+    /// let (complete, list) = list.into_iter_completed();
+    /// let complete: Vec<String> = complete.map(|task| task.to_string()).collect();
+    ///
+    /// assert_eq!(complete, [
+    ///     "x 2011-03-03 Call Mom",
+    ///     "x Review Tim's pull request +TodoTxtTouch @github",
+    /// ]);
+    /// assert_eq!(list.len(), 2);
+    /// assert!(list.iter().all(|task| !task.is_complete()));
+    /// # }
+    /// ```
+    pub fn into_iter_completed(self) -> (vec::IntoIter<Task<'a>>, TodoList<'a>) {
+        let (complete, incomplete): (Vec<_>, Vec<_>) =
+            self.tasks.into_iter().partition(Task::is_complete);
+
+        let list = TodoList {
+            tasks: incomplete,
+            ..self
+        };
+
+        (complete.into_iter(), list)
+    }
+
+    /// Consumes the list and returns the tasks it contains.
+    pub fn into_vec(self) -> Vec<Task<'a>> {
+        self.tasks
+    }
+
+    /// Returns the line ending that the list is written with. A list that is
+    /// read from a file uses the line ending of the file. See
+    /// [`LineEnding::detect`].
+    ///
+    /// [`LineEnding::detect`]: enum.LineEnding.html#method.detect
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Removes the task at `index`, marks it complete on `completion_date`,
+    /// and returns it, shifting the tasks after it down by one. `None` is
+    /// returned if `index` is out of bounds.
+    ///
+    /// The returned task owns its description, so it can be appended to a
+    /// separate list of done tasks. See [`Task::complete_on`] for how the
+    /// task is completed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{Date, TodoList};
+    ///
+    /// let data = "
+    ///     (A) 2011-03-01 Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    /// let date = Date::from_ymd_opt(2011, 3, 3).unwrap();
+    /// let mut list: TodoList = data.tasks().collect();
+    /// let mut done = TodoList::new();
+    ///
+    /// done.push(list.move_to_done(0, date).unwrap());
+    ///
+    /// assert!(done[0].is_complete());
+    /// assert_eq!(done[0].to_string(), "x 2011-03-03 2011-03-01 Thank Mom for the meatballs @phone");
+    /// assert_eq!(list.len(), 2);
+    /// assert_eq!(list[0].priority(), Some(Priority::B));
+    /// assert_eq!(list[1].description(), "Post signs around the neighborhood +GarageSale");
+    /// assert_eq!(list.move_to_done(2, date), None);
+    /// # }
+    /// ```
+    ///
+    /// [`Task::complete_on`]: struct.Task.html#method.complete_on
+    pub fn move_to_done(&mut self, index: usize, completion_date: Date) -> Option<Task<'static>> {
+        if index >= self.tasks.len() {
+            return None;
+        }
+
+        let mut task = self.tasks.remove(index);
+
+        task.complete_on(completion_date);
+        Some(task.into_owned())
+    }
+
+    /// Appends a task to the end of the list.
+    pub fn push(&mut self, task: Task<'a>) {
+        self.tasks.push(task);
+    }
+
+    /// Reads and parses the todo.txt file at `path`. The tasks own their
+    /// descriptions, so the list does not borrow from the contents of the
+    /// file. The list remembers the line ending of the file and whether it
+    /// ends with a line break.
+    ///
+    /// An error is returned if reading the file fails, or with the kind
+    /// `InvalidData` if a line that is not blank can't be parsed, such as `(B`,
+    /// since writing the list back would lose the line. A file that is not
+    /// UTF-8, such as one saved as Windows-1252, is decoded with
+    /// [`decode_lossy`].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::path::Path;
+    /// use todotxt::TodoList;
+    ///
+    /// let list = TodoList::read_todo_txt(Path::new("todo.txt"))?;
+    ///
+    /// for task in list.iter().filter(|task| !task.is_complete()) {
+    ///     println!("{}", task);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`decode_lossy`]: parser/fn.decode_lossy.html
+    pub fn read_todo_txt(path: &Path) -> io::Result<TodoList<'static>> {
+        let bytes = fs::read(path)?;
+        let data = decode_lossy(&bytes);
+        let mut raw_lines = HashMap::<_, VecDeque<_>>::new();
+        let mut tasks = Vec::new();
+
+        // Each line is parsed on its own, which yields the same tasks as
+        // parsing the whole file while pairing every task with its line.
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let task = match parse_line(line.trim(), || index + 1) {
+                Some(task) => task,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: the line is not a task", index + 1),
+                    ))
+                }
+            };
+
+            raw_lines
+                .entry(task.to_string())
+                .or_default()
+                .push_back(line.to_owned());
+            tasks.push(task.into_owned());
+        }
+
+        Ok(TodoList {
+            line_ending: LineEnding::detect(&data),
+            raw_lines,
+            tasks,
+            trailing_newline: data.is_empty() || data.ends_with('\n'),
+        })
+    }
+
+    /// Returns the tasks in the list paired with the score that `scorer`
+    /// assigns to each, sorted from the highest score to the lowest. Tasks
+    /// with equal scores keep their order in the list. See [`Task::score`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::TodoList;
+    ///
+    /// let data = "
+    ///     Call Mom @phone
+    ///     Pay rent +Chores @home
+    ///     File taxes +Chores
+    /// ";
+    /// let list: TodoList = data.tasks().collect();
+    /// let ranked: Vec<(i64, &str)> = list
+    ///     .ranked(|task| task.projects_iter().count() as i64 + task.contexts_iter().count() as i64)
+    ///     .into_iter()
+    ///     .map(|(score, task)| (score, task.description()))
+    ///     .collect();
+    ///
+    /// assert_eq!(ranked, [
+    ///     (2, "Pay rent +Chores @home"),
+    ///     (1, "Call Mom @phone"),
+    ///     (1, "File taxes +Chores"),
+    /// ]);
+    /// # }
+    /// ```
+    ///
+    /// [`Task::score`]: struct.Task.html#method.score
+    pub fn ranked<F>(&self, scorer: F) -> Vec<(i64, &Task<'a>)>
+    where
+        F: Fn(&Task<'_>) -> i64,
+    {
+        let mut ranked: Vec<_> = self
+            .tasks
+            .iter()
+            .map(|task| (task.score(&scorer), task))
+            .collect();
+
+        ranked.sort_by_key(|&(score, _)| Reverse(score));
+        ranked
+    }
+
+    /// Shifts the priority of every task in the list by `amount` steps and
+    /// returns the number of tasks whose priority changed. A negative amount
+    /// raises the priorities, e.g. `-1` promotes `(B)` to `(A)` and `(C)` to
+    /// `(B)`, and a positive amount lowers them.
+    ///
+    /// Priorities are clamped rather than wrapped around, so `(A)` stays `(A)`
+    /// when the priorities are raised and `(Z)` stays `(Z)` when they are
+    /// lowered. Tasks without a priority, including complete tasks, are left
+    /// unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::TodoList;
+    ///
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (C) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    /// let mut list: TodoList = data.tasks().collect();
+    ///
+    /// assert_eq!(list.rotate_priorities(-1), 1);
+    /// assert_eq!(list[0].priority(), Some(Priority::A));
+    /// assert_eq!(list[1].priority(), Some(Priority::B));
+    /// assert_eq!(list[2].priority(), None);
+    /// # }
+    /// ```
+    pub fn rotate_priorities(&mut self, amount: i8) -> usize {
+        let mut changed = 0;
+
+        for task in &mut self.tasks {
+            if let Some(priority) = task.priority() {
+                let shifted = priority.shift(amount);
+
+                if shifted != priority {
+                    task.set_priority(Some(shifted));
+                    changed += 1;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Sorts the list by `keys`, where each key breaks the ties of the keys
+    /// before it. The sort is stable, so tasks that are equal by every key keep
+    /// their order. See [`SortKey`].
+    ///
+    /// [`SortKey`]: enum.SortKey.html
+    pub fn sort_by_keys(&mut self, keys: &[SortKey]) {
+        self.tasks.sort_by(|a, b| SortKey::compare_all(keys, a, b));
+    }
+
+    /// Computes statistics about the tasks in the list. The age of a task is
+    /// relative to the current local date.
+    ///
+    /// See [`TaskStats::from_tasks`] to compute statistics relative to a
+    /// specific date.
+    ///
+    /// [`TaskStats::from_tasks`]: struct.TaskStats.html#method.from_tasks
+    #[cfg(feature = "dates")]
+    pub fn statistics(&self) -> TaskStats {
+        TaskStats::from_tasks(self.iter(), time::today())
+    }
+
+    /// Returns an iterator over the template tasks in the list. See
+    /// [`Task::is_template`].
+    ///
+    /// [`Task::is_template`]: struct.Task.html#method.is_template
+    pub fn templates(&self) -> impl Iterator<Item = &Task<'a>> + '_ {
+        self.tasks.iter().filter(|task| task.is_template())
+    }
+
+    /// Serializes the list as a JSON array of tasks with snake case field
+    /// names.
+    #[cfg(feature = "serde")]
+    pub fn to_json_array(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&self.tasks)
+    }
+
+    /// Returns `true` if the list is written with a line break after its last
+    /// task. A list that is read from a file does if the file does.
+    pub fn trailing_newline(&self) -> bool {
+        self.trailing_newline
+    }
+
+    /// Writes the list to the todo.txt file at `path`, with each task on its
+    /// own line, replacing the file if it exists. The lines end with the line
+    /// ending of the list, and the last line ends with one if
+    /// [`trailing_newline`] is `true`.
+    ///
+    /// The list is written to a temporary file in the same directory, which is
+    /// then renamed to `path`. The file at `path` is therefore either left
+    /// as it was or replaced with the complete list, even if writing fails
+    /// partway through.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::path::Path;
+    /// use todotxt::TodoList;
+    ///
+    /// let mut list = TodoList::read_todo_txt(Path::new("todo.txt"))?;
+    ///
+    /// list.sort_by_key(|task| task.is_complete());
+    /// list.write_todo_txt(Path::new("todo.txt"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`trailing_newline`]: #method.trailing_newline
+    pub fn write_todo_txt(&self, path: &Path) -> io::Result<()> {
+        self.write_todo_txt_with(path, WriteOptions::default())
+    }
+
+    /// Writes the list to the todo.txt file at `path` like [`write_todo_txt`],
+    /// with `options` in place of the line ending and trailing line break of
+    /// the list.
+    ///
+    /// [`write_todo_txt`]: #method.write_todo_txt
+    pub fn write_todo_txt_with(&self, path: &Path, options: WriteOptions) -> io::Result<()> {
+        let line_ending = options.line_ending.unwrap_or(self.line_ending).as_str();
+        let trailing_newline = options.trailing_newline.unwrap_or(self.trailing_newline);
+        let mut raw_lines = match options.keep_unmodified_lines {
+            true => self.raw_lines.clone(),
+            false => HashMap::new(),
+        };
+        let name = path.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "the path has no file name")
+        })?;
+        let mut temporary = name.to_os_string();
+
+        temporary.push(".tmp");
+
+        let temporary = path.with_file_name(temporary);
+        let result = File::create(&temporary).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+
+            for (index, task) in self.tasks.iter().enumerate() {
+                if index > 0 {
+                    writer.write_all(line_ending.as_bytes())?;
+                }
+
+                if raw_lines.is_empty() {
+                    write!(writer, "{}", task)?;
+                    continue;
+                }
+
+                // A task is unmodified if it is formatted the same as the
+                // task that was parsed from one of the lines of the file.
+                let line = task.to_string();
+
+                match raw_lines.get_mut(&line).and_then(VecDeque::pop_front) {
+                    Some(raw) => writer.write_all(raw.as_bytes())?,
+                    None => writer.write_all(line.as_bytes())?,
+                }
+            }
+
+            if trailing_newline && !self.tasks.is_empty() {
+                writer.write_all(line_ending.as_bytes())?;
+            }
+
+            writer.into_inner()?.sync_all()
+        });
+
+        match result.and_then(|_| fs::rename(&temporary, path)) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                let _ = fs::remove_file(&temporary);
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<'a> Deref for TodoList<'a> {
+    type Target = [Task<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.tasks
+    }
+}
+
+impl<'a> DerefMut for TodoList<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tasks
+    }
+}
+
+impl<'a> Eq for TodoList<'a> {}
+
+impl<'a> Default for TodoList<'a> {
+    fn default() -> Self {
+        TodoList::new()
+    }
+}
+
+impl<'a> From<Vec<Task<'a>>> for TodoList<'a> {
+    fn from(tasks: Vec<Task<'a>>) -> Self {
+        TodoList {
+            line_ending: LineEnding::Lf,
+            raw_lines: HashMap::new(),
+            tasks,
+            trailing_newline: true,
+        }
+    }
+}
+
+impl<'a> FromIterator<Task<'a>> for TodoList<'a> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Task<'a>>,
+    {
+        TodoList::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<'a> IntoIterator for TodoList<'a> {
+    type IntoIter = vec::IntoIter<Task<'a>>;
+    type Item = Task<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tasks.into_iter()
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b TodoList<'a> {
+    type IntoIter = slice::Iter<'b, Task<'a>>;
+    type Item = &'b Task<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tasks.iter()
+    }
+}
+
+impl<'a, 'b> PartialEq<TodoList<'b>> for TodoList<'a> {
+    fn eq(&self, other: &TodoList<'b>) -> bool {
+        self.tasks == other.tasks
+    }
+}
+
+impl WriteOptions {
+    /// Returns options that keep the line ending and trailing line break of
+    /// the list.
+    pub fn new() -> Self {
+        WriteOptions::default()
+    }
+
+    /// Sets whether the tasks that were not modified since the list was read
+    /// from a file are written as the lines they were read from, including
+    /// their indentation and trailing whitespace, so that rewriting a file
+    /// only changes the lines of the tasks that were modified.
+    ///
+    /// A task counts as modified if it is formatted differently than the task
+    /// parsed from its line. Tasks that were added to the list, and every task
+    /// of a list that was not read from a file, are formatted as usual.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::path::Path;
+    /// use todotxt::{TodoList, WriteOptions};
+    ///
+    /// let mut list = TodoList::read_todo_txt(Path::new("todo.txt"))?;
+    /// let options = WriteOptions::new().keep_unmodified_lines(true);
+    ///
+    /// list[0] = "(A) Call Mom @phone".tasks().next().unwrap().into_owned();
+    /// list.write_todo_txt_with(Path::new("todo.txt"), options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn keep_unmodified_lines(mut self, keep_unmodified_lines: bool) -> Self {
+        self.keep_unmodified_lines = keep_unmodified_lines;
+        self
+    }
+
+    /// Sets the line ending to write the list with.
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = Some(line_ending);
+        self
+    }
+
+    /// Sets whether a line break is written after the last task.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = Some(trailing_newline);
+        self
+    }
+}
+
+/// A reference scorer for [`Task::score`] and [`TodoList::ranked`] that
+/// combines the priority, urgency, and age of a task, relative to the current
+/// local date.
+///
+/// - A priority scores from 26 for `(A)` to 1 for `(Z)`.
+/// - An overdue task scores 100 more, so it ranks above every task that is not
+///   overdue. See [`Task::is_overdue`].
+/// - Each full week since the creation date scores 1 more, up to 25, so that
+///   age breaks ties without outweighing a higher priority by itself.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{chrono::NaiveDate, default_scorer, time, TodoList};
+///
+/// time::set_test_date(NaiveDate::from_ymd_opt(2011, 3, 15).unwrap());
+///
+/// let data = "
+///     (B) 2011-03-01 Schedule Goodwill pickup +GarageSale @phone
+///     (A) Pay rent due:2011-03-01
+///     Post signs around the neighborhood +GarageSale
+/// ";
+/// let list: TodoList = data.tasks().collect();
+/// let scores: Vec<i64> = list.ranked(default_scorer).iter().map(|(score, _)| *score).collect();
+///
+/// assert_eq!(scores, [126, 27, 0]);
+/// # }
+/// ```
+///
+/// [`Task::score`]: struct.Task.html#method.score
+/// [`TodoList::ranked`]: struct.TodoList.html#method.ranked
+/// [`Task::is_overdue`]: struct.Task.html#method.is_overdue
+#[cfg(feature = "dates")]
+pub fn default_scorer(task: &Task<'_>) -> i64 {
+    let priority = task.priority().map_or(0, |priority| 26 - priority as i64);
+    let urgency = if task.is_overdue() { 100 } else { 0 };
+    let age = task
+        .creation_date()
+        .map_or(0, |date| ((time::today() - date).num_weeks()).clamp(0, 25));
+
+    priority + urgency + age
+}
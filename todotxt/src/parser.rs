@@ -1,69 +1,2059 @@
 //! Contains data structures and traits used to parse a list of tasks.
 
-use crate::task::Task;
+use crate::{
+    date::Date,
+    priority::Priority,
+    tags::{Tag, Tags},
+    task::{find_control, split_special, Task},
+};
+#[cfg(feature = "dates")]
+use crate::{stats::TaskStats, time};
+use memchr::{memchr, memchr_iter, memrchr};
 #[cfg(feature = "rayon")]
 use rayon::{
-    iter::{plumbing::UnindexedConsumer, ParallelIterator},
+    iter::{self as rayon_iter, plumbing::UnindexedConsumer, ParallelIterator},
     str::{Lines as ParallelLines, ParallelString},
 };
-use std::{iter::FusedIterator, str::Lines};
+use std::{
+    borrow::Cow,
+    char,
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    fs,
+    io::{self, BufRead, Stdin, StdinLock},
+    iter::{self, FusedIterator, Inspect},
+    ops::Range,
+    path::Path,
+};
 
 /// Provides methods for types that can be used as parser input.
 pub trait Input {
     /// Returns an iterator of tasks contained in `self`.
     fn tasks(&self) -> Iter<'_>;
+
+    /// Returns an iterator of tasks contained in `self` that parses in strict
+    /// mode.
+    ///
+    /// Lines are parsed the same way as [`tasks`], but a line that parses
+    /// into a task with any of the problems reported by [`Task::validate`] is
+    /// yielded as an error rather than a task. A line that contains a control
+    /// character other than tab is rejected as well, instead of having the
+    /// character removed, and so is a line that can't be parsed at all, which
+    /// would end iteration with [`tasks`]. Iteration continues with the next
+    /// line after an error.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::parser::ParseErrorKind;
+    ///
+    /// let data = "x 2011-03-02 2011-03-01 Call Mom\nx 2020-01-01 2023-05-05 Pay rent\n";
+    /// let results: Vec<_> = data.try_tasks().collect();
+    /// let error = results[1].as_ref().unwrap_err();
+    ///
+    /// assert!(results[0].is_ok());
+    /// assert_eq!(error.kind(), ParseErrorKind::CompletionBeforeCreation);
+    /// assert_eq!(error.line(), 2);
+    /// assert_eq!(&data[error.span()], "2020-01-01 2023-05-05");
+    /// # }
+    /// ```
+    ///
+    /// [`tasks`]: #tymethod.tasks
+    /// [`Task::validate`]: ../struct.Task.html#method.validate
+    fn try_tasks(&self) -> TryIter<'_>;
+
+    /// Returns an iterator of tasks contained in `self` that keeps the
+    /// indentation of each line, for lists that indent sub-tasks.
+    ///
+    /// Lines are parsed the same way as [`tasks`], and each task is paired with
+    /// the spaces and tabs at the start of its line. An [`Indented`] task is
+    /// formatted with its indentation, so the lines of the input are reproduced
+    /// other than the normalization that [`Task`] applies to them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Plan the garage sale\n    (B) Schedule Goodwill pickup\n\tPost signs";
+    /// let tasks: Vec<_> = data.indented_tasks().collect();
+    ///
+    /// assert_eq!(tasks[0].indentation(), "");
+    /// assert_eq!(tasks[1].indentation(), "    ");
+    /// assert_eq!(tasks[1].task().priority(), Some(Priority::B));
+    /// assert_eq!(tasks[2].to_string(), "\tPost signs");
+    /// # }
+    /// ```
+    ///
+    /// [`tasks`]: #tymethod.tasks
+    /// [`Indented`]: struct.Indented.html
+    /// [`Task`]: ../struct.Task.html
+    fn indented_tasks(&self) -> IndentedIter<'_>;
+}
+
+/// Provides methods for readers that can be used as parser input, such as
+/// standard input.
+pub trait ReadInput {
+    /// The buffered reader that lines are read from.
+    type Reader: BufRead;
+
+    /// Returns an iterator of tasks read from `self` a line at a time.
+    ///
+    /// Only the current line is held in memory, in a buffer that is reused for
+    /// each line, and each task owns its description. Blank lines are skipped.
+    /// An error is yielded if reading fails or a line is not valid UTF-8. To
+    /// read tasks from any other `BufRead`, use [`ReadIter::new`].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::io;
+    ///
+    /// for task in io::stdin().tasks() {
+    ///     let task = task?;
+    ///
+    ///     if !task.is_complete() {
+    ///         println!("{}", task);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadIter::new`]: struct.ReadIter.html#method.new
+    fn tasks(self) -> ReadIter<Self::Reader>;
+}
+
+/// A task paired with the indentation of the line that it was parsed from. See
+/// [`Input::indented_tasks`].
+///
+/// [`Input::indented_tasks`]: trait.Input.html#tymethod.indented_tasks
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Indented<'a> {
+    indentation: &'a str,
+    task: Task<'a>,
+}
+
+/// An iterator over the tasks of a given input that keeps the indentation of
+/// each line. See [`Input::indented_tasks`].
+///
+/// [`Input::indented_tasks`]: trait.Input.html#tymethod.indented_tasks
+#[derive(Clone, Debug)]
+pub struct IndentedIter<'a> {
+    input: &'a str,
+    lines: Lines<'a>,
 }
 
 /// An iterator over the tasks of a given input.
 #[derive(Clone, Debug)]
 pub struct Iter<'a> {
     lines: Lines<'a>,
+    parser: Parser,
+}
+
+/// Whether the lines that would be rejected in strict mode are parsed or
+/// skipped. See [`Parser::mode`].
+///
+/// [`Parser::mode`]: struct.Parser.html#method.mode
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ParseMode {
+    /// Every line is parsed, as with [`Input::tasks`].
+    ///
+    /// [`Input::tasks`]: trait.Input.html#tymethod.tasks
+    #[default]
+    Lenient,
+    /// Lines that [`Input::try_tasks`] would reject are skipped.
+    ///
+    /// [`Input::try_tasks`]: trait.Input.html#tymethod.try_tasks
+    Strict,
+}
+
+/// A configurable parser, for when the defaults of [`Input::tasks`] are not
+/// enough. The methods of [`Input`] parse with `Parser::default()`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::parser::Parser;
+///
+/// let data = "
+///     (A) Call Mom @phone
+///     x 2011-03-02 Review Tim's pull request +TodoTxtTouch @github
+///     Water the plants h:1 rec:1w
+/// ";
+/// let parser = Parser::new().skip_complete(true).skip_hidden(true);
+/// let tasks: Vec<String> = parser.parse_str(data).map(|task| task.to_string()).collect();
+///
+/// assert_eq!(tasks, ["(A) Call Mom @phone"]);
+/// assert_eq!(Parser::default().parse_str(data).count(), data.tasks().count());
+/// # }
+/// ```
+///
+/// [`Input`]: trait.Input.html
+/// [`Input::tasks`]: trait.Input.html#tymethod.tasks
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Parser {
+    mode: ParseMode,
+    skip_complete: bool,
+    skip_hidden: bool,
+}
+
+/// An iterator over the tasks of a given input that reports its progress to a
+/// callback. See [`Iter::with_progress`].
+///
+/// [`Iter::with_progress`]: struct.Iter.html#method.with_progress
+pub struct ProgressIter<'a> {
+    callback: Box<dyn Fn(usize, usize) + 'a>,
+    done: bool,
+    interval: usize,
+    lines: Lines<'a>,
+    parsed: usize,
+    parser: Parser,
+    reported: bool,
+    scanned: usize,
+}
+
+/// An iterator over the tasks read from a reader. See [`ReadInput::tasks`].
+///
+/// [`ReadInput::tasks`]: trait.ReadInput.html#tymethod.tasks
+#[derive(Debug)]
+pub struct ReadIter<R> {
+    buffer: String,
+    line: usize,
+    reader: R,
+}
+
+/// An iterator over the tasks of a given input that parses in strict mode. See
+/// [`Input::try_tasks`].
+///
+/// [`Input::try_tasks`]: trait.Input.html#tymethod.try_tasks
+#[derive(Clone, Debug)]
+pub struct TryIter<'a> {
+    input: &'a str,
+    line: usize,
+    lines: Lines<'a>,
+}
+
+/// An error produced when a line is rejected in strict mode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    #[cfg(feature = "verbose-errors")]
+    context: Vec<&'static str>,
+    kind: ParseErrorKind,
+    line: usize,
+    span: Range<usize>,
+}
+
+/// The reasons that a line can be rejected in strict mode.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The completion date of a complete task is before its creation date.
+    CompletionBeforeCreation,
+    /// The line contains a control character other than tab, which would be
+    /// removed when parsing normally.
+    ControlCharacter,
+    /// A date is separated with a character other than `-`, e.g.
+    /// `2024/01/01`.
+    InvalidDateSeparator,
+    /// The day of a date is not in its month, e.g. `2023-02-29`.
+    InvalidDay,
+    /// The month of a date is not from 1 to 12, e.g. `2024-13-01`.
+    InvalidMonth,
+    /// A date has a two-digit year, e.g. `24-01-01`.
+    TwoDigitYear,
+    /// The month or day of a date is a single digit, e.g. `2024-1-1`.
+    UnpaddedDate,
+    /// The line can't be parsed as a task, e.g. `(A`, which would end
+    /// iteration when parsing normally.
+    Unparseable,
+}
+
+/// The descriptions of the parts of a line that are reported by
+/// [`ParseError::context`].
+///
+/// [`ParseError::context`]: struct.ParseError.html#method.context
+#[cfg(feature = "verbose-errors")]
+mod context {
+    pub(crate) const COMPLETION_DATE: &str = "while parsing completion date";
+    pub(crate) const CREATION_DATE: &str = "while parsing creation date";
+    pub(crate) const DAY: &str = "while parsing day";
+    pub(crate) const LINE: &str = "while parsing line";
+    pub(crate) const MONTH: &str = "while parsing month";
+    pub(crate) const SEPARATOR: &str = "while parsing date separator";
+    pub(crate) const YEAR: &str = "while parsing year";
+}
+
+/// The trimmed lines of an input.
+///
+/// Lines are split at `\n` with `memchr` rather than with `str::lines`, and
+/// trimmed a byte at a time while the edges of a line are ASCII. The yielded
+/// slices are identical to `input.lines().map(str::trim)`.
+#[derive(Clone, Debug)]
+struct Lines<'a> {
+    data: &'a str,
+    /// The whole input, which the lines are slices of.
+    input: &'a str,
 }
 
 #[allow(missing_docs)]
 #[cfg(feature = "rayon")]
 pub trait ParallelInput {
-    fn par_tasks(&self) -> ParallelIter;
+    fn par_tasks(&self) -> ParallelIter<'_>;
+
+    /// Returns a parallel iterator of tasks contained in `self` that splits
+    /// the input into chunks of roughly `chunk_size` bytes, aligned to line
+    /// breaks, and parses each chunk sequentially in its own rayon job.
+    ///
+    /// For very large inputs this has better locality than [`par_tasks`],
+    /// which splits the input at individual lines. Operations that preserve
+    /// order in rayon, such as `collect`, yield tasks in the same order as
+    /// [`tasks`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    /// let tasks: Vec<Task> = data.par_tasks_chunked(16).collect();
+    ///
+    /// assert_eq!(tasks, data.tasks().collect::<Vec<_>>());
+    /// # }
+    /// ```
+    ///
+    /// [`par_tasks`]: #tymethod.par_tasks
+    /// [`tasks`]: trait.Input.html#tymethod.tasks
+    fn par_tasks_chunked(&self, chunk_size: usize) -> ParallelChunks<'_>;
 }
 
 #[allow(missing_docs)]
 #[cfg(feature = "rayon")]
 #[derive(Clone, Debug)]
 pub struct ParallelIter<'a> {
+    input: &'a str,
     lines: ParallelLines<'a>,
 }
 
-pub(crate) trait Parse<'a> {
+/// A parallel iterator over the tasks of a given input, parsed in chunks of
+/// whole lines. See [`ParallelInput::par_tasks_chunked`].
+///
+/// [`ParallelInput::par_tasks_chunked`]: trait.ParallelInput.html#tymethod.par_tasks_chunked
+#[cfg(feature = "rayon")]
+#[derive(Clone, Debug)]
+pub struct ParallelChunks<'a> {
+    chunk_size: usize,
+    data: &'a str,
+}
+
+/// Provides a way to parse the value of a domain-specific special tag into a
+/// custom type.
+///
+/// Special tags are tags in the form `key:value`. The standard parser only
+/// identifies the location of each special tag. Implementing this trait allows
+/// the value of a special tag to be parsed on demand via
+/// [`Task::parse_special`] once the standard parsing of a task is complete.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use std::time::Duration;
+/// use todotxt::parser::ParseSpecial;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct DurationTag(Duration);
+///
+/// impl<'a> ParseSpecial<'a> for DurationTag {
+///     const KEY: &'static str = "timer";
+///
+///     fn parse_special(value: &'a str) -> Option<Self> {
+///         let minutes: u64 = value.strip_suffix('m')?.parse().ok()?;
+///
+///         Some(DurationTag(Duration::from_secs(minutes * 60)))
+///     }
+/// }
+///
+/// let data = "
+///     Write the quarterly report timer:30m
+///     Water the plants timer:soon
+///     Call Mom @phone
+/// ";
+/// let timers: Vec<Option<DurationTag>> = data
+///     .tasks()
+///     .map(|task| task.parse_special::<DurationTag>())
+///     .collect();
+///
+/// assert_eq!(
+///     timers,
+///     [Some(DurationTag(Duration::from_secs(1800))), None, None]
+/// );
+/// # }
+/// ```
+///
+/// [`Task::parse_special`]: ../struct.Task.html#method.parse_special
+pub trait ParseSpecial<'a>: Sized {
+    /// The key of the special tags that can be parsed (e.g. `"timer"` for
+    /// `timer:30m`).
+    const KEY: &'static str;
+
+    /// Parses the value of a special tag, returning `None` if the value is
+    /// not valid.
+    fn parse_special(value: &'a str) -> Option<Self>;
+}
+
+/// Parses a value from the start of a string, returning the rest of the
+/// string along with the value.
+///
+/// The prefixes of a task are parsed by the implementations for [`Priority`],
+/// [`Date`], and [`State`], and a whole line by the implementation for
+/// [`Task`]. They can be reused to parse lines in an extended format, such as
+/// one with additional fields between the prefixes and the description.
+///
+/// - `Priority` and `Date` parse `(A)` and `2011-03-01` without the whitespace
+///   after them.
+/// - `State` parses the prefixes of a line and the spaces and tabs after each
+///   of them. It expects its input to be trimmed, and returns an incomplete
+///   error for input that ends partway through a prefix, such as `(A`.
+/// - `Task` trims its input and consumes all of it.
+///
+/// Implement this trait to parse a field of your own with the same
+/// conventions, and see [`parse_one`] to parse a value on its own.
+///
+/// ## Stability
+///
+/// The trait is part of the public API along with the four implementations
+/// above, and its shape will only change in a major version. Its results are
+/// the [`IResult`] of nom 4, which is re-exported as `todotxt::nom`, so
+/// upgrading nom will also be a breaking change. The kinds of the errors in
+/// the results are not part of the API, and neither are the results for
+/// input that other parts of the crate would not pass to a parser, such as an
+/// untrimmed line passed to `State`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{parser::Parse, Date, State};
+///
+/// // An extended format with an estimate in points after the prefixes.
+/// let line = "(A) 2011-03-01 ~3 Call Mom @phone";
+/// let (rest, state) = State::parse(line).unwrap();
+/// let (points, description) = rest.split_at(rest.find(' ').unwrap());
+///
+/// assert_eq!(
+///     state,
+///     State::Incomplete(Some(Priority::A), Date::from_ymd_opt(2011, 3, 1)),
+/// );
+/// assert_eq!(points, "~3");
+/// assert_eq!(description.trim_start(), "Call Mom @phone");
+/// # }
+/// ```
+///
+/// [`Date`]: crate::Date
+/// [`IResult`]: https://docs.rs/nom/4/nom/type.IResult.html
+/// [`Priority`]: ../enum.Priority.html
+/// [`State`]: ../enum.State.html
+/// [`Task`]: ../struct.Task.html
+/// [`parse_one`]: fn.parse_one.html
+pub trait Parse<'a> {
+    /// The type of the parsed value.
     type Output;
+
+    /// Parses a value from the start of `input`, returning the rest of
+    /// `input` along with the value.
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self::Output>;
 }
 
-pub(crate) fn parse<'a, T>(input: &'a str) -> Option<T::Output>
+// The parsers below are plain functions that return the same results as the
+// streaming macros of nom 4 that they replace. In particular, input that ends
+// before a parser can tell whether it matches is incomplete, which a line that
+// ends with the prefix of a task relies on.
+
+/// Parses the character `c` at the start of `input`.
+pub(crate) fn character(input: &str, c: char) -> nom::IResult<&str, char> {
+    match input.chars().next() {
+        Some(first) if first == c => Ok((&input[c.len_utf8()..], c)),
+        Some(_) => error(input, nom::ErrorKind::Char),
+        None => incomplete(),
+    }
+}
+
+/// Returns the error of a parser that does not match `input`.
+pub(crate) fn error<T>(input: &str, kind: nom::ErrorKind) -> nom::IResult<&str, T> {
+    Err(nom::Err::Error(nom::Context::Code(input, kind)))
+}
+
+/// Returns the error of a parser that needs more input to match.
+pub(crate) fn incomplete<'a, T>() -> nom::IResult<&'a str, T> {
+    Err(nom::Err::Incomplete(nom::Needed::Size(1)))
+}
+
+/// Parses a prefix of a line with `parser`, followed by the spaces and tabs
+/// that separate it from the rest of the line or the end of the line. If the
+/// prefix does not match, `None` is returned and no input is consumed.
+pub(crate) fn optional<'a, T, F>(input: &'a str, parser: F) -> nom::IResult<&'a str, Option<T>>
+where
+    F: FnOnce(&'a str) -> nom::IResult<&'a str, T>,
+{
+    let result = parser(input).and_then(|(rest, output)| {
+        let (rest, _) = separator(rest)?;
+        Ok((rest, output))
+    });
+
+    match result {
+        Ok((rest, output)) => Ok((rest, Some(output))),
+        Err(nom::Err::Error(_)) => Ok((input, None)),
+        Err(error) => Err(error),
+    }
+}
+
+/// Parses the spaces and tabs after a prefix. The input is trimmed, so a line
+/// that only contains a prefix such as `x` or `(A)` ends right after it, which
+/// is accepted as well.
+fn separator(input: &str) -> nom::IResult<&str, &str> {
+    let end = input
+        .bytes()
+        .position(|byte| byte != b' ' && byte != b'\t')
+        .unwrap_or(input.len());
+
+    match end {
+        _ if input.is_empty() => Ok((input, input)),
+        0 => error(input, nom::ErrorKind::Space),
+        _ if end == input.len() => incomplete(),
+        _ => Ok((&input[end..], &input[..end])),
+    }
+}
+
+/// The characters of the bytes from `0x80` to `0x9F` in Windows-1252. The five
+/// bytes that the encoding leaves undefined are mapped to the C1 controls with
+/// the same value, as they are in Latin-1.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+/// Decodes the contents of a todo.txt file that may not be UTF-8, such as a
+/// file that was saved by an older Windows tool.
+///
+/// UTF-16 is detected by its byte order mark, little or big endian. Otherwise
+/// the bytes are read as UTF-8, with or without a byte order mark, and bytes
+/// that are not valid UTF-8 are read as Windows-1252, a superset of Latin-1.
+/// Decoding never fails: a UTF-16 code unit that is unpaired is replaced with
+/// `U+FFFD`.
+///
+/// The input is borrowed rather than copied if it is valid UTF-8.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use std::borrow::Cow;
+/// use todotxt::parser::decode_lossy;
+///
+/// let windows_1252 = b"(A) Buy caf\xe9 for the \x93meeting\x94 @store";
+/// let utf_8 = "(A) Buy café for the “meeting” @store";
+///
+/// assert_eq!(decode_lossy(windows_1252), utf_8);
+/// assert!(matches!(decode_lossy(utf_8.as_bytes()), Cow::Borrowed(_)));
+/// # }
+/// ```
+pub fn decode_lossy(bytes: &[u8]) -> Cow<'_, str> {
+    let decode_utf_16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units = bytes.chunks(2).map(|pair| match *pair {
+            [a, b] => from_bytes([a, b]),
+            // A trailing byte is not a whole code unit, so it is replaced
+            // like an unpaired surrogate.
+            _ => 0xD800,
+        });
+
+        char::decode_utf16(units)
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect::<String>()
+    };
+
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => Cow::Owned(decode_utf_16(rest, u16::from_le_bytes)),
+        [0xFE, 0xFF, rest @ ..] => Cow::Owned(decode_utf_16(rest, u16::from_be_bytes)),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => decode_utf_8(rest),
+        _ => decode_utf_8(bytes),
+    }
+}
+
+/// Decodes `bytes` as UTF-8, or as Windows-1252 if they are not valid UTF-8.
+fn decode_utf_8(bytes: &[u8]) -> Cow<'_, str> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Cow::Borrowed(text);
+    }
+
+    let text = bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9F => WINDOWS_1252[usize::from(byte - 0x80)],
+            _ => char::from(byte),
+        })
+        .collect();
+
+    Cow::Owned(text)
+}
+
+/// Merges two iterators whose tasks are sorted by `cmp` into one iterator of
+/// all of their tasks in sorted order, without collecting either of them. This
+/// is the merge step of merge sort.
+///
+/// The merge is stable: tasks that compare equal keep their order within
+/// each iterator, and the tasks of `a` come before the equal tasks of `b`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::parser::interleave_sorted;
+///
+/// let work = "(A) Review Tim's pull request\n(C) File the report\nClean the desk";
+/// let home = "(B) Call Mom\n(C) Pay rent";
+/// let merged: Vec<String> = interleave_sorted(work.tasks(), home.tasks(), |a, b| {
+///     Priority::cmp_opt(a.priority(), b.priority())
+/// })
+/// .map(|task| task.to_string())
+/// .collect();
+///
+/// assert_eq!(
+///     merged,
+///     [
+///         "(A) Review Tim's pull request",
+///         "(B) Call Mom",
+///         "(C) File the report",
+///         "(C) Pay rent",
+///         "Clean the desk",
+///     ]
+/// );
+/// # }
+/// ```
+pub fn interleave_sorted<'a, F>(a: Iter<'a>, b: Iter<'a>, cmp: F) -> impl Iterator<Item = Task<'a>>
+where
+    F: Fn(&Task<'_>, &Task<'_>) -> Ordering,
+{
+    let (mut a, mut b) = (a.peekable(), b.peekable());
+
+    iter::from_fn(move || match (a.peek(), b.peek()) {
+        // A task from `b` is only taken first if it is strictly less, so
+        // that equal tasks from `a` come first.
+        (Some(x), Some(y)) if cmp(y, x) == Ordering::Less => b.next(),
+        (Some(_), _) => a.next(),
+        (None, _) => b.next(),
+    })
+}
+
+/// The number of bytes of a line or tag value that are included in a warning,
+/// so that a malformed file can't flood the output of a subscriber.
+#[cfg(feature = "tracing")]
+const TRACE_LIMIT: usize = 80;
+
+/// Parses a value from the start of `input` with the [`Parse`]
+/// implementation of `T`, returning `None` if it fails. The rest of `input`
+/// after the value is ignored.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{parser::parse_one, Date};
+///
+/// assert_eq!(parse_one::<Priority>("(A) Call Mom"), Some(Priority::A));
+/// assert_eq!(parse_one::<Priority>("(a) Call Mom"), None);
+/// assert_eq!(parse_one::<Date>("2011-03-01"), Date::from_ymd_opt(2011, 3, 1));
+///
+/// let task = parse_one::<Task>("  x 2011-03-02 Call Mom  ").unwrap();
+///
+/// assert_eq!(task.to_string(), "x 2011-03-02 Call Mom");
+/// # }
+/// ```
+///
+/// [`Parse`]: trait.Parse.html
+pub fn parse_one<'a, T>(input: &'a str) -> Option<T::Output>
 where
     T: Parse<'a>,
 {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("parse", len = input.len()).entered();
+
     match T::parse(input) {
         Err(ref e) if cfg!(debug) => unreachable!("Error: {:#?}", e),
-        result => result.ok().map(|(_, output)| output),
+        Ok((_, output)) => Some(output),
+        Err(_) => None,
+    }
+}
+
+/// Parses a trimmed line of input as a task like `parse_one`, warning about
+/// the line along with its number, starting from 1, if it fails to parse.
+/// `line_number` is only called in that case.
+pub(crate) fn parse_line<'a, F>(line: &'a str, line_number: F) -> Option<Task<'a>>
+where
+    F: FnOnce() -> usize,
+{
+    let task = parse_one::<Task<'a>>(line);
+
+    #[cfg(feature = "tracing")]
+    if task.is_none() {
+        tracing::warn!(
+            line = truncate_for_trace(line),
+            line_number = line_number(),
+            "failed to parse line"
+        );
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    let _ = line_number;
+
+    task
+}
+
+/// Returns the number of `line` in `input`, starting from 1, where `line` is
+/// a slice of `input`.
+fn line_number(input: &str, line: &str) -> usize {
+    let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+
+    memchr_iter(b'\n', &input.as_bytes()[..offset]).count() + 1
+}
+
+/// Returns at most the first `TRACE_LIMIT` bytes of `text`, ending at a char
+/// boundary.
+#[cfg(feature = "tracing")]
+pub(crate) fn truncate_for_trace(text: &str) -> &str {
+    &text[..text.floor_char_boundary(TRACE_LIMIT)]
+}
+
+fn priority_score(priority: Priority) -> u64 {
+    26 - priority as u64
+}
+
+/// Splits `data` at the line break closest to its middle, or returns it as is
+/// if it is no longer than `chunk_size` or consists of a single line.
+#[cfg(feature = "rayon")]
+fn split_chunk(data: &str, chunk_size: usize) -> (&str, Option<&str>) {
+    if data.len() <= chunk_size {
+        return (data, None);
+    }
+
+    let bytes = data.as_bytes();
+    let middle = bytes.len() / 2;
+    let index = memchr(b'\n', &bytes[middle..])
+        .map(|index| middle + index)
+        .or_else(|| memrchr(b'\n', &bytes[..middle]));
+
+    match index {
+        Some(index) if index + 1 < bytes.len() => {
+            let (head, tail) = data.split_at(index + 1);
+            (head, Some(tail))
+        }
+        _ => (data, None),
+    }
+}
+
+/// Returns true if `byte` is one of the ASCII characters with the Unicode
+/// `White_Space` property, which is what `str::trim` removes.
+fn is_ascii_space(byte: u8) -> bool {
+    matches!(byte, b'\t'..=b'\r' | b' ')
+}
+
+fn trim(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let start = bytes
+        .iter()
+        .position(|byte| !is_ascii_space(*byte))
+        .unwrap_or(bytes.len());
+    let end = bytes[start..]
+        .iter()
+        .rposition(|byte| !is_ascii_space(*byte))
+        .map_or(start, |index| start + index + 1);
+
+    // Both edges are ASCII whitespace boundaries, so slicing can't split a
+    // character. Unicode whitespace is rare enough to defer to `str::trim`.
+    let line = &line[start..end];
+
+    match (line.as_bytes().first(), line.as_bytes().last()) {
+        (Some(first), Some(last)) if !first.is_ascii() || !last.is_ascii() => line.trim(),
+        _ => line,
     }
 }
 
 impl Input for str {
     fn tasks(&self) -> Iter<'_> {
+        Parser::default().parse_str(self)
+    }
+
+    fn try_tasks(&self) -> TryIter<'_> {
+        TryIter {
+            input: self,
+            line: 0,
+            lines: Lines::new(self),
+        }
+    }
+
+    fn indented_tasks(&self) -> IndentedIter<'_> {
+        IndentedIter {
+            input: self,
+            lines: Lines::new(self),
+        }
+    }
+}
+
+impl Parser {
+    /// Returns a parser with the default options, which parses every line of
+    /// an input in lenient mode.
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// Sets whether the lines that would be rejected in strict mode are
+    /// parsed or skipped. See [`Input::try_tasks`] to read the reasons that
+    /// lines are rejected instead.
+    ///
+    /// [`Input::try_tasks`]: trait.Input.html#tymethod.try_tasks
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Parses the todo.txt file at `path`, returning tasks that own their
+    /// descriptions. An error is returned if reading the file fails. A file
+    /// that is not UTF-8 is decoded with [`decode_lossy`].
+    ///
+    /// [`decode_lossy`]: fn.decode_lossy.html
+    pub fn parse_file(&self, path: &Path) -> io::Result<Vec<Task<'static>>> {
+        let bytes = fs::read(path)?;
+        let data = decode_lossy(&bytes);
+
+        Ok(self.parse_str(&data).map(Task::into_owned).collect())
+    }
+
+    /// Returns an iterator of the tasks contained in `input` that are not
+    /// skipped by the options of the parser.
+    pub fn parse_str<'a>(&self, input: &'a str) -> Iter<'a> {
         Iter {
-            lines: self.lines(),
+            lines: Lines::new(input),
+            parser: *self,
+        }
+    }
+
+    /// Sets whether complete tasks are skipped.
+    pub fn skip_complete(mut self, skip: bool) -> Self {
+        self.skip_complete = skip;
+        self
+    }
+
+    /// Sets whether hidden tasks, which have an `h:1` tag, are skipped. See
+    /// [`Task::is_hidden`].
+    ///
+    /// [`Task::is_hidden`]: ../struct.Task.html#method.is_hidden
+    pub fn skip_hidden(mut self, skip: bool) -> Self {
+        self.skip_hidden = skip;
+        self
+    }
+
+    /// Returns the next task from `lines` that is not skipped, along with the
+    /// line that it was parsed from. Lines are read with `next`, so that
+    /// lines can be read from either end. As with `parse`, a line that fails
+    /// to parse ends iteration.
+    fn next_task<'a, F>(&self, lines: &mut Lines<'a>, next: F) -> Option<(&'a str, Task<'a>)>
+    where
+        F: FnMut(&mut Lines<'a>) -> Option<&'a str>,
+    {
+        self.next_task_or_warn(lines, next, None::<fn(&'a str)>)
+    }
+
+    /// Returns the next task from `lines` like `next_task`, calling `warn`
+    /// with each line that is malformed: a line that fails to parse, or one
+    /// that is rejected in strict mode. Lines that are skipped by the other
+    /// options are not malformed. A line that fails to parse ends iteration
+    /// only if `warn` is `None`.
+    fn next_task_or_warn<'a, F, W>(
+        &self,
+        lines: &mut Lines<'a>,
+        mut next: F,
+        warn: Option<W>,
+    ) -> Option<(&'a str, Task<'a>)>
+    where
+        F: FnMut(&mut Lines<'a>) -> Option<&'a str>,
+        W: Fn(&'a str),
+    {
+        loop {
+            let line = next(lines)?;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let task = match (parse_line(line, || line_number(lines.input, line)), &warn) {
+                (Some(task), _) => task,
+                (None, Some(warn)) => {
+                    warn(line);
+                    continue;
+                }
+                (None, None) => return None,
+            };
+
+            if self.rejects(line, &task) {
+                if let Some(warn) = &warn {
+                    warn(line);
+                }
+            } else if !self.skips(&task) {
+                return Some((line, task));
+            }
+        }
+    }
+
+    /// Returns `true` if `task`, which was parsed from `line`, is rejected by
+    /// the mode of the parser.
+    fn rejects(&self, line: &str, task: &Task<'_>) -> bool {
+        self.mode == ParseMode::Strict
+            && (find_control(line).is_some() || !task.validate().is_empty())
+    }
+
+    /// Returns `true` if the options of the parser skip `task`.
+    fn skips(&self, task: &Task<'_>) -> bool {
+        (self.skip_complete && task.is_complete()) || (self.skip_hidden && task.is_hidden())
+    }
+}
+
+impl<'a> Indented<'a> {
+    /// Returns the spaces and tabs at the start of the line.
+    pub fn indentation(&self) -> &'a str {
+        self.indentation
+    }
+
+    /// Consumes `self` and returns the task without its indentation.
+    pub fn into_task(self) -> Task<'a> {
+        self.task
+    }
+
+    /// Returns the task without its indentation.
+    pub fn task(&self) -> &Task<'a> {
+        &self.task
+    }
+}
+
+impl<'a> Display for Indented<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.indentation, self.task)
+    }
+}
+
+impl ParseError {
+    /// Returns what was being parsed when the line was rejected, from the
+    /// outermost part of the line to the innermost, e.g. `["while parsing
+    /// creation date", "while parsing month"]` for `2024-13-01 Call Mom`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let error = "x 2024-1-01 Call Mom".try_tasks().next().unwrap().unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.context(),
+    ///     ["while parsing completion date", "while parsing month"]
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "verbose-errors")]
+    pub fn context(&self) -> &[&'static str] {
+        &self.context
+    }
+
+    /// Returns the reason that the line was rejected.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the line number of the rejected line, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the byte range of the text that caused the error, relative to
+    /// the start of the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)?;
+
+        #[cfg(feature = "verbose-errors")]
+        if !self.context.is_empty() {
+            write!(f, " ({})", self.context.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+impl ParseErrorKind {
+    /// Returns the parts of the line that `task` was parsed from that contain
+    /// the error, from the outermost to the innermost.
+    #[cfg(feature = "verbose-errors")]
+    fn context(self, task: &Task<'_>) -> Vec<&'static str> {
+        // A word that is almost a date is left at the start of the description,
+        // in place of the first date that the task does not have.
+        let date = match task.state() {
+            crate::State::Complete(None, _) => context::COMPLETION_DATE,
+            _ => context::CREATION_DATE,
+        };
+        let word = task.description().split_whitespace().next().unwrap_or("");
+
+        match self {
+            ParseErrorKind::CompletionBeforeCreation => vec![context::COMPLETION_DATE],
+            ParseErrorKind::ControlCharacter | ParseErrorKind::Unparseable => {
+                vec![context::LINE]
+            }
+            ParseErrorKind::InvalidDateSeparator => vec![date, context::SEPARATOR],
+            ParseErrorKind::InvalidDay => vec![date, context::DAY],
+            ParseErrorKind::InvalidMonth => vec![date, context::MONTH],
+            ParseErrorKind::TwoDigitYear => vec![date, context::YEAR],
+            ParseErrorKind::UnpaddedDate => {
+                let mut groups = word.split(|c: char| !c.is_ascii_digit()).skip(1);
+
+                match groups.next() {
+                    Some(month) if month.len() == 1 => vec![date, context::MONTH],
+                    _ => vec![date, context::DAY],
+                }
+            }
+        }
+    }
+
+    /// Returns the byte range of the text in `line` that caused the error,
+    /// where `line` is the trimmed line that `task` was parsed from.
+    fn span(self, line: &str, task: &Task<'_>) -> Range<usize> {
+        match self {
+            ParseErrorKind::CompletionBeforeCreation => {
+                // The description is a suffix of the line, so whatever comes
+                // before it is the prefix that contains the dates.
+                let prefix = line[..line.len() - task.description().len()].trim_end();
+                let start = prefix.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+
+                start..prefix.len()
+            }
+            ParseErrorKind::InvalidDateSeparator
+            | ParseErrorKind::InvalidDay
+            | ParseErrorKind::InvalidMonth
+            | ParseErrorKind::TwoDigitYear
+            | ParseErrorKind::UnpaddedDate => {
+                // The malformed date is the first word of the description.
+                let start = line.len() - task.description().len();
+                let len = task
+                    .description()
+                    .find(char::is_whitespace)
+                    .unwrap_or(task.description().len());
+
+                start..start + len
+            }
+            ParseErrorKind::ControlCharacter => match find_control(line) {
+                Some(index) => {
+                    let len = line[index..].chars().next().map_or(0, char::len_utf8);
+
+                    index..index + len
+                }
+                None => 0..0,
+            },
+            ParseErrorKind::Unparseable => 0..line.len(),
+        }
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseErrorKind::CompletionBeforeCreation => {
+                "the completion date is before the creation date"
+            }
+            ParseErrorKind::ControlCharacter => "the line contains a control character",
+            ParseErrorKind::InvalidDateSeparator => "the parts of a date must be separated by `-`",
+            ParseErrorKind::InvalidDay => "the day is not in the month of the date",
+            ParseErrorKind::InvalidMonth => "the month of a date must be from 01 to 12",
+            ParseErrorKind::TwoDigitYear => "the year of a date must have four digits",
+            ParseErrorKind::UnpaddedDate => "the month and day of a date must have two digits",
+            ParseErrorKind::Unparseable => "the line is not a task",
+        })
+    }
+}
+
+impl<'a> Iter<'a> {
+    /// Returns `true` if any task has the provided priority. Iteration stops at
+    /// the first match.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     Post signs around the neighborhood +GarageSale
+    ///     (A) Call Mom
+    /// ";
+    /// let mut iter = data.tasks();
+    ///
+    /// assert!(iter.any_with_priority(Priority::A));
+    ///
+    /// // Only the tasks after the first match have not been visited.
+    /// assert_eq!(iter.count(), 2);
+    /// assert!(!data.tasks().any_with_priority(Priority::C));
+    /// # }
+    /// ```
+    pub fn any_with_priority(&mut self, priority: Priority) -> bool {
+        self.any(|task| task.priority() == Some(priority))
+    }
+
+    /// Returns an iterator of batches of `chunk_size` tasks, in order. The last
+    /// batch has fewer tasks if the number of tasks is not a multiple of
+    /// `chunk_size`. Batches own their tasks, so each one can be sent to a
+    /// thread pool on its own, e.g. with `rayon::spawn`.
+    ///
+    /// Lines are parsed as the batches are read, so only one batch is held in
+    /// memory at a time.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    ///     @GroceryStore Eskimo pies
+    ///     x 2011-03-02 Pay rent
+    /// ";
+    /// let sizes: Vec<usize> = data.tasks().batch_parse(2).map(|batch| batch.len()).collect();
+    ///
+    /// assert_eq!(sizes, [2, 2, 1]);
+    /// # }
+    /// ```
+    pub fn batch_parse(mut self, chunk_size: usize) -> impl Iterator<Item = Vec<Task<'a>>> {
+        assert!(chunk_size > 0, "chunk size must be greater than 0");
+
+        iter::from_fn(move || {
+            let batch: Vec<_> = self.by_ref().take(chunk_size).collect();
+
+            match batch.is_empty() {
+                true => None,
+                false => Some(batch),
+            }
+        })
+    }
+
+    /// Returns the number of tasks with the provided priority.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     (A) Call Mom
+    /// ";
+    ///
+    /// assert_eq!(data.tasks().count_with_priority(Priority::A), 2);
+    /// assert_eq!(data.tasks().count_with_priority(Priority::C), 0);
+    /// # }
+    /// ```
+    pub fn count_with_priority(self, priority: Priority) -> usize {
+        self.filter(|task| task.priority() == Some(priority))
+            .count()
+    }
+
+    /// Returns an iterator of the complete tasks, each paired with the number
+    /// of complete tasks before it, starting from 0. Incomplete tasks are not
+    /// yielded, rather than being paired with a placeholder index.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     x 2011-03-01 Call Mom
+    ///     (A) Schedule Goodwill pickup
+    ///     x 2011-03-02 Pay rent
+    ///     Post signs around the neighborhood
+    ///     x 2011-03-03 Review Tim's pull request
+    /// ";
+    /// let done: Vec<(usize, String)> = data
+    ///     .tasks()
+    ///     .enumerate_complete()
+    ///     .map(|(index, task)| (index, task.description().to_owned()))
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     done,
+    ///     [
+    ///         (0, "Call Mom".to_owned()),
+    ///         (1, "Pay rent".to_owned()),
+    ///         (2, "Review Tim's pull request".to_owned()),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn enumerate_complete(self) -> impl Iterator<Item = (usize, Task<'a>)> {
+        self.filter(Task::is_complete).enumerate()
+    }
+
+    /// Returns the first task that satisfies the predicate.
+    ///
+    /// This is a named alias for [`Iterator::find`] that does not require the
+    /// closure argument to be annotated. Iteration stops at the first match.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    ///
+    /// let mut iter = data.tasks();
+    /// let task = iter.find_task(|task| task.priority() == Some(Priority::B));
+    ///
+    /// assert_eq!(
+    ///     task.map(|task| task.to_string()),
+    ///     Some("(B) Schedule Goodwill pickup +GarageSale @phone".to_owned())
+    /// );
+    ///
+    /// // The remaining task has not been visited.
+    /// assert_eq!(iter.count(), 1);
+    /// # }
+    /// ```
+    ///
+    /// [`Iterator::find`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.find
+    pub fn find_task<F>(&mut self, predicate: F) -> Option<Task<'a>>
+    where
+        F: Fn(&Task<'a>) -> bool,
+    {
+        self.find(|task| predicate(task))
+    }
+
+    /// Returns the first task with a description that is equal to `text`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    ///
+    /// let task = data.tasks().find_by_description("Thank Mom for the meatballs @phone");
+    ///
+    /// assert_eq!(task.and_then(|task| task.priority()), Some(Priority::A));
+    /// assert!(data.tasks().find_by_description("Thank Mom").is_none());
+    /// # }
+    /// ```
+    pub fn find_by_description(&mut self, text: &str) -> Option<Task<'a>> {
+        self.find_task(|task| task.description() == text)
+    }
+
+    /// Returns an iterator over the value of each special tag with the
+    /// provided key, paired with the task that contains it. A task with several
+    /// tags with the key is yielded once per tag, and a task without the key is
+    /// skipped. The values borrow from the input rather than from the tasks,
+    /// so a value is yielded as written even if it contains a control
+    /// character that is removed from the description.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     Pay rent due:2011-03-01
+    ///     Call Mom @phone
+    ///     File taxes due:2011-04-01 due:2011-04-15
+    /// ";
+    /// let dues: Vec<(&str, String)> = data
+    ///     .tasks()
+    ///     .flat_map_special("due")
+    ///     .map(|(value, task)| (value, task.description().to_owned()))
+    ///     .collect();
+    ///
+    /// assert_eq!(dues, [
+    ///     ("2011-03-01", "Pay rent due:2011-03-01".to_owned()),
+    ///     ("2011-04-01", "File taxes due:2011-04-01 due:2011-04-15".to_owned()),
+    ///     ("2011-04-15", "File taxes due:2011-04-01 due:2011-04-15".to_owned()),
+    /// ]);
+    /// # }
+    /// ```
+    pub fn flat_map_special<'k>(
+        self,
+        key: &'k str,
+    ) -> impl Iterator<Item = (&'a str, Task<'a>)> + 'k
+    where
+        'a: 'k,
+    {
+        let Iter { mut lines, parser } = self;
+        let tasks = iter::from_fn(move || parser.next_task(&mut lines, Lines::next));
+
+        tasks.flat_map(move |(line, task)| {
+            // Tasks parsed from an input borrow their description unless
+            // control characters were removed from it. The tags of the line
+            // are the same, since a prefix never contains a special tag.
+            let description = task.borrowed_description().unwrap_or(line);
+            let tags = Tags {
+                iter: description.char_indices(),
+            };
+
+            tags.filter_map(move |tag| match tag {
+                Tag::Special { .. } => match split_special(tag.as_str(description)) {
+                    (name, value) if name == key => Some((value, task.clone())),
+                    _ => None,
+                },
+                Tag::Context { .. } | Tag::Project { .. } => None,
+            })
+        })
+    }
+
+    /// Returns an iterator over the tasks with a description that matches the
+    /// regular expression `pattern`. The pattern is compiled once, before
+    /// iteration begins. An error is returned if `pattern` is invalid.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    /// let matches: Vec<String> = data
+    ///     .tasks()
+    ///     .filter_regex(r"\+Garage\w+")
+    ///     .unwrap()
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(matches, [
+    ///     "(B) Schedule Goodwill pickup +GarageSale @phone",
+    ///     "Post signs around the neighborhood +GarageSale",
+    /// ]);
+    /// assert!(data.tasks().filter_regex(r"[A-").is_err());
+    /// # }
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn filter_regex(
+        self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = Task<'a>>, regex::Error> {
+        let regex = regex::Regex::new(pattern)?;
+
+        Ok(self.filter(move |task| task.text_matches(&regex)))
+    }
+
+    /// Calls `f` with a reference to each task as it is yielded, without
+    /// consuming it.
+    ///
+    /// This is a named alias for [`Iterator::inspect`] that does not require
+    /// the closure argument to be annotated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    /// let mut seen = Vec::new();
+    /// let count = data
+    ///     .tasks()
+    ///     .inspect_tasks(|task| seen.push(task.description().len()))
+    ///     .count();
+    ///
+    /// assert_eq!(count, 2);
+    /// assert_eq!(seen, [34, 46]);
+    /// # }
+    /// ```
+    ///
+    /// [`Iterator::inspect`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.inspect
+    pub fn inspect_tasks<F>(self, f: F) -> Inspect<Self, F>
+    where
+        F: FnMut(&Task<'a>),
+    {
+        self.inspect(f)
+    }
+
+    /// Returns the last task in the input.
+    ///
+    /// Unlike [`Iterator::last`], which parses every task in the input, this
+    /// scans backward from the end of the input to the last non-blank line and
+    /// only parses that line.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     Post signs around the neighborhood +GarageSale
+    ///
+    /// ";
+    ///
+    /// assert_eq!(data.tasks().last_task(), data.tasks().last());
+    /// assert_eq!(
+    ///     data.tasks().last_task().map(|task| task.to_string()),
+    ///     Some("Post signs around the neighborhood +GarageSale".to_owned())
+    /// );
+    /// assert_eq!("".tasks().last_task(), None);
+    /// # }
+    /// ```
+    ///
+    /// [`Iterator::last`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.last
+    pub fn last_task(mut self) -> Option<Task<'a>> {
+        self.next_back()
+    }
+
+    /// Emits each task as it is yielded to the [`log`] crate at `level`. The
+    /// target of each record is `todotxt::parser` and the message is the task
+    /// formatted as a line of a todo.txt file.
+    ///
+    /// [`log`]: https://docs.rs/log
+    #[cfg(feature = "log")]
+    pub fn log_tasks(self, level: log::Level) -> impl Iterator<Item = Task<'a>> {
+        self.inspect(move |task| log::log!(level, "{}", task))
+    }
+
+    /// Returns the mean priority score of the incomplete tasks that have a
+    /// priority, or `None` if there are no such tasks. See [`sum_priority`]
+    /// for how priorities are scored.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    ///
+    /// assert_eq!(data.tasks().mean_priority(), Some(25.5));
+    /// assert_eq!("Eskimo pies".tasks().mean_priority(), None);
+    /// # }
+    /// ```
+    ///
+    /// [`sum_priority`]: #method.sum_priority
+    pub fn mean_priority(self) -> Option<f64> {
+        let (sum, count) = self
+            .filter_map(|task| task.priority())
+            .fold((0, 0), |(sum, count), priority| {
+                (sum + priority_score(priority), count + 1)
+            });
+
+        if count == 0 {
+            None
+        } else {
+            Some(sum as f64 / count as f64)
         }
     }
+
+    /// Returns the 0-based index of the first task that satisfies the
+    /// predicate. Blank lines are not counted. Iteration stops at the first
+    /// match.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     Post signs around the neighborhood +GarageSale
+    /// ";
+    ///
+    /// let mut iter = data.tasks();
+    ///
+    /// assert_eq!(iter.position_of(|task| task.priority().is_none()), Some(2));
+    /// assert_eq!(iter.count(), 0);
+    ///
+    /// assert_eq!(data.tasks().position_of(|task| task.is_complete()), None);
+    /// # }
+    /// ```
+    pub fn position_of<F>(&mut self, predicate: F) -> Option<usize>
+    where
+        F: Fn(&Task<'a>) -> bool,
+    {
+        self.position(|task| predicate(&task))
+    }
+
+    /// Consumes the iterator and computes statistics about the remaining tasks
+    /// in a single pass, without collecting them first. The age of a task is
+    /// measured from [`time::today`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    /// ";
+    /// let stats = data.tasks().reduce_to_stats();
+    ///
+    /// assert_eq!(stats.total(), 3);
+    /// assert_eq!(stats.complete(), 1);
+    /// assert_eq!(stats.context_count("@phone"), 2);
+    /// # }
+    /// ```
+    ///
+    /// [`time::today`]: ../time/fn.today.html
+    #[cfg(feature = "dates")]
+    pub fn reduce_to_stats(self) -> TaskStats {
+        let today = time::today();
+
+        self.fold(TaskStats::default(), |mut stats, task| {
+            stats.add(&task, today);
+            stats
+        })
+    }
+
+    /// Skips the tasks that were created before `date`, returning an iterator
+    /// of the first task created on or after `date` and every task after it.
+    /// Tasks without a creation date are skipped as well.
+    ///
+    /// Unlike `filter`, scanning stops at the first task that is not skipped,
+    /// so the rest of the input is neither parsed nor checked. This is only
+    /// correct for input that is sorted by creation date, such as a log-style
+    /// todo.txt file that tasks are appended to.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let data = "
+    ///     2011-03-01 Call Mom
+    ///     2011-03-02 Schedule Goodwill pickup
+    ///     2011-03-03 Post signs around the neighborhood
+    /// ";
+    /// let date = Date::from_ymd_opt(2011, 3, 2).unwrap();
+    /// let tasks: Vec<String> = data
+    ///     .tasks()
+    ///     .skip_before_date(date)
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     tasks,
+    ///     [
+    ///         "2011-03-02 Schedule Goodwill pickup",
+    ///         "2011-03-03 Post signs around the neighborhood",
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn skip_before_date(mut self, date: Date) -> Iter<'a> {
+        loop {
+            // The lines are restored to before the first task that is not
+            // skipped, so that the task is the next one that is yielded.
+            let lines = self.lines.clone();
+
+            match self.parser.next_task(&mut self.lines, Lines::next) {
+                Some((_, task)) if task.creation_date().is_none_or(|created| created < date) => {}
+                Some(_) => {
+                    self.lines = lines;
+                    return self;
+                }
+                None => return self,
+            }
+        }
+    }
+
+    /// Returns an iterator that skips the first `n` complete tasks and yields
+    /// every other task in order. Unlike `Iterator::skip`, incomplete tasks are
+    /// not counted, which is useful to page through the tasks that are done.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     x 2011-03-01 Call Mom
+    ///     Pay rent
+    ///     x 2011-03-02 Review Tim's pull request
+    ///     x 2011-03-03 Post signs around the neighborhood
+    /// ";
+    /// let page: Vec<String> = data
+    ///     .tasks()
+    ///     .skip_first_n_complete(2)
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(page, ["Pay rent", "x 2011-03-03 Post signs around the neighborhood"]);
+    /// # }
+    /// ```
+    pub fn skip_first_n_complete(self, n: usize) -> impl Iterator<Item = Task<'a>> {
+        let mut remaining = n;
+
+        self.filter(move |task| {
+            if remaining > 0 && task.is_complete() {
+                remaining -= 1;
+                false
+            } else {
+                true
+            }
+        })
+    }
+
+    /// Returns the sum of the priority scores of the incomplete tasks.
+    ///
+    /// A priority of `(A)` scores 26, `(B)` scores 25, and so on through `(Z)`,
+    /// which scores 1. Tasks without a priority, including every complete
+    /// task, score 0.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     (Z) Post signs around the neighborhood +GarageSale
+    ///     x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    ///     @GroceryStore Eskimo pies
+    /// ";
+    ///
+    /// assert_eq!(data.tasks().sum_priority(), 26 + 25 + 1);
+    /// # }
+    /// ```
+    pub fn sum_priority(self) -> u64 {
+        self.sum_priority_with(priority_score)
+    }
+
+    /// Returns the sum of the priority scores of the incomplete tasks, using
+    /// `score` to assign a score to each priority. Tasks without a priority
+    /// score 0.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     (C) Post signs around the neighborhood +GarageSale
+    /// ";
+    ///
+    /// let total = data.tasks().sum_priority_with(|priority| match priority {
+    ///     Priority::A => 100,
+    ///     Priority::B => 10,
+    ///     _ => 1,
+    /// });
+    ///
+    /// assert_eq!(total, 111);
+    /// # }
+    /// ```
+    pub fn sum_priority_with<F>(self, score: F) -> u64
+    where
+        F: Fn(Priority) -> u64,
+    {
+        self.filter_map(|task| task.priority()).map(score).sum()
+    }
+
+    /// Returns an iterator of the tasks that were created before `date`, which
+    /// ends at the first task created on or after `date`. Tasks without a
+    /// creation date are yielded, as [`skip_before_date`] skips them, so the
+    /// two methods split sorted input at the same task.
+    ///
+    /// As with [`skip_before_date`], the lines after the first later task are
+    /// not parsed, which is only correct for input that is sorted by creation
+    /// date.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let data = "
+    ///     2011-03-01 Call Mom
+    ///     2011-03-02 Schedule Goodwill pickup
+    ///     2011-03-03 Post signs around the neighborhood
+    /// ";
+    /// let date = Date::from_ymd_opt(2011, 3, 2).unwrap();
+    /// let tasks: Vec<String> = data
+    ///     .tasks()
+    ///     .take_until_date(date)
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(tasks, ["2011-03-01 Call Mom"]);
+    /// # }
+    /// ```
+    ///
+    /// [`skip_before_date`]: #method.skip_before_date
+    pub fn take_until_date(self, date: Date) -> impl Iterator<Item = Task<'a>> {
+        self.take_while(move |task| task.creation_date().is_none_or(|created| created < date))
+    }
+
+    /// Returns an iterator that calls `callback` with each non-blank line that
+    /// is malformed rather than silently passing over it, which is useful to
+    /// find out why tasks are missing. The line is passed without its leading
+    /// and trailing whitespace.
+    ///
+    /// A line is malformed if it can't be parsed, such as `(A`, or if it is
+    /// rejected by a parser in strict mode. Unlike the other methods of
+    /// `Iter`, a line that can't be parsed does not end iteration, so the tasks
+    /// after it are still yielded. Blank lines and tasks that are skipped by
+    /// the other options of the parser are skipped on purpose, so the callback
+    /// is not called for them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use std::cell::RefCell;
+    /// use todotxt::parser::{ParseMode, Parser};
+    ///
+    /// let data = "
+    ///     Call Mom
+    ///
+    ///     x 2011-03-01 2011-03-02 Pay rent
+    ///     File taxes
+    ///     (A
+    ///     Water the plants
+    /// ";
+    /// let skipped = RefCell::new(Vec::new());
+    /// let count = Parser::new()
+    ///     .mode(ParseMode::Strict)
+    ///     .parse_str(data)
+    ///     .warn_on_skip(|line| skipped.borrow_mut().push(line.to_owned()))
+    ///     .count();
+    ///
+    /// assert_eq!(count, 3);
+    /// assert_eq!(*skipped.borrow(), ["x 2011-03-01 2011-03-02 Pay rent", "(A"]);
+    /// # }
+    /// ```
+    pub fn warn_on_skip<F>(self, callback: F) -> impl Iterator<Item = Task<'a>>
+    where
+        F: Fn(&str),
+    {
+        let Iter { mut lines, parser } = self;
+
+        iter::from_fn(move || {
+            let (_, task) = parser.next_task_or_warn(&mut lines, Lines::next, Some(&callback))?;
+            Some(task)
+        })
+        .fuse()
+    }
+
+    /// Returns an iterator that calls `callback(tasks_parsed, lines_scanned)`
+    /// every 100 lines. This is useful to report progress while parsing large
+    /// files. Use [`with_progress_interval`] to report at a different interval.
+    ///
+    /// Blank lines count towards the lines scanned. Once the input is
+    /// exhausted, the callback is called a final time with the totals unless
+    /// they were just reported.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use std::cell::Cell;
+    ///
+    /// let data = "Call Mom @phone\n\n".repeat(150);
+    /// let calls = Cell::new(0);
+    /// let last = Cell::new((0, 0));
+    /// let count = data
+    ///     .tasks()
+    ///     .with_progress(|parsed, scanned| {
+    ///         calls.set(calls.get() + 1);
+    ///         last.set((parsed, scanned));
+    ///     })
+    ///     .count();
+    ///
+    /// assert_eq!(count, 150);
+    /// assert_eq!(calls.get(), 3);
+    /// assert_eq!(last.get(), (150, 300));
+    /// # }
+    /// ```
+    ///
+    /// [`with_progress_interval`]: #method.with_progress_interval
+    pub fn with_progress<F>(self, callback: F) -> ProgressIter<'a>
+    where
+        F: Fn(usize, usize) + 'a,
+    {
+        self.with_progress_interval(100, callback)
+    }
+
+    /// Returns an iterator that calls `callback(tasks_parsed, lines_scanned)`
+    /// every `interval` lines. An interval of 0 is treated as 1. See
+    /// [`with_progress`].
+    ///
+    /// [`with_progress`]: #method.with_progress
+    pub fn with_progress_interval<F>(self, interval: usize, callback: F) -> ProgressIter<'a>
+    where
+        F: Fn(usize, usize) + 'a,
+    {
+        ProgressIter {
+            callback: Box::new(callback),
+            done: false,
+            interval: interval.max(1),
+            lines: self.lines,
+            parsed: 0,
+            parser: self.parser,
+            reported: false,
+            scanned: 0,
+        }
+    }
+
+    /// Returns an iterator over the tasks that have a special tag with the
+    /// provided key. See [`without_special`] for the inverse.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     Pay rent due:2011-03-01
+    ///     Call Mom @phone
+    ///     File taxes due:2011-04-15
+    /// ";
+    /// let scheduled: Vec<String> = data
+    ///     .tasks()
+    ///     .with_special("due")
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(scheduled, ["Pay rent due:2011-03-01", "File taxes due:2011-04-15"]);
+    /// # }
+    /// ```
+    ///
+    /// [`without_special`]: #method.without_special
+    pub fn with_special<'k>(self, key: &'k str) -> impl Iterator<Item = Task<'a>> + 'k
+    where
+        'a: 'k,
+    {
+        self.filter(move |task| task.has_special_key(key))
+    }
+
+    /// Returns an iterator over the tasks that do not have a special tag with
+    /// the provided key, e.g. the tasks without a `due:` tag. See
+    /// [`with_special`] for the inverse.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     Pay rent due:2011-03-01
+    ///     Call Mom @phone
+    ///     File taxes due:2011-04-15
+    /// ";
+    /// let unscheduled: Vec<String> = data
+    ///     .tasks()
+    ///     .without_special("due")
+    ///     .map(|task| task.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(unscheduled, ["Call Mom @phone"]);
+    /// # }
+    /// ```
+    ///
+    /// [`with_special`]: #method.with_special
+    pub fn without_special<'k>(self, key: &'k str) -> impl Iterator<Item = Task<'a>> + 'k
+    where
+        'a: 'k,
+    {
+        self.filter(move |task| !task.has_special_key(key))
+    }
 }
 
 impl<'a> DoubleEndedIterator for Iter<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        let line = self.lines.next_back()?.trim();
+        let (_, task) = self.parser.next_task(&mut self.lines, Lines::next_back)?;
 
-        if line.is_empty() {
-            self.next_back()
-        } else {
-            parse::<Self::Item>(line)
+        Some(task)
+    }
+}
+
+impl<'a> FusedIterator for IndentedIter<'a> {}
+
+impl<'a> Iterator for IndentedIter<'a> {
+    type Item = Indented<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            // Lines are trimmed slices of the input, so the whitespace that
+            // was trimmed from the start of a line precedes it in the input.
+            let offset = line.as_ptr() as usize - self.input.as_ptr() as usize;
+            let start =
+                memrchr(b'\n', &self.input.as_bytes()[..offset]).map_or(0, |index| index + 1);
+            let leading = &self.input[start..offset];
+            let end = leading
+                .find(|c| c != ' ' && c != '\t')
+                .unwrap_or(leading.len());
+
+            return parse_line(line, || line_number(self.input, line)).map(|task| Indented {
+                indentation: &leading[..end],
+                task,
+            });
         }
     }
 }
@@ -74,23 +2064,364 @@ impl<'a> Iterator for Iter<'a> {
     type Item = Task<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let line = self.lines.next()?.trim();
+        let (_, task) = self.parser.next_task(&mut self.lines, Lines::next)?;
 
-        if line.is_empty() {
-            self.next()
-        } else {
-            parse::<Self::Item>(line)
+        Some(task)
+    }
+}
+
+impl<'a> Debug for ProgressIter<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressIter")
+            .field("interval", &self.interval)
+            .field("lines", &self.lines)
+            .field("parsed", &self.parsed)
+            .field("scanned", &self.scanned)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> FusedIterator for ProgressIter<'a> {}
+
+impl<'a> Iterator for ProgressIter<'a> {
+    type Item = Task<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let line = match self.lines.next() {
+                Some(line) => line,
+                None => break,
+            };
+
+            self.scanned += 1;
+            self.reported = false;
+
+            let task = if line.is_empty() {
+                None
+            } else {
+                // A line that fails to parse ends iteration, as in `Iter`.
+                match parse_line(line, || line_number(self.lines.input, line)) {
+                    Some(task) if self.parser.rejects(line, &task) || self.parser.skips(&task) => {
+                        None
+                    }
+                    Some(task) => Some(task),
+                    None => break,
+                }
+            };
+
+            if task.is_some() {
+                self.parsed += 1;
+            }
+
+            if self.scanned.is_multiple_of(self.interval) {
+                (self.callback)(self.parsed, self.scanned);
+                self.reported = true;
+            }
+
+            if task.is_some() {
+                return task;
+            }
+        }
+
+        if !self.done && !self.reported {
+            (self.callback)(self.parsed, self.scanned);
+        }
+
+        self.done = true;
+        None
+    }
+}
+
+impl<'a> DoubleEndedIterator for Lines<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let bytes = self.data.as_bytes();
+        let end = bytes.len() - (bytes.last() == Some(&b'\n')) as usize;
+        let (line, rest) = match memrchr(b'\n', &bytes[..end]) {
+            Some(index) => (&self.data[index + 1..end], &self.data[..=index]),
+            None => (&self.data[..end], ""),
+        };
+
+        self.data = rest;
+        Some(trim(line))
+    }
+}
+
+impl<'a> Lines<'a> {
+    fn new(input: &'a str) -> Self {
+        Lines { data: input, input }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = match memchr(b'\n', self.data.as_bytes()) {
+            Some(index) => (&self.data[..index], &self.data[index + 1..]),
+            None => (self.data, ""),
+        };
+
+        self.data = rest;
+        Some(trim(line))
+    }
+}
+
+impl<R: BufRead> ReadIter<R> {
+    /// Returns an iterator of tasks read from `reader` a line at a time. See
+    /// [`ReadInput::tasks`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use todotxt::parser::ReadIter;
+    ///
+    /// let reader = Cursor::new("(A) Call Mom @phone\n\nx Pay rent\n");
+    /// let tasks: Vec<Task<'static>> = ReadIter::new(reader).map(Result::unwrap).collect();
+    ///
+    /// assert_eq!(tasks.len(), 2);
+    /// assert_eq!(tasks[0].priority(), Some(Priority::A));
+    /// # }
+    /// ```
+    ///
+    /// [`ReadInput::tasks`]: trait.ReadInput.html#tymethod.tasks
+    pub fn new(reader: R) -> Self {
+        ReadIter {
+            buffer: String::new(),
+            line: 0,
+            reader,
         }
     }
+
+    /// Consumes `self` and returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for ReadIter<R> {
+    type Item = io::Result<Task<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => return Some(Err(error)),
+            }
+
+            self.line += 1;
+
+            let line = trim(&self.buffer);
+            let number = self.line;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(task) = parse_line(line, || number) {
+                return Some(Ok(task.into_owned()));
+            }
+        }
+    }
+}
+
+impl ReadInput for Stdin {
+    type Reader = StdinLock<'static>;
+
+    fn tasks(self) -> ReadIter<Self::Reader> {
+        ReadIter::new(self.lock())
+    }
+}
+
+impl<'a> ReadInput for StdinLock<'a> {
+    type Reader = StdinLock<'a>;
+
+    fn tasks(self) -> ReadIter<Self::Reader> {
+        ReadIter::new(self)
+    }
+}
+
+impl<'a> FusedIterator for TryIter<'a> {}
+
+impl<'a> Iterator for TryIter<'a> {
+    type Item = Result<Task<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+
+            self.line += 1;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            // Lines are slices of the input.
+            let offset = line.as_ptr() as usize - self.input.as_ptr() as usize;
+            let number = self.line;
+            let task = match parse_line(line, || number) {
+                Some(task) => task,
+                None => {
+                    return Some(Err(ParseError {
+                        #[cfg(feature = "verbose-errors")]
+                        context: vec![context::LINE],
+                        kind: ParseErrorKind::Unparseable,
+                        line: self.line,
+                        span: offset..offset + line.len(),
+                    }))
+                }
+            };
+
+            // Control characters are removed from the task as it is parsed, so
+            // they are looked for in the line instead.
+            let kind = match find_control(line) {
+                Some(_) => Some(ParseErrorKind::ControlCharacter),
+                None => task.validate().first().copied(),
+            };
+
+            return match kind {
+                None => Some(Ok(task)),
+                Some(kind) => {
+                    let span = kind.span(line, &task);
+
+                    Some(Err(ParseError {
+                        #[cfg(feature = "verbose-errors")]
+                        context: kind.context(&task),
+                        kind,
+                        line: self.line,
+                        span: offset + span.start..offset + span.end,
+                    }))
+                }
+            };
+        }
+    }
+}
+
+#[cfg(all(feature = "dates", feature = "rayon"))]
+impl<'a> ParallelIter<'a> {
+    /// Computes statistics about the tasks in parallel. The result is
+    /// identical to [`Iter::reduce_to_stats`], so the tasks after a line that
+    /// fails to parse are not counted.
+    ///
+    /// Each thread folds the tasks that it parses into its own statistics,
+    /// which are then combined in order with [`TaskStats::merge`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    /// ";
+    ///
+    /// assert_eq!(data.par_tasks().par_reduce_to_stats(), data.tasks().reduce_to_stats());
+    /// # }
+    /// ```
+    ///
+    /// [`Iter::reduce_to_stats`]: struct.Iter.html#method.reduce_to_stats
+    /// [`TaskStats::merge`]: ../struct.TaskStats.html#method.merge
+    pub fn par_reduce_to_stats(self) -> TaskStats {
+        // Read the date on this thread, where a test date may be set.
+        let today = time::today();
+        let input = self.input;
+
+        // Each fold notes whether it reached a line that fails to parse, and
+        // rayon combines the folds in the order of the lines, so the
+        // statistics of the lines after it are dropped.
+        let (stats, _) = self
+            .lines
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .fold(
+                || (TaskStats::default(), false),
+                |(mut stats, stopped), line| {
+                    if stopped {
+                        return (stats, true);
+                    }
+
+                    match parse_line(line, || line_number(input, line)) {
+                        Some(task) => {
+                            stats.add(&task, today);
+                            (stats, false)
+                        }
+                        None => (stats, true),
+                    }
+                },
+            )
+            .reduce(
+                || (TaskStats::default(), false),
+                |(mut stats, stopped), (other, other_stopped)| {
+                    if stopped {
+                        return (stats, true);
+                    }
+
+                    stats.merge(other);
+                    (stats, other_stopped)
+                },
+            );
+
+        stats
+    }
 }
 
 #[cfg(feature = "rayon")]
 impl ParallelInput for str {
-    fn par_tasks(&self) -> ParallelIter {
+    fn par_tasks(&self) -> ParallelIter<'_> {
         ParallelIter {
+            input: self,
             lines: self.par_lines(),
         }
     }
+
+    fn par_tasks_chunked(&self, chunk_size: usize) -> ParallelChunks<'_> {
+        ParallelChunks {
+            chunk_size: chunk_size.max(1),
+            data: self,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a> ParallelIterator for ParallelChunks<'a> {
+    type Item = Task<'a>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let chunk_size = self.chunk_size;
+        let input = self.data;
+
+        rayon_iter::split(self.data, |data| split_chunk(data, chunk_size))
+            .flat_map_iter(move |data| {
+                Lines { data, input }
+                    .filter(|line| !line.is_empty())
+                    .filter_map(move |line| parse_line(line, || line_number(input, line)))
+            })
+            .drive_unindexed(consumer)
+    }
 }
 
 #[cfg(feature = "rayon")]
@@ -101,10 +2432,12 @@ impl<'a> ParallelIterator for ParallelIter<'a> {
     where
         C: UnindexedConsumer<Self::Item>,
     {
+        let input = self.input;
+
         self.lines
             .map(str::trim)
             .filter(|line| !line.is_empty())
-            .filter_map(parse::<Self::Item>)
+            .filter_map(|line| parse_line(line, || line_number(input, line)))
             .drive_unindexed(consumer)
     }
 }
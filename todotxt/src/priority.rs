@@ -1,9 +1,14 @@
-use crate::parser::Parse;
+//! Contains the priority of tasks and the ways that it can be serialized.
+
+use crate::parser::{error, incomplete, Parse};
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     fmt::{self, Display, Formatter},
+    str,
 };
 
 /// The priority of an incomplete task.
@@ -26,17 +31,136 @@ use std::{
 /// # }
 /// ```
 #[allow(missing_docs)]
-#[cfg_attr(rustfmt, rustfmt_skip)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq)]
+#[rustfmt::skip]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Priority {
     A, B, C, D, E, F, G, H, I, J, K, L, M,
     N, O, P, Q, R, S, T, U, V, W, X, Y, Z
 }
 
+impl Priority {
+    /// Compares two optional priorities in the order that tasks are listed by
+    /// priority: `(A)` first, then `(B)` through `(Z)`, and tasks without a
+    /// priority last.
+    ///
+    /// The ordering of `Option<Priority>` lists `(A)` first and `None` last only
+    /// when sorting in descending order. This function sorts the same way in
+    /// ascending order, so it can be combined with other ascending keys.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let mut priorities = [None, Some(Priority::C), Some(Priority::A), None, Some(Priority::B)];
+    ///
+    /// priorities.sort_by(|a, b| Priority::cmp_opt(*a, *b));
+    ///
+    /// assert_eq!(
+    ///     priorities,
+    ///     [Some(Priority::A), Some(Priority::B), Some(Priority::C), None, None]
+    /// );
+    /// # }
+    /// ```
+    pub fn cmp_opt(a: Option<Priority>, b: Option<Priority>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    /// Returns the number of steps that `self` is above `other`. The result is
+    /// negative if `self` is a lower priority than `other`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::Priority;
+    /// #
+    /// # fn main() {
+    /// assert_eq!(Priority::A.distance(Priority::C), 2);
+    /// assert_eq!(Priority::C.distance(Priority::A), -2);
+    /// assert_eq!(Priority::B.distance(Priority::B), 0);
+    /// assert_eq!(Priority::A.distance(Priority::Z), 25);
+    /// # }
+    /// ```
+    pub fn distance(self, other: Priority) -> i8 {
+        other as i8 - self as i8
+    }
+
+    /// Returns the priority at `index`, where 0 is `A`.
+    pub(crate) fn from_index(index: u8) -> Option<Priority> {
+        use Priority::*;
+
+        #[rustfmt::skip]
+        let priorities = [
+            A, B, C, D, E, F, G, H, I, J, K, L, M,
+            N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        ];
+
+        priorities.get(usize::from(index)).copied()
+    }
+
+    /// Returns `true` if `self` and `other` are one step apart, e.g. `(A)` and
+    /// `(B)`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::Priority;
+    /// #
+    /// # fn main() {
+    /// assert!(Priority::A.is_adjacent(Priority::B));
+    /// assert!(Priority::B.is_adjacent(Priority::A));
+    /// assert!(!Priority::A.is_adjacent(Priority::A));
+    /// assert!(!Priority::A.is_adjacent(Priority::C));
+    /// # }
+    /// ```
+    pub fn is_adjacent(self, other: Priority) -> bool {
+        self.distance(other).abs() == 1
+    }
+
+    /// Returns the priority `amount` steps below `self`, or above it if
+    /// `amount` is negative, clamped to the range from `A` to `Z`.
+    pub(crate) fn shift(self, amount: i8) -> Priority {
+        let index = (i16::from(self as u8) + i16::from(amount)).clamp(0, 25);
+
+        Priority::from_index(index as u8).unwrap_or(self)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Priority {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        use Priority::*;
+
+        #[rustfmt::skip]
+        let priorities = [
+            A, B, C, D, E, F, G, H, I, J, K, L, M,
+            N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        ];
+
+        u.choose(&priorities).copied()
+    }
+}
+
 impl Display for Priority {
+    /// Width, fill, alignment, and precision apply to the whole priority,
+    /// parentheses included.
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "({:?})", self)
+        let text = [b'(', b'A' + *self as u8, b')'];
+
+        f.pad(str::from_utf8(&text).map_err(|_| fmt::Error)?)
     }
 }
 
@@ -44,52 +168,156 @@ impl<'a> Parse<'a> for Priority {
     type Output = Priority;
 
     fn parse(input: &str) -> nom::IResult<&str, Self::Output> {
-        named!(value<&str, Priority>,
-            alt!(
-                value!(Priority::A, char!('A')) |
-                value!(Priority::B, char!('B')) |
-                value!(Priority::C, char!('C')) |
-                value!(Priority::D, char!('D')) |
-                value!(Priority::E, char!('E')) |
-                value!(Priority::F, char!('F')) |
-                value!(Priority::G, char!('G')) |
-                value!(Priority::H, char!('H')) |
-                value!(Priority::I, char!('I')) |
-                value!(Priority::J, char!('J')) |
-                value!(Priority::K, char!('K')) |
-                value!(Priority::L, char!('L')) |
-                value!(Priority::M, char!('M')) |
-                value!(Priority::N, char!('N')) |
-                value!(Priority::O, char!('O')) |
-                value!(Priority::P, char!('P')) |
-                value!(Priority::Q, char!('Q')) |
-                value!(Priority::R, char!('R')) |
-                value!(Priority::S, char!('S')) |
-                value!(Priority::T, char!('T')) |
-                value!(Priority::U, char!('U')) |
-                value!(Priority::V, char!('V')) |
-                value!(Priority::W, char!('W')) |
-                value!(Priority::X, char!('X')) |
-                value!(Priority::Y, char!('Y')) |
-                value!(Priority::Z, char!('Z'))
-            )
-        );
-
-        delimited!(input, char!('('), value, char!(')'))
+        match input.as_bytes() {
+            [b'(', letter, b')', ..] if letter.is_ascii_uppercase() => {
+                match Priority::from_index(letter - b'A') {
+                    Some(priority) => Ok((&input[3..], priority)),
+                    None => error(input, nom::ErrorKind::Alt),
+                }
+            }
+            // `(` and `(A` may be the start of a priority that has not been
+            // read in full.
+            [] | [b'('] => incomplete(),
+            [b'(', letter] if letter.is_ascii_uppercase() => incomplete(),
+            _ => error(input, nom::ErrorKind::Char),
+        }
     }
 }
 
-impl PartialOrd for Priority {
-    fn partial_cmp(&self, other: &Priority) -> Option<Ordering> {
+impl Ord for Priority {
+    fn cmp(&self, other: &Priority) -> Ordering {
         let lhs = *self as usize;
         let rhs = *other as usize;
 
-        if lhs == rhs {
-            Some(Ordering::Equal)
-        } else if lhs < rhs {
-            Some(Ordering::Greater)
-        } else {
-            Some(Ordering::Less)
+        rhs.cmp(&lhs)
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Priority) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Serializes priorities as integers from 1 for `A` to 26 for `Z`, for use
+/// with `#[serde(with = "todotxt::priority::serde_numeric")]`. Priorities are
+/// serialized as letters, such as `"A"`, by default.
+///
+/// A higher priority has a lower number, so that sorting the numbers in
+/// ascending order sorts the priorities in the same order as their letters.
+/// Deserialization fails for integers outside of the range from 1 to 26.
+///
+/// Use the [`option`] module for an `Option<Priority>`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate serde_json;
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, PartialEq, Serialize)]
+/// struct Row {
+///     #[serde(with = "todotxt::priority::serde_numeric::option")]
+///     priority: Option<Priority>,
+/// }
+///
+/// let row = Row { priority: Some(Priority::C) };
+/// let json = serde_json::to_string(&row).unwrap();
+///
+/// assert_eq!(json, r#"{"priority":3}"#);
+/// assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+/// assert!(serde_json::from_str::<Row>(r#"{"priority":27}"#).is_err());
+/// # }
+/// ```
+///
+/// [`option`]: option/index.html
+#[cfg(feature = "serde")]
+pub mod serde_numeric {
+    use super::Priority;
+    use serde::{
+        de::{Error, Unexpected},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+    use std::convert::TryFrom;
+
+    /// A priority that is serialized as an integer.
+    struct Numeric(Priority);
+
+    /// Serializes `priority` as an integer from 1 to 26.
+    pub fn serialize<S>(priority: &Priority, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Numeric(*priority).serialize(serializer)
+    }
+
+    /// Deserializes a priority from an integer from 1 to 26.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Priority, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Numeric::deserialize(deserializer).map(|Numeric(priority)| priority)
+    }
+
+    /// Serializes an optional priority as an integer from 1 to 26, or as none
+    /// if it is absent. See the [parent module] for an example.
+    ///
+    /// [parent module]: ../index.html
+    pub mod option {
+        use super::{Numeric, Priority};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        /// Serializes `priority` as an integer from 1 to 26, or as none.
+        pub fn serialize<S>(priority: &Option<Priority>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            priority.map(Numeric).serialize(serializer)
+        }
+
+        /// Deserializes an optional priority from an integer from 1 to 26.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Priority>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let priority = Option::<Numeric>::deserialize(deserializer)?;
+
+            Ok(priority.map(|Numeric(priority)| priority))
+        }
+    }
+
+    impl Serialize for Numeric {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_u8(self.0 as u8 + 1)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Numeric {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let value = u64::deserialize(deserializer)?;
+            let priority = value
+                .checked_sub(1)
+                .and_then(|index| u8::try_from(index).ok())
+                .and_then(Priority::from_index);
+
+            match priority {
+                Some(priority) => Ok(Numeric(priority)),
+                None => Err(D::Error::invalid_value(
+                    Unexpected::Unsigned(value),
+                    &"an integer from 1 to 26",
+                )),
+            }
         }
     }
 }
@@ -0,0 +1,176 @@
+use crate::parser::ParseSpecial;
+#[cfg(feature = "dates")]
+use chrono::{Days, Months, NaiveDate};
+use std::fmt::{self, Display, Formatter};
+
+/// How often a task repeats, as written in a `rec:` special tag.
+///
+/// The value of the tag is a number followed by a unit, e.g. `rec:1w` for a
+/// task that repeats every week. A `+` before the number, e.g. `rec:+1w`,
+/// makes the recurrence strict. See [`next_occurrence`] for how strict and
+/// non-strict recurrences differ.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{Recurrence, RecurrenceUnit};
+///
+/// let task = "Water the plants rec:+2w due:2011-03-01".tasks().next().unwrap();
+/// let recurrence = task.parse_special::<Recurrence>().unwrap();
+///
+/// assert_eq!(recurrence.amount(), 2);
+/// assert_eq!(recurrence.unit(), RecurrenceUnit::Week);
+/// assert!(recurrence.is_strict());
+/// assert_eq!(recurrence.to_string(), "+2w");
+/// # }
+/// ```
+///
+/// [`next_occurrence`]: #method.next_occurrence
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Recurrence {
+    amount: u32,
+    strict: bool,
+    unit: RecurrenceUnit,
+}
+
+/// The unit of a [`Recurrence`].
+///
+/// [`Recurrence`]: struct.Recurrence.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RecurrenceUnit {
+    /// `d`
+    Day,
+    /// `w`
+    Week,
+    /// `m`
+    Month,
+    /// `y`
+    Year,
+}
+
+impl Recurrence {
+    /// Returns a recurrence of `amount` units. `amount` should be at least 1.
+    pub fn new(amount: u32, unit: RecurrenceUnit, strict: bool) -> Self {
+        Recurrence {
+            amount,
+            strict,
+            unit,
+        }
+    }
+
+    /// Returns the number of units between occurrences.
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
+    /// Returns `true` if the recurrence is strict, i.e. it was written with a
+    /// leading `+`.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the due date of the next occurrence of a task that was due on
+    /// `due_date` and completed on `completion_date`.
+    ///
+    /// A strict recurrence advances from the previous due date, so the task
+    /// stays on a fixed schedule no matter when it is completed. A non-strict
+    /// recurrence advances from the completion date. If a strict recurrence
+    /// has no previous due date, it falls back to the completion date.
+    ///
+    /// Adding months or years to a date that does not exist in the resulting
+    /// month, such as January 31st, results in the last day of that month.
+    /// `None` is returned if the result is out of range.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{chrono::NaiveDate, Recurrence, RecurrenceUnit};
+    ///
+    /// let due = NaiveDate::from_ymd_opt(2011, 3, 1);
+    /// let completed = NaiveDate::from_ymd_opt(2011, 3, 4).unwrap();
+    /// let strict = Recurrence::new(1, RecurrenceUnit::Week, true);
+    /// let relaxed = Recurrence::new(1, RecurrenceUnit::Week, false);
+    ///
+    /// assert_eq!(strict.next_occurrence(due, completed), NaiveDate::from_ymd_opt(2011, 3, 8));
+    /// assert_eq!(relaxed.next_occurrence(due, completed), NaiveDate::from_ymd_opt(2011, 3, 11));
+    /// assert_eq!(strict.next_occurrence(None, completed), NaiveDate::from_ymd_opt(2011, 3, 11));
+    /// # }
+    /// ```
+    #[cfg(feature = "dates")]
+    pub fn next_occurrence(
+        &self,
+        due_date: Option<NaiveDate>,
+        completion_date: NaiveDate,
+    ) -> Option<NaiveDate> {
+        let from = match due_date {
+            Some(due_date) if self.strict => due_date,
+            _ => completion_date,
+        };
+
+        match self.unit {
+            RecurrenceUnit::Day => from.checked_add_days(Days::new(u64::from(self.amount))),
+            RecurrenceUnit::Week => from.checked_add_days(Days::new(u64::from(self.amount) * 7)),
+            RecurrenceUnit::Month => from.checked_add_months(Months::new(self.amount)),
+            RecurrenceUnit::Year => {
+                from.checked_add_months(Months::new(self.amount.checked_mul(12)?))
+            }
+        }
+    }
+
+    /// Returns the unit of the recurrence.
+    pub fn unit(&self) -> RecurrenceUnit {
+        self.unit
+    }
+}
+
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let unit = match self.unit {
+            RecurrenceUnit::Day => 'd',
+            RecurrenceUnit::Week => 'w',
+            RecurrenceUnit::Month => 'm',
+            RecurrenceUnit::Year => 'y',
+        };
+
+        if self.strict {
+            f.write_str("+")?;
+        }
+
+        write!(f, "{}{}", self.amount, unit)
+    }
+}
+
+impl<'a> ParseSpecial<'a> for Recurrence {
+    const KEY: &'static str = "rec";
+
+    fn parse_special(value: &'a str) -> Option<Self> {
+        let (strict, value) = match value.strip_prefix('+') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let (amount, unit) = match *value.as_bytes().last()? {
+            b'd' => (&value[..value.len() - 1], RecurrenceUnit::Day),
+            b'w' => (&value[..value.len() - 1], RecurrenceUnit::Week),
+            b'm' => (&value[..value.len() - 1], RecurrenceUnit::Month),
+            b'y' => (&value[..value.len() - 1], RecurrenceUnit::Year),
+            _ => return None,
+        };
+
+        if amount.is_empty() || !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        match amount.parse() {
+            Ok(0) | Err(_) => None,
+            Ok(amount) => Some(Recurrence::new(amount, unit, strict)),
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! Renders tasks as plain text, either as a table with aligned columns, e.g.
+//! for a weekly review in a terminal, or as a numbered listing in the format
+//! of `todo.sh ls`.
+
+use crate::{compact::days_from_civil, date::Date, parser::parse_line, task::Task};
+#[cfg(feature = "dates")]
+use chrono::Datelike;
+
+/// A column of a table rendered by [`render_table`].
+///
+/// [`render_table`]: fn.render_table.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Column {
+    /// The number of days from the creation date of the task to the provided
+    /// date, e.g. `12d`.
+    Age(Date),
+    /// The creation date of the task.
+    CreationDate,
+    /// The description of the task, which is truncated with an ellipsis to fit
+    /// the width of the table.
+    Description,
+    /// The due date of the task from its `due:` tag.
+    DueDate,
+    /// The line number that the task is paired with.
+    Line,
+    /// The priority of the task, e.g. `(A)`.
+    Priority,
+    /// The projects of the task, separated by spaces.
+    Projects,
+}
+
+/// The options of [`render_ls`]. By default, the listing is sorted and ends
+/// with a summary labeled `TODO`, as it is by `todo.sh ls`.
+///
+/// [`render_ls`]: fn.render_ls.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LsOptions {
+    label: &'static str,
+    sort: bool,
+    summary: bool,
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Age(_) => "Age",
+            Column::CreationDate => "Created",
+            Column::Description => "Description",
+            Column::DueDate => "Due",
+            Column::Line => "#",
+            Column::Priority => "Pri",
+            Column::Projects => "Projects",
+        }
+    }
+
+    fn cell(self, line: usize, task: &Task<'_>) -> String {
+        match self {
+            Column::Age(today) => task
+                .creation_date()
+                .map(|date| format!("{}d", days(today) - days(date)))
+                .unwrap_or_default(),
+            Column::CreationDate => task
+                .creation_date()
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            // Tabs and control characters would break the alignment of the
+            // columns.
+            Column::Description => task
+                .description()
+                .chars()
+                .filter_map(|c| match c {
+                    _ if c.is_whitespace() => Some(' '),
+                    _ if c.is_control() => None,
+                    _ => Some(c),
+                })
+                .collect(),
+            Column::DueDate => task
+                .due_date()
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            Column::Line => line.to_string(),
+            Column::Priority => task
+                .priority()
+                .map(|priority| priority.to_string())
+                .unwrap_or_default(),
+            Column::Projects => task.projects_iter().collect::<Vec<_>>().join(" "),
+        }
+    }
+}
+
+/// Renders `tasks`, each paired with its line number, as a table with a
+/// header row and a row per task. Columns are separated by two spaces, and
+/// trailing spaces are removed from each row.
+///
+/// Every column is as wide as its widest cell, except for the description,
+/// which takes the rest of `width` and is truncated with `…` to fit. The
+/// width of text is measured in terminal columns, approximately: characters
+/// such as CJK ideographs count as two columns and combining marks as none.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::report::{render_table, Column};
+///
+/// let data = "(A) Call Mom @phone\nPost signs around the neighborhood +GarageSale";
+/// let tasks: Vec<(usize, Task)> = data.tasks().enumerate().map(|(i, t)| (i + 1, t)).collect();
+/// let columns = [Column::Line, Column::Priority, Column::Description];
+///
+/// assert_eq!(
+///     render_table(&tasks, &columns, 32),
+///     "#  Pri  Description\n\
+///      1  (A)  Call Mom @phone\n\
+///      2       Post signs around the n…\n"
+/// );
+/// # }
+/// ```
+pub fn render_table(tasks: &[(usize, Task<'_>)], columns: &[Column], width: usize) -> String {
+    let rows: Vec<Vec<String>> = tasks
+        .iter()
+        .map(|(line, task)| {
+            columns
+                .iter()
+                .map(|column| column.cell(*line, task))
+                .collect()
+        })
+        .collect();
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .map(|row| text_width(&row[index]))
+                .fold(text_width(column.header()), usize::max)
+        })
+        .collect();
+
+    // The description takes whatever is left of the width after the other
+    // columns and the separators between them.
+    if let Some(index) = columns.iter().position(|c| *c == Column::Description) {
+        let others: usize = widths
+            .iter()
+            .enumerate()
+            .filter(|&(other, _)| other != index)
+            .map(|(_, width)| width + 2)
+            .sum();
+
+        widths[index] = width.saturating_sub(others).min(widths[index]);
+    }
+
+    let mut output = String::new();
+    let header = columns.iter().map(|column| column.header().to_owned());
+
+    for row in std::iter::once(header.collect()).chain(rows) {
+        let mut line = String::new();
+
+        for (index, cell) in row.iter().enumerate() {
+            let cell = truncate(cell, widths[index]);
+            let padding = widths[index] - text_width(&cell);
+
+            if index > 0 {
+                line.push_str("  ");
+            }
+
+            // Numbers are aligned to the right, and everything else to the left.
+            match columns[index] {
+                Column::Line | Column::Age(_) => {
+                    line.extend(std::iter::repeat_n(' ', padding));
+                    line.push_str(&cell);
+                }
+                _ => {
+                    line.push_str(&cell);
+                    line.extend(std::iter::repeat_n(' ', padding));
+                }
+            }
+        }
+
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+impl LsOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        LsOptions {
+            label: "TODO",
+            sort: true,
+            summary: true,
+        }
+    }
+
+    /// Sets the label of the summary, which `todo.sh` derives from the name of
+    /// the file, e.g. `DONE` for done.txt.
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Sets whether the tasks are sorted by their text, ignoring case, or
+    /// listed in the order of the input.
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Sets whether the listing ends with a `--` line and a summary such as
+    /// `TODO: 4 of 4 tasks shown`, as it does in the verbose mode of `todo.sh`.
+    pub fn summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+}
+
+impl Default for LsOptions {
+    fn default() -> Self {
+        LsOptions::new()
+    }
+}
+
+/// Renders the tasks of `input` that match `filter` as a numbered listing in
+/// the format of `todo.sh ls`, so that the output can be read by scripts that
+/// parse the output of `todo.sh`.
+///
+/// Each line is written as it appears in `input`, after its line number. The
+/// line numbers are zero-padded to the number of digits in the number of
+/// lines in `input`, regardless of how many tasks are shown. Blank lines are
+/// neither shown nor counted in the summary.
+///
+/// Like `todo.sh`, tasks are sorted by comparing their lines byte by byte
+/// with ASCII letters folded to uppercase, and then by their line numbers.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::report::{render_ls, LsOptions};
+///
+/// let data = "\
+/// (B) Schedule Goodwill pickup +GarageSale @phone
+/// Post signs around the neighborhood +GarageSale
+/// (A) Thank Mom for the meatballs @phone
+/// @GroceryStore Eskimo pies
+/// ";
+/// let phone = |task: &Task| task.contexts_iter().any(|context| context == "@phone");
+///
+/// assert_eq!(
+///     render_ls(data, Some(&phone), LsOptions::new()),
+///     "3 (A) Thank Mom for the meatballs @phone\n\
+///      1 (B) Schedule Goodwill pickup +GarageSale @phone\n\
+///      --\n\
+///      TODO: 2 of 4 tasks shown\n"
+/// );
+/// # }
+/// ```
+pub fn render_ls(
+    input: &str,
+    filter: Option<&dyn Fn(&Task<'_>) -> bool>,
+    opts: LsOptions,
+) -> String {
+    let mut lines = Vec::new();
+    let mut padding = 1;
+    let mut total = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        padding = (index + 1).to_string().len();
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let task = match parse_line(line.trim(), || index + 1) {
+            Some(task) => task,
+            None => continue,
+        };
+
+        total += 1;
+
+        if filter.is_none_or(|filter| filter(&task)) {
+            lines.push((index + 1, line));
+        }
+    }
+
+    if opts.sort {
+        lines.sort_by(|a, b| fold_case(a.1).cmp(fold_case(b.1)).then(a.0.cmp(&b.0)));
+    }
+
+    let mut output = String::new();
+
+    for (number, line) in &lines {
+        output.push_str(&format!("{:0width$} {}\n", number, line, width = padding));
+    }
+
+    if opts.summary {
+        output.push_str(&format!(
+            "--\n{}: {} of {} tasks shown\n",
+            opts.label,
+            lines.len(),
+            total
+        ));
+    }
+
+    output
+}
+
+/// Returns the number of terminal columns that `c` takes up, approximately.
+fn char_width(c: char) -> usize {
+    match u32::from(c) {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+fn days(date: Date) -> i64 {
+    days_from_civil(
+        i64::from(date.year()),
+        i64::from(date.month()),
+        i64::from(date.day()),
+    )
+}
+
+/// Returns the bytes of `text` with ASCII letters folded to uppercase, as
+/// `sort -f` compares them.
+fn fold_case(text: &str) -> impl Iterator<Item = u8> + '_ {
+    text.bytes().map(|byte| byte.to_ascii_uppercase())
+}
+
+fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Returns `text` truncated with an ellipsis to at most `width` columns.
+fn truncate(text: &str, width: usize) -> String {
+    if text_width(text) <= width {
+        return text.to_owned();
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+
+    for c in text.chars() {
+        if used + char_width(c) + 1 > width {
+            break;
+        }
+
+        used += char_width(c);
+        truncated.push(c);
+    }
+
+    if width > 0 {
+        truncated.truncate(truncated.trim_end().len());
+        truncated.push('…');
+    }
+
+    truncated
+}
@@ -0,0 +1,141 @@
+use crate::{date, priority::Priority, task::Task};
+use std::cmp::Ordering;
+
+/// A key to sort tasks by. Keys are combined with [`SortKey::compare_all`] or
+/// [`TodoList::sort_by_keys`], where each key breaks the ties of the keys
+/// before it.
+///
+/// Tasks are sorted in ascending order of each key, with the tasks that do
+/// not have a value for the key last, such as tasks without a due date when
+/// sorting by [`DueDate`]. Those tasks are equal to each other, so they are
+/// ordered by the next key, or keep their order in a stable sort.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{SortKey, TodoList, ValueKind};
+///
+/// let data = "
+///     Pay rent order:2
+///     (B) Call Mom order:1
+///     (A) Schedule Goodwill pickup
+///     File taxes order:2 due:2011-03-01
+/// ";
+/// let mut list: TodoList = data.tasks().collect();
+///
+/// list.sort_by_keys(&[
+///     SortKey::TagValue { key: "order", parse: ValueKind::Integer },
+///     SortKey::DueDate,
+/// ]);
+///
+/// let descriptions: Vec<&str> = list.iter().map(|task| task.description()).collect();
+///
+/// assert_eq!(
+///     descriptions,
+///     [
+///         "Call Mom order:1",
+///         "File taxes order:2 due:2011-03-01",
+///         "Pay rent order:2",
+///         "Schedule Goodwill pickup",
+///     ]
+/// );
+/// # }
+/// ```
+///
+/// [`DueDate`]: #variant.DueDate
+/// [`SortKey::compare_all`]: #method.compare_all
+/// [`TodoList::sort_by_keys`]: struct.TodoList.html#method.sort_by_keys
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SortKey {
+    /// The creation date of the task, from the earliest.
+    CreationDate,
+    /// The description of the task, compared byte by byte.
+    Description,
+    /// The due date of the task from its `due:` tag, from the earliest. See
+    /// [`Task::due_date`].
+    ///
+    /// [`Task::due_date`]: struct.Task.html#method.due_date
+    DueDate,
+    /// The priority of the task, from `(A)`. Complete tasks do not have a
+    /// priority. See [`Priority::cmp_opt`].
+    ///
+    /// [`Priority::cmp_opt`]: enum.Priority.html#method.cmp_opt
+    Priority,
+    /// The value of the first special tag with the key `key`, such as
+    /// `order:12`, parsed as `parse`. Values that can't be parsed are ordered
+    /// with the missing ones.
+    TagValue {
+        /// The key of the special tag, without the `:`.
+        key: &'static str,
+        /// How the value of the tag is parsed and compared.
+        parse: ValueKind,
+    },
+}
+
+/// How the value of a special tag is parsed and compared by
+/// [`SortKey::TagValue`].
+///
+/// [`SortKey::TagValue`]: enum.SortKey.html#variant.TagValue
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValueKind {
+    /// A `YYYY-MM-DD` date, from the earliest. Use [`SortKey::DueDate`] to
+    /// sort by `due:` tags that may include a time of day.
+    ///
+    /// [`SortKey::DueDate`]: enum.SortKey.html#variant.DueDate
+    Date,
+    /// A signed integer such as `12` or `-1`, from the smallest.
+    Integer,
+    /// Any text, compared byte by byte.
+    Text,
+}
+
+impl SortKey {
+    /// Compares `a` and `b` by this key.
+    pub fn compare(&self, a: &Task<'_>, b: &Task<'_>) -> Ordering {
+        match *self {
+            SortKey::CreationDate => missing_last(a.creation_date(), b.creation_date()),
+            SortKey::Description => a.description().cmp(b.description()),
+            SortKey::DueDate => missing_last(a.due_date(), b.due_date()),
+            SortKey::Priority => Priority::cmp_opt(a.priority(), b.priority()),
+            SortKey::TagValue { key, parse } => {
+                let (a, b) = (a.get_special(key), b.get_special(key));
+
+                match parse {
+                    ValueKind::Date => {
+                        missing_last(a.and_then(date::parse_exact), b.and_then(date::parse_exact))
+                    }
+                    ValueKind::Integer => missing_last(
+                        a.and_then(|value| value.parse::<i64>().ok()),
+                        b.and_then(|value| value.parse::<i64>().ok()),
+                    ),
+                    ValueKind::Text => missing_last(a, b),
+                }
+            }
+        }
+    }
+
+    /// Compares `a` and `b` by each of `keys` in turn, until one of them is
+    /// not equal. The tasks are equal if they are equal by every key.
+    pub fn compare_all(keys: &[SortKey], a: &Task<'_>, b: &Task<'_>) -> Ordering {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Compares two optional values in ascending order, with `None` last.
+fn missing_last<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
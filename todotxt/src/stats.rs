@@ -0,0 +1,491 @@
+use crate::{parser::parse_line, priority::Priority, tags::Tag, task::Task};
+use chrono::{Duration, NaiveDate};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    io::{self, BufRead},
+};
+
+/// Aggregate statistics about a collection of tasks.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::{chrono::NaiveDate, TaskStats};
+///
+/// let data = "
+///     (A) 2011-03-01 Thank Mom for the meatballs @phone
+///     (B) Schedule Goodwill pickup +GarageSale @phone
+///     2011-03-05 Post signs around the neighborhood +GarageSale
+///     x 2011-03-02 2011-02-20 Review Tim's pull request +TodoTxtTouch @github
+/// ";
+/// let today = NaiveDate::from_ymd_opt(2011, 3, 10).unwrap();
+/// let stats = TaskStats::from_tasks(&data.tasks().collect::<Vec<_>>(), today);
+///
+/// assert_eq!(stats.total(), 4);
+/// assert_eq!(stats.complete(), 1);
+/// assert_eq!(stats.incomplete(), 3);
+/// assert_eq!(stats.completion_percentage(), 25.0);
+/// assert_eq!(stats.priority_count(Priority::A), 1);
+/// assert_eq!(stats.priority_count(Priority::B), 1);
+/// assert_eq!(stats.priority_count(Priority::C), 0);
+/// assert_eq!(stats.context_count("@phone"), 2);
+/// assert_eq!(stats.context_count("@github"), 1);
+/// assert_eq!(stats.project_count("+GarageSale"), 2);
+/// assert_eq!(stats.most_used_context(), Some("@phone"));
+/// assert_eq!(stats.most_used_project(), Some("+GarageSale"));
+/// assert_eq!(stats.oldest_task_age().map(|age| age.num_days()), Some(18));
+/// assert_eq!(stats.newest_task_age().map(|age| age.num_days()), Some(5));
+///
+/// assert_eq!(
+///     stats.report(),
+///     "\
+/// Tasks: 4 (1 complete, 3 incomplete)
+/// Completed: 25.0%
+/// Priorities:
+///   (A) 1
+///   (B) 1
+/// Contexts:
+///   @phone 2
+///   @github 1
+/// Projects:
+///   +GarageSale 2
+///   +TodoTxtTouch 1
+/// Oldest task: 18 days
+/// Newest task: 5 days
+/// "
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaskStats {
+    complete: usize,
+    contexts: HashMap<String, usize>,
+    newest_task_age: Option<Duration>,
+    oldest_task_age: Option<Duration>,
+    overdue: usize,
+    priorities: BTreeMap<Priority, usize>,
+    projects: HashMap<String, usize>,
+    total: usize,
+}
+
+/// The sum of the durations in a special tag across a collection of tasks.
+///
+/// This type is returned by [`TaskStats::sum_durations`].
+///
+/// [`TaskStats::sum_durations`]: struct.TaskStats.html#method.sum_durations
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DurationSum {
+    count: usize,
+    malformed: usize,
+    total: Duration,
+}
+
+impl DurationSum {
+    /// Returns the number of tasks with a well-formed duration.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of tasks that have the tag, but with a value that is
+    /// not a valid duration. These tasks are not included in the total.
+    pub fn malformed(&self) -> usize {
+        self.malformed
+    }
+
+    /// Returns the sum of the well-formed durations.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+impl TaskStats {
+    /// Computes statistics about `tasks`. The age of a task is the time that
+    /// has passed between its creation date and `today`.
+    pub fn from_tasks<'t, 'a: 't, I>(tasks: I, today: NaiveDate) -> Self
+    where
+        I: IntoIterator<Item = &'t Task<'a>>,
+    {
+        let mut stats = TaskStats::default();
+
+        for task in tasks {
+            stats.add(task, today);
+        }
+
+        stats
+    }
+
+    /// Computes statistics about the tasks read from `reader`, one line at a
+    /// time. The result is identical to calling [`from_tasks`] with every task
+    /// in the input, so reading stops at a line that fails to parse, as it
+    /// ends iteration over [`Input::tasks`].
+    ///
+    /// Only the current line is held in memory, in a buffer that is reused for
+    /// each line, so the memory used for tasks does not grow with the size of
+    /// the input. Each distinct context and project is copied into an owned
+    /// string the first time it appears, so memory grows with the number of
+    /// distinct tags rather than the number of tasks.
+    ///
+    /// An error is returned if reading from `reader` fails or the input is not
+    /// valid UTF-8.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use std::io::Cursor;
+    /// use todotxt::{chrono::NaiveDate, TaskStats};
+    ///
+    /// let data = "(A) Call Mom @phone\nx Pay rent due:2011-03-01\nFile taxes due:2011-03-05\n";
+    /// let today = NaiveDate::from_ymd_opt(2011, 3, 10).unwrap();
+    /// let stats = TaskStats::from_reader(Cursor::new(data), today).unwrap();
+    ///
+    /// assert_eq!(stats, TaskStats::from_tasks(&data.tasks().collect::<Vec<_>>(), today));
+    /// assert_eq!(stats.total(), 3);
+    /// assert_eq!(stats.overdue(), 1);
+    /// # }
+    /// ```
+    ///
+    /// [`Input::tasks`]: parser/trait.Input.html#tymethod.tasks
+    /// [`from_tasks`]: #method.from_tasks
+    pub fn from_reader<R>(mut reader: R, today: NaiveDate) -> io::Result<Self>
+    where
+        R: BufRead,
+    {
+        let mut line = String::new();
+        let mut number = 0;
+        let mut stats = TaskStats::default();
+
+        loop {
+            line.clear();
+
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+
+            number += 1;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match parse_line(line.trim(), || number) {
+                Some(task) => stats.add(&task, today),
+                None => break,
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Returns the number of complete tasks.
+    pub fn complete(&self) -> usize {
+        self.complete
+    }
+
+    /// Returns the percentage of tasks that are complete, from `0.0` to
+    /// `100.0`. If there are no tasks, `0.0` is returned.
+    pub fn completion_percentage(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.complete as f64 / self.total as f64 * 100.0
+        }
+    }
+
+    /// Returns the number of times the context `value` (e.g. `"@phone"`)
+    /// appears in the tasks.
+    pub fn context_count(&self, value: &str) -> usize {
+        self.contexts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Returns an iterator over each distinct context and the number of times
+    /// it appears, in no particular order.
+    pub fn contexts(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.contexts
+            .iter()
+            .map(|(value, count)| (value.as_str(), *count))
+    }
+
+    /// Returns the number of incomplete tasks.
+    pub fn incomplete(&self) -> usize {
+        self.total - self.complete
+    }
+
+    /// Adds the statistics in `other` to `self`, as if the tasks that `other`
+    /// was computed from had been added to `self`. Both must have been
+    /// computed with the same `today` for the result to be meaningful.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{chrono::NaiveDate, TaskStats};
+    ///
+    /// let data = "
+    ///     (A) 2011-03-01 Thank Mom for the meatballs @phone
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone
+    ///     x 2011-03-02 2011-02-20 Review Tim's pull request +TodoTxtTouch @github
+    /// ";
+    /// let today = NaiveDate::from_ymd_opt(2011, 3, 10).unwrap();
+    /// let tasks: Vec<Task> = data.tasks().collect();
+    /// let (left, right) = tasks.split_at(1);
+    /// let mut stats = TaskStats::from_tasks(left, today);
+    ///
+    /// stats.merge(TaskStats::from_tasks(right, today));
+    /// assert_eq!(stats, TaskStats::from_tasks(&tasks, today));
+    /// # }
+    /// ```
+    pub fn merge(&mut self, other: TaskStats) {
+        self.complete += other.complete;
+        self.overdue += other.overdue;
+        self.total += other.total;
+
+        for (priority, count) in other.priorities {
+            *self.priorities.entry(priority).or_insert(0) += count;
+        }
+
+        for (value, count) in other.contexts {
+            *self.contexts.entry(value).or_insert(0) += count;
+        }
+
+        for (value, count) in other.projects {
+            *self.projects.entry(value).or_insert(0) += count;
+        }
+
+        self.oldest_task_age = self.oldest_task_age.max(other.oldest_task_age);
+        self.newest_task_age = match (self.newest_task_age, other.newest_task_age) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    /// Returns the context that appears most often. Ties are broken by choosing
+    /// the context that sorts first.
+    pub fn most_used_context(&self) -> Option<&str> {
+        most_used(&self.contexts)
+    }
+
+    /// Returns the project that appears most often. Ties are broken by choosing
+    /// the project that sorts first.
+    pub fn most_used_project(&self) -> Option<&str> {
+        most_used(&self.projects)
+    }
+
+    /// Returns the age of the most recently created task. Tasks without a
+    /// creation date are ignored.
+    pub fn newest_task_age(&self) -> Option<Duration> {
+        self.newest_task_age
+    }
+
+    /// Returns the age of the least recently created task. Tasks without a
+    /// creation date are ignored.
+    pub fn oldest_task_age(&self) -> Option<Duration> {
+        self.oldest_task_age
+    }
+
+    /// Returns the number of incomplete tasks with a due date before `today`.
+    pub fn overdue(&self) -> usize {
+        self.overdue
+    }
+
+    /// Returns the number of incomplete tasks with the provided priority.
+    pub fn priority_count(&self, priority: Priority) -> usize {
+        self.priorities.get(&priority).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of times the project `value` (e.g. `"+GarageSale"`)
+    /// appears in the tasks.
+    pub fn project_count(&self, value: &str) -> usize {
+        self.projects.get(value).copied().unwrap_or(0)
+    }
+
+    /// Returns an iterator over each distinct project and the number of times
+    /// it appears, in no particular order.
+    pub fn projects(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.projects
+            .iter()
+            .map(|(value, count)| (value.as_str(), *count))
+    }
+
+    /// Formats the statistics as a human-readable, plain text report.
+    ///
+    /// Priorities are listed from highest to lowest. Contexts and projects are
+    /// listed from most to least frequent.
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+
+        // Writing to a String never fails.
+        let _ = writeln!(
+            report,
+            "Tasks: {} ({} complete, {} incomplete)",
+            self.total,
+            self.complete,
+            self.incomplete()
+        );
+        let _ = writeln!(report, "Completed: {:.1}%", self.completion_percentage());
+
+        if !self.priorities.is_empty() {
+            report.push_str("Priorities:\n");
+
+            for (priority, count) in self.priorities.iter().rev() {
+                let _ = writeln!(report, "  {} {}", priority, count);
+            }
+        }
+
+        for (heading, counts) in &[("Contexts", &self.contexts), ("Projects", &self.projects)] {
+            if counts.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(report, "{}:", heading);
+
+            for (value, count) in by_frequency(counts) {
+                let _ = writeln!(report, "  {} {}", value, count);
+            }
+        }
+
+        if let Some(age) = self.oldest_task_age {
+            let _ = writeln!(report, "Oldest task: {} days", age.num_days());
+        }
+
+        if let Some(age) = self.newest_task_age {
+            let _ = writeln!(report, "Newest task: {} days", age.num_days());
+        }
+
+        report
+    }
+
+    /// Sums the durations in the special tag `key` of `tasks`, e.g. the
+    /// estimates in `est:2h` tags. See [`Task::duration_tag`] for the format
+    /// of a duration. Tasks without the tag are ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{chrono::Duration, TaskStats};
+    ///
+    /// let data = "
+    ///     (A) Thank Mom for the meatballs @phone est:15m
+    ///     (B) Schedule Goodwill pickup +GarageSale @phone est:1h
+    ///     Post signs around the neighborhood +GarageSale est:abc
+    ///     @GroceryStore Eskimo pies
+    /// ";
+    /// let tasks: Vec<Task> = data.tasks().collect();
+    /// let sum = TaskStats::sum_durations(&tasks, "est");
+    ///
+    /// assert_eq!(sum.total(), Duration::minutes(75));
+    /// assert_eq!(sum.count(), 2);
+    /// assert_eq!(sum.malformed(), 1);
+    /// # }
+    /// ```
+    ///
+    /// [`Task::duration_tag`]: struct.Task.html#method.duration_tag
+    pub fn sum_durations<'t, 'a: 't, I>(tasks: I, key: &str) -> DurationSum
+    where
+        I: IntoIterator<Item = &'t Task<'a>>,
+    {
+        let mut sum = DurationSum {
+            count: 0,
+            malformed: 0,
+            total: Duration::zero(),
+        };
+
+        for task in tasks {
+            if task.get_special(key).is_none() {
+                continue;
+            }
+
+            // A sum that overflows is treated like a malformed value so that
+            // the total is always the sum of `count` durations.
+            match task
+                .duration_tag(key)
+                .and_then(|duration| sum.total.checked_add(&duration))
+            {
+                Some(total) => {
+                    sum.count += 1;
+                    sum.total = total;
+                }
+                None => sum.malformed += 1,
+            }
+        }
+
+        sum
+    }
+
+    /// Returns the total number of tasks.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub(crate) fn add(&mut self, task: &Task<'_>, today: NaiveDate) {
+        let description = task.description();
+
+        self.total += 1;
+
+        if task.is_complete() {
+            self.complete += 1;
+        } else if task.due_date().is_some_and(|date| date < today) {
+            self.overdue += 1;
+        }
+
+        if let Some(priority) = task.priority() {
+            *self.priorities.entry(priority).or_insert(0) += 1;
+        }
+
+        if let Some(creation_date) = task.creation_date() {
+            let age = today - creation_date;
+
+            self.oldest_task_age = self.oldest_task_age.max(Some(age));
+            self.newest_task_age = Some(self.newest_task_age.map_or(age, |newest| newest.min(age)));
+        }
+
+        for tag in task.tags() {
+            let counts = match tag {
+                Tag::Context { .. } => &mut self.contexts,
+                Tag::Project { .. } => &mut self.projects,
+                Tag::Special { .. } => continue,
+            };
+
+            // Only copy the value the first time that it appears.
+            match counts.get_mut(tag.as_str(description)) {
+                Some(count) => *count += 1,
+                None => {
+                    counts.insert(tag.as_str(description).to_owned(), 1);
+                }
+            }
+        }
+    }
+}
+
+fn by_frequency(counts: &HashMap<String, usize>) -> Vec<(&str, usize)> {
+    let mut counts: Vec<(&str, usize)> = counts
+        .iter()
+        .map(|(value, count)| (value.as_str(), *count))
+        .collect();
+
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counts
+}
+
+fn most_used(counts: &HashMap<String, usize>) -> Option<&str> {
+    by_frequency(counts).first().map(|(value, _)| *value)
+}
@@ -1,6 +1,10 @@
 #[cfg(feature = "serde")]
-use serde::Serialize;
-use std::{iter::FusedIterator, ops::Index, str::CharIndices};
+use serde::{Deserialize, Serialize};
+use std::{
+    iter::{self, FusedIterator},
+    ops::Index,
+    str::CharIndices,
+};
 
 /// The various tags that can appear within the description of a task.
 ///
@@ -9,9 +13,9 @@ use std::{iter::FusedIterator, ops::Index, str::CharIndices};
 /// tag. The indices are relative to the parsed description of the task in
 /// which the tag belongs to.
 ///
-/// As a convenience, [`Index<Tag>`] is implemented for [`str`]. That means
-/// that you can get the value of a tag by using the tag to index into the
-/// task's description.
+/// The value of a tag can be read from the task's description with
+/// [`as_str`]. As a convenience, [`Index<Tag>`] is also implemented for
+/// [`str`], so `&description[tag]` is equivalent to `tag.as_str(description)`.
 ///
 /// ```
 /// # extern crate todotxt;
@@ -24,7 +28,7 @@ use std::{iter::FusedIterator, ops::Index, str::CharIndices};
 /// let description = task.description();
 ///
 /// for tag in task.tags() {
-///     let value = &description[tag];
+///     let value = tag.as_str(description);
 ///
 ///     println!("tag: {:?}", tag);
 ///     println!("value: {}", value);
@@ -32,15 +36,13 @@ use std::{iter::FusedIterator, ops::Index, str::CharIndices};
 /// # }
 /// ```
 ///
+/// [`as_str`]: #method.as_str
 /// [`Index<Tag>`]: https://doc.rust-lang.org/std/ops/trait.Index.html
 /// [`str`]: https://doc.rust-lang.org/std/primitive.str.html
 /// [copy semantics]: https://doc.rust-lang.org/std/marker/trait.Copy.html
 #[allow(missing_docs)]
-#[cfg_attr(
-    feature = "serde",
-    serde(content = "location", rename_all = "UPPERCASE", tag = "type")
-)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "TagRepr", into = "TagRepr"))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Tag {
     Context { start: usize, end: usize },
@@ -48,6 +50,33 @@ pub enum Tag {
     Special { start: usize, end: usize },
 }
 
+/// The serialized representation of a tag, `{ "type": "CONTEXT", "location":
+/// { "start": 0, "end": 6 } }`. A struct is used rather than an adjacently
+/// tagged enum so that formats that are not self-describing can deserialize it.
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct TagRepr {
+    #[serde(rename = "type")]
+    kind: TagKind,
+    location: Location,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+enum TagKind {
+    Context,
+    Project,
+    Special,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct Location {
+    start: usize,
+    end: usize,
+}
+
 /// An iterator over the tags of a given task.
 ///
 /// When you parse a task, you may not actually need to look at it's tags.
@@ -85,11 +114,35 @@ pub enum Tag {
 ///
 #[derive(Clone, Debug)]
 pub struct Tags<'a> {
-    pub(super) data: &'a str,
     pub(super) iter: CharIndices<'a>,
 }
 
 impl Tag {
+    /// Returns the text of the tag within `description`, the description of
+    /// the task that the tag belongs to.
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "x write a +todo.txt parser in @rust";
+    /// let task = data.tasks().next().unwrap();
+    /// let description = task.description();
+    /// let values: Vec<&str> = task.tags().map(|tag| tag.as_str(description)).collect();
+    ///
+    /// assert_eq!(values, ["+todo.txt", "@rust"]);
+    ///
+    /// for tag in task.tags() {
+    ///     assert_eq!(tag.as_str(description), &description[tag]);
+    /// }
+    /// # }
+    /// ```
+    pub fn as_str<'a>(&self, description: &'a str) -> &'a str {
+        &description[self.start()..self.end()]
+    }
+
     /// Returns the end index of the tag, relative to the task's description.
     ///
     /// ```
@@ -112,6 +165,38 @@ impl Tag {
         }
     }
 
+    /// Returns a tag of the same kind with its start and end indices moved by
+    /// `bytes`, such as to update the position of a tag after text is inserted
+    /// into or removed from the description before it. `None` is returned if
+    /// either index would be negative or overflow.
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let tag = Tag::Context {
+    ///     start: 5,
+    ///     end: 10,
+    /// };
+    ///
+    /// assert_eq!(tag.offset_by(3), Some(Tag::Context { start: 8, end: 13 }));
+    /// assert_eq!(tag.offset_by(-5), Some(Tag::Context { start: 0, end: 5 }));
+    /// assert_eq!(tag.offset_by(-6), None);
+    /// # }
+    /// ```
+    pub fn offset_by(&self, bytes: isize) -> Option<Tag> {
+        let start = self.start().checked_add_signed(bytes)?;
+        let end = self.end().checked_add_signed(bytes)?;
+
+        Some(match *self {
+            Tag::Context { .. } => Tag::Context { start, end },
+            Tag::Project { .. } => Tag::Project { start, end },
+            Tag::Special { .. } => Tag::Special { start, end },
+        })
+    }
+
     /// Returns the start index of the tag, relative to the task's description.
     ///
     /// ```
@@ -137,11 +222,271 @@ impl Tag {
     }
 }
 
+impl<'a> Tags<'a> {
+    /// Returns `true` if `predicate` returns `true` for every remaining tag.
+    /// Iteration stops at the first tag for which it returns `false`, so the
+    /// tags after it can still be read from the iterator.
+    ///
+    /// This is the same as [`Iterator::all`].
+    ///
+    /// [`Iterator::all`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.all
+    pub fn all<F>(&mut self, predicate: F) -> bool
+    where
+        F: FnMut(Tag) -> bool,
+    {
+        Iterator::all(self, predicate)
+    }
+
+    /// Returns `true` if `predicate` returns `true` for any remaining tag.
+    /// Iteration stops at the first tag for which it returns `true`, so the
+    /// tags after it can still be read from the iterator.
+    ///
+    /// This is the same as [`Iterator::any`].
+    ///
+    /// [`Iterator::any`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.any
+    pub fn any<F>(&mut self, predicate: F) -> bool
+    where
+        F: FnMut(Tag) -> bool,
+    {
+        Iterator::any(self, predicate)
+    }
+
+    /// Returns `true` if any remaining tag is the context `value`, e.g.
+    /// `@phone`. Iteration stops at the first match.
+    ///
+    /// Unlike [`Task::has_any_context`], this continues from the current
+    /// position of the iterator and does not need the task's description.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Call Mom @phone +Family @home";
+    /// let task = data.tasks().next().unwrap();
+    /// let mut tags = task.tags();
+    ///
+    /// assert!(tags.any_context("@phone"));
+    /// assert_eq!(tags.next().map(|tag| &task.description()[tag]), Some("+Family"));
+    /// assert!(!tags.any_context("@phone"));
+    /// # }
+    /// ```
+    ///
+    /// [`Task::has_any_context`]: struct.Task.html#method.has_any_context
+    pub fn any_context(&mut self, value: &str) -> bool {
+        self.any_value(value, |tag| matches!(tag, Tag::Context { .. }))
+    }
+
+    /// Returns `true` if any remaining tag is the project `value`, e.g.
+    /// `+Family`. Iteration stops at the first match. See [`any_context`].
+    ///
+    /// [`any_context`]: #method.any_context
+    pub fn any_project(&mut self, value: &str) -> bool {
+        self.any_value(value, |tag| matches!(tag, Tag::Project { .. }))
+    }
+
+    /// Returns the number of remaining tags without advancing the iterator,
+    /// e.g. to reserve space for them. The rest of the description is scanned
+    /// on a copy of the iterator.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Call Mom @phone +Family due:2011-03-01 @ soon";
+    /// let task = data.tasks().next().unwrap();
+    /// let tags = task.tags();
+    ///
+    /// assert_eq!(tags.count_fast(), 3);
+    /// assert_eq!(tags.count(), 3);
+    /// # }
+    /// ```
+    pub fn count_fast(&self) -> usize {
+        let mut iter = self.iter.clone();
+
+        iter::from_fn(|| next_word(&mut iter))
+            .filter(|word| word.tag().is_some())
+            .count()
+    }
+
+    /// Consumes the iterator and returns the text of each remaining tag as an
+    /// owned `String`. `description` must be the description of the task that
+    /// the tags belong to.
+    ///
+    /// Unlike the rest of the parser, this allocates a string for every tag.
+    /// It is intended for contexts such as FFI that require owned data.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "x write a +todo.txt parser in @rust due:2011-03-01";
+    /// let task = data.tasks().next().unwrap();
+    /// let description = task.description();
+    /// let values = task.tags().into_owned_values(description);
+    ///
+    /// assert_eq!(values, ["+todo.txt", "@rust", "due:2011-03-01"]);
+    /// assert!(task.tags().zip(&values).all(|(tag, value)| &description[tag] == value));
+    /// # }
+    /// ```
+    pub fn into_owned_values(self, description: &str) -> Vec<String> {
+        self.map(|tag| tag.as_str(description).to_owned()).collect()
+    }
+
+    /// Consumes the iterator and returns each remaining tag paired with its
+    /// text. `description` must be the description of the task that the tags
+    /// belong to.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Call Mom @phone due:2011-03-01";
+    /// let task = data.tasks().next().unwrap();
+    /// let tags = task.tags().into_vec_with_values(task.description());
+    ///
+    /// assert_eq!(
+    ///     tags,
+    ///     [
+    ///         (Tag::Context { start: 9, end: 15 }, "@phone"),
+    ///         (Tag::Special { start: 16, end: 30 }, "due:2011-03-01"),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn into_vec_with_values(self, description: &str) -> Vec<(Tag, &str)> {
+        self.map(|tag| (tag, tag.as_str(description))).collect()
+    }
+
+    /// Returns the iterator advanced to the byte `offset` of the description,
+    /// without classifying the words before it as tags. This is useful when
+    /// the start of the description is already known not to contain tags.
+    ///
+    /// The offset should be at the start of a word or in the whitespace
+    /// between words, or the rest of the word will be read as a tag on its
+    /// own. If the iterator is already past `offset`, it is returned as is.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `offset` is past the end of the description or is not on a
+    /// `char` boundary.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Call Mom @phone +Family";
+    /// let task = data.tasks().next().unwrap();
+    /// let tags: Vec<&str> = task
+    ///     .tags()
+    ///     .skip_to(15)
+    ///     .map(|tag| &task.description()[tag])
+    ///     .collect();
+    ///
+    /// assert_eq!(tags, ["+Family"]);
+    /// # }
+    /// ```
+    pub fn skip_to(mut self, offset: usize) -> Tags<'a> {
+        let current = self.iter.offset();
+
+        if offset <= current {
+            return self;
+        }
+
+        let rest = self.iter.as_str();
+
+        assert!(
+            rest.is_char_boundary(offset - current),
+            "offset {} is not on a char boundary of the description",
+            offset
+        );
+
+        let skipped = rest[..offset - current].chars().count();
+
+        self.iter.nth(skipped - 1);
+        self
+    }
+
+    fn any_value<F>(&mut self, value: &str, is_kind: F) -> bool
+    where
+        F: Fn(&Tag) -> bool,
+    {
+        loop {
+            // The indices of a tag are relative to the description, while the
+            // remaining text starts at the current offset of the iterator.
+            let offset = self.iter.offset();
+            let rest = self.iter.as_str();
+
+            match self.next() {
+                Some(tag) if is_kind(&tag) => {
+                    if rest[tag.start() - offset..tag.end() - offset] == *value {
+                        return true;
+                    }
+                }
+                Some(_) => {}
+                None => return false,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Tag> for TagRepr {
+    fn from(tag: Tag) -> Self {
+        let kind = match tag {
+            Tag::Context { .. } => TagKind::Context,
+            Tag::Project { .. } => TagKind::Project,
+            Tag::Special { .. } => TagKind::Special,
+        };
+        let location = Location {
+            start: tag.start(),
+            end: tag.end(),
+        };
+
+        TagRepr { kind, location }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<TagRepr> for Tag {
+    fn from(TagRepr { kind, location }: TagRepr) -> Self {
+        let Location { start, end } = location;
+
+        match kind {
+            TagKind::Context => Tag::Context { start, end },
+            TagKind::Project => Tag::Project { start, end },
+            TagKind::Special => Tag::Special { start, end },
+        }
+    }
+}
+
+/// Indexing a description with a tag is equivalent to [`Tag::as_str`].
+///
+/// [`Tag::as_str`]: enum.Tag.html#method.as_str
 impl Index<Tag> for str {
     type Output = str;
 
     fn index(&self, tag: Tag) -> &Self::Output {
-        &self[tag.start()..tag.end()]
+        tag.as_str(self)
     }
 }
 
@@ -151,33 +496,66 @@ impl<'a> Iterator for Tags<'a> {
     type Item = Tag;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (start, end) = next_word_boundary(&mut self.iter)?;
-        let word = &self.data[start..end];
+        loop {
+            if let Some(tag) = next_word(&mut self.iter)?.tag() {
+                return Some(tag);
+            }
+        }
+    }
+}
+
+/// The location of a word in a description, along with what is needed to
+/// classify it as a tag. Both are found in a single scan over the word.
+struct Word {
+    start: usize,
+    end: usize,
+    first: char,
+    has_colon: bool,
+}
+
+impl Word {
+    /// Returns the tag that the word is, if any.
+    fn tag(&self) -> Option<Tag> {
+        let Word {
+            start,
+            end,
+            first,
+            has_colon,
+        } = *self;
 
-        if word.starts_with('@') {
+        if end - start < 2 {
+            // A lone `@`, `+`, or `:` is not a tag.
+            None
+        } else if first == '@' {
             Some(Tag::Context { start, end })
-        } else if word.starts_with('+') {
+        } else if first == '+' {
             Some(Tag::Project { start, end })
-        } else if word.contains(':') {
+        } else if has_colon {
             Some(Tag::Special { start, end })
         } else {
-            self.next()
+            None
         }
     }
 }
 
-fn is_not_whitespace((_, item): &(usize, char)) -> bool {
-    !item.is_whitespace()
-}
+fn next_word(iter: &mut CharIndices<'_>) -> Option<Word> {
+    let (start, first) = iter.find(|(_, item)| !item.is_whitespace())?;
+    let mut end = start + first.len_utf8();
+    let mut has_colon = first == ':';
 
-fn is_whitespace((_, item): &(usize, char)) -> bool {
-    item.is_whitespace()
-}
+    for (index, item) in iter {
+        if item.is_whitespace() {
+            break;
+        }
 
-fn next_word_boundary(iter: &mut CharIndices<'_>) -> Option<(usize, usize)> {
-    let mut iter = iter.skip_while(is_whitespace).take_while(is_not_whitespace);
-    let start = iter.next().map(|(index, _)| index)?;
-    let end = iter.last().map_or(start, |(index, _)| index + 1);
+        end = index + item.len_utf8();
+        has_colon |= item == ':';
+    }
 
-    Some((start, end))
+    Some(Word {
+        start,
+        end,
+        first,
+        has_colon,
+    })
 }
@@ -1,14 +1,37 @@
+#[cfg(feature = "color")]
+use crate::color::{ColorScheme, Colored};
 use crate::{
-    parser::Parse,
+    compact::CompactTask,
+    date::{self, ConvertDate, Date, DueValue},
+    parser::{character, error, optional, Parse, ParseErrorKind, ParseSpecial},
     priority::Priority,
+    recurrence::Recurrence,
     tags::{Tag, Tags},
 };
-use chrono::NaiveDate;
+#[cfg(feature = "dates")]
+use crate::{date::DueDate, time};
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(any(feature = "arbitrary", feature = "dates"))]
+use chrono::Duration;
+#[cfg(feature = "dates")]
+use chrono::{DateTime, FixedOffset, ParseError, TimeZone};
+#[cfg(feature = "regex")]
+use regex::Regex;
 #[cfg(feature = "serde")]
-use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde::{
+    de::{Deserializer, Error as _},
+    ser::{SerializeSeq, SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
 use std::{
     borrow::Cow,
+    collections::hash_map::DefaultHasher,
+    error::Error,
     fmt::{self, Debug, Display, Formatter},
+    hash::Hasher,
 };
 
 /// The disjoint state of complete and incomplete tasks.
@@ -17,11 +40,122 @@ use std::{
 /// specification. While a complete task and incomplete task can be viewed as
 /// distinct types, for convenience in the common cases, this library choses to
 /// wrap the disjoint union of a complete and incomplete task in a single type.
+///
+/// ## Serialization
+///
+/// When the `serde` feature is enabled, a state is represented as a struct
+/// with a `type` field of either `"COMPLETE"` or `"INCOMPLETE"`, matching the
+/// `type` field of a serialized [`Task`]. The `completion_date`,
+/// `creation_date`, and `priority` fields are omitted when absent.
+///
+/// ```json
+/// { "completion_date": "2011-03-02", "creation_date": "2011-03-01", "type": "COMPLETE" }
+/// { "creation_date": "2011-03-01", "priority": "A", "type": "INCOMPLETE" }
+/// ```
+///
+/// Deserialization fails when a complete state has a priority or a creation
+/// date without a completion date, or when an incomplete state has a completion
+/// date.
+///
+/// ## Dates of complete tasks
+///
+/// The dates of a complete state are its completion date and creation date, in
+/// that order. A line such as `x 2011-03-02 Review PR` only has a completion
+/// date. Since a creation date is written after the completion date, a complete
+/// task never has a creation date without a completion date.
+///
+/// [`Task`]: struct.Task.html
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(into = "StateRepr", try_from = "StateRepr"))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum State {
-    Complete(Option<(NaiveDate, NaiveDate)>),
-    Incomplete(Option<Priority>, Option<NaiveDate>),
+    Complete(Option<Date>, Option<Date>),
+    Incomplete(Option<Priority>, Option<Date>),
+}
+
+/// The reasons that [`State::complete`] rejects the dates of a complete state.
+///
+/// [`State::complete`]: enum.State.html#method.complete
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InvalidState {
+    /// The completion date is before the creation date.
+    CompletionBeforeCreation,
+    /// The state has a creation date without a completion date, which can't
+    /// be written, since the creation date follows the completion date.
+    CreationWithoutCompletion,
+}
+
+/// The naming convention used for field names when a task is serialized.
+///
+/// Multi-word field names such as `completion_date` are the only names that
+/// are affected by the case. The fields of a serialized [`Tag`] are single
+/// words and are therefore identical in every case.
+///
+/// [`Tag`]: enum.Tag.html
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Case {
+    /// Field names are written in `camelCase` (e.g. `completionDate`).
+    Camel,
+    /// Field names are written in `snake_case` (e.g. `completion_date`). This
+    /// is the case used by the `Serialize` implementation of `Task`.
+    #[default]
+    Snake,
+}
+
+/// A serializable view of a task that uses a specific [`Case`] for field
+/// names.
+///
+/// This type is returned by [`Task::serialize_with`].
+///
+/// [`Case`]: enum.Case.html
+/// [`Task::serialize_with`]: struct.Task.html#method.serialize_with
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug)]
+pub struct WithCase<'t, 'a> {
+    case: Case,
+    task: &'t Task<'a>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+enum StateKind {
+    Complete,
+    Incomplete,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct StateRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completion_date: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    creation_date: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<Priority>,
+    #[serde(rename = "type")]
+    kind: StateKind,
+}
+
+/// The fields of a serialized task, in the order they are serialized. The tags
+/// of a task are derived from its description, so they are ignored.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct TaskRepr {
+    #[serde(alias = "completionDate", default)]
+    completion_date: Option<Date>,
+    #[serde(alias = "creationDate", default)]
+    creation_date: Option<Date>,
+    description: String,
+    #[serde(default)]
+    priority: Option<Priority>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    tags: Vec<Tag>,
+    #[serde(rename = "type")]
+    kind: StateKind,
 }
 
 /// A single complete or incomplete task.
@@ -31,114 +165,359 @@ pub struct Task<'a> {
     text: Cow<'a, str>,
 }
 
+/// Counts the bytes written to it, to measure formatted output.
+struct ByteCount(usize);
+
+/// Formats and serializes the tags of a task as a sequence without collecting
+/// them into a `Vec`.
+struct TagList<'t>(Tags<'t>);
+
+#[cfg(feature = "serde")]
+impl Case {
+    fn pick(self, snake: &'static str, camel: &'static str) -> &'static str {
+        match self {
+            Case::Camel => camel,
+            Case::Snake => snake,
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for State {
+    /// Generates a state that upholds the invariants of the specification. The
+    /// completion date of a complete task is never before its creation date.
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.arbitrary()? {
+            match u.int_in_range(0..=2)? {
+                0 => Ok(State::Complete(None, None)),
+                1 => Ok(State::Complete(Some(arbitrary_date(u)?), None)),
+                _ => {
+                    let a = arbitrary_date(u)?;
+                    let b = arbitrary_date(u)?;
+
+                    Ok(State::Complete(Some(a.max(b)), Some(a.min(b))))
+                }
+            }
+        } else {
+            let priority = u.arbitrary()?;
+            let creation_date = if u.arbitrary()? {
+                Some(arbitrary_date(u)?)
+            } else {
+                None
+            };
+
+            Ok(State::Incomplete(priority, creation_date))
+        }
+    }
+}
+
+/// Generates a task that formats to a line which parses back into an equal
+/// task.
+///
+/// Descriptions contain between one and eight words separated by a single
+/// space. Words are made of ASCII letters, digits, and the characters that are
+/// meaningful to tags. The first word always starts with a letter other than
+/// `x` so that it can't be mistaken for a completion marker, priority, or date.
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use arbitrary::{Arbitrary, Unstructured};
+///
+/// let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+/// let mut next_byte = || {
+///     seed ^= seed << 13;
+///     seed ^= seed >> 7;
+///     seed ^= seed << 17;
+///     seed as u8
+/// };
+///
+/// for _ in 0..1000 {
+///     let bytes: Vec<u8> = (0..128).map(|_| next_byte()).collect();
+///     let task = Task::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+///     let line = task.to_string();
+///
+///     assert_eq!(line.tasks().next(), Some(task));
+/// }
+/// # }
+/// ```
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for Task<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        const REST: &[u8] =
+            b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789@+:-_.'";
+
+        let state = u.arbitrary()?;
+        let mut text = String::new();
+
+        for index in 0..u.int_in_range(1..=8)? {
+            if index == 0 {
+                text.push(*u.choose(FIRST)? as char);
+            } else {
+                text.push(' ');
+                text.push(*u.choose(REST)? as char);
+            }
+
+            for _ in 0..u.int_in_range(0..=9)? {
+                text.push(*u.choose(REST)? as char);
+            }
+        }
+
+        Ok(Task {
+            state,
+            text: Cow::Owned(text),
+        })
+    }
+}
+
+/// Tasks can be deserialized from the output of their `Serialize`
+/// implementation in any [`Case`]. Because the tags of a task are derived from
+/// its description, the `tags` field is optional and ignored.
+///
+/// [`Case`]: enum.Case.html
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Task<'static> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = TaskRepr::deserialize(deserializer)?;
+        let state = State::try_from(StateRepr {
+            completion_date: repr.completion_date,
+            creation_date: repr.creation_date,
+            priority: repr.priority,
+            kind: repr.kind,
+        })
+        .map_err(D::Error::custom)?;
+
+        Ok(Task {
+            state,
+            text: Cow::Owned(repr.description),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<State> for StateRepr {
+    fn from(state: State) -> Self {
+        match state {
+            State::Complete(completion_date, creation_date) => StateRepr {
+                completion_date,
+                creation_date,
+                priority: None,
+                kind: StateKind::Complete,
+            },
+            State::Incomplete(priority, creation_date) => StateRepr {
+                completion_date: None,
+                creation_date,
+                priority,
+                kind: StateKind::Incomplete,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StateRepr> for State {
+    type Error = &'static str;
+
+    fn try_from(repr: StateRepr) -> Result<Self, Self::Error> {
+        match repr {
+            StateRepr {
+                kind: StateKind::Complete,
+                priority: Some(_),
+                ..
+            } => Err("a complete task can't have a priority"),
+            StateRepr {
+                kind: StateKind::Complete,
+                completion_date,
+                creation_date,
+                ..
+            } => match (completion_date, creation_date) {
+                (None, Some(_)) => {
+                    Err("a complete task can't have a creation date without a completion date")
+                }
+                (completion_date, creation_date) => {
+                    Ok(State::Complete(completion_date, creation_date))
+                }
+            },
+            StateRepr {
+                kind: StateKind::Incomplete,
+                completion_date: Some(_),
+                ..
+            } => Err("an incomplete task can't have a completion date"),
+            StateRepr {
+                kind: StateKind::Incomplete,
+                creation_date,
+                priority,
+                ..
+            } => Ok(State::Incomplete(priority, creation_date)),
+        }
+    }
+}
+
 impl<'a> Debug for Task<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let tags: Vec<Tag> = self.tags().collect();
-
         f.debug_struct("Task")
             .field("completion_date", &self.completion_date())
             .field("creation_date", &self.creation_date())
             .field("description", &self.description())
             .field("is_complete", &self.is_complete())
             .field("priority", &self.priority())
-            .field("tags", &tags)
+            .field("tags", &TagList(self.tags()))
             .finish()
     }
 }
 
-impl<'a> Parse<'a> for NaiveDate {
-    type Output = NaiveDate;
+impl<'t> Debug for TagList<'t> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
 
-    fn parse(input: &str) -> nom::IResult<&str, Self::Output> {
-        named!(ymd<&str, (i32, u32, u32)>,
-            tuple!(
-                terminated!(flat_map!(take!(4), parse_to!(i32)), char!('-')),
-                terminated!(flat_map!(take!(2), parse_to!(u32)), char!('-')),
-                flat_map!(take!(2), parse_to!(u32))
-            )
-        );
-
-        map_opt!(input, complete!(ymd), |(y, m, d)| {
-            NaiveDate::from_ymd_opt(y, m, d)
+impl fmt::Write for ByteCount {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.0 += text.len();
+        Ok(())
+    }
+}
+
+impl Display for InvalidState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InvalidState::CompletionBeforeCreation => {
+                "the completion date is before the creation date"
+            }
+            InvalidState::CreationWithoutCompletion => {
+                "a complete task can't have a creation date without a completion date"
+            }
         })
     }
 }
 
-impl<'a> Parse<'a> for State {
-    type Output = State;
+impl Error for InvalidState {}
+
+impl<'a> Parse<'a> for Date {
+    type Output = Date;
 
     fn parse(input: &str) -> nom::IResult<&str, Self::Output> {
-        named!(complete<&str, (NaiveDate, NaiveDate)>,
-            terminated!(
-                separated_pair!(NaiveDate::parse, nom::space, NaiveDate::parse),
-                nom::space
-            )
-        );
-
-        named!(unknown<&str, (Option<Priority>, Option<NaiveDate>, Option<NaiveDate>)>,
-            tuple!(
-                opt!(terminated!(Priority::parse, nom::space)),
-                opt!(terminated!(NaiveDate::parse, nom::space)),
-                opt!(terminated!(NaiveDate::parse, nom::space))
-            )
-        );
-
-        switch!(input, opt!(terminated!(char!('x'), nom::space)),
-            Some(_) => map!(opt!(complete), State::Complete) |
-            None => map!(unknown, |result| match result {
-                (None, Some(completion_date), Some(creation_date)) => {
-                    State::Complete(Some((completion_date, creation_date)))
-                }
-                (priority, creation_date, _) => {
-                    State::Incomplete(priority, creation_date)
-                }
-            })
-        )
+        // Dates are parsed up to twice per line, so the bytes are checked in
+        // place rather than through `take!` and `parse_to!`. Anything other
+        // than exactly `YYYY-MM-DD` in ASCII is rejected.
+        fn digit(byte: u8) -> Option<u32> {
+            (byte as char).to_digit(10)
+        }
+
+        let date = match input.as_bytes() {
+            [y0, y1, y2, y3, b'-', m0, m1, b'-', d0, d1, ..] => (|| {
+                let y = ((digit(*y0)? * 10 + digit(*y1)?) * 10 + digit(*y2)?) * 10 + digit(*y3)?;
+                let m = digit(*m0)? * 10 + digit(*m1)?;
+                let d = digit(*d0)? * 10 + digit(*d1)?;
+
+                Date::from_ymd_opt(y as i32, m, d)
+            })(),
+            _ => None,
+        };
+
+        match date {
+            // The first 10 bytes are ASCII, so 10 is a char boundary.
+            Some(date) => Ok((&input[10..], date)),
+            None => error(input, nom::ErrorKind::MapOpt),
+        }
     }
 }
 
-impl<'a> Task<'a> {
-    /// Get the completion date of the task. If the task is incomplete, the
-    /// completion date is guaranteed to be `Option::None`.
-    pub fn completion_date(&self) -> Option<NaiveDate> {
-        match self.state {
-            State::Complete(state) => state.map(|(date, _)| date),
-            State::Incomplete(_, _) => None,
+impl<'a> Parse<'a> for State {
+    type Output = State;
+
+    fn parse(input: &str) -> nom::IResult<&str, Self::Output> {
+        let (input, x) = optional(input, |input| character(input, 'x'))?;
+
+        // Only a line that starts with `x` is complete. A second date after the
+        // creation date of an incomplete task is part of its description, as
+        // in `2011-03-01 2011-03-02 standup notes`.
+        if x.is_some() {
+            let (input, completion_date) = optional(input, Date::parse)?;
+            let (input, creation_date) = optional(input, Date::parse)?;
+
+            Ok((input, State::Complete(completion_date, creation_date)))
+        } else {
+            let (input, priority) = optional(input, Priority::parse)?;
+            let (input, creation_date) = optional(input, Date::parse)?;
+
+            Ok((input, State::Incomplete(priority, creation_date)))
         }
     }
+}
 
-    /// Get the creation date of the task.
-    pub fn creation_date(&self) -> Option<NaiveDate> {
-        match self.state {
-            State::Complete(state) => state.map(|(date, _)| date),
-            State::Incomplete(_, date) => date,
+impl State {
+    /// Returns a complete state with the provided dates, or an error if they
+    /// break the invariants of a complete task: a creation date requires a
+    /// completion date, which can't be before it.
+    ///
+    /// The parser is more lenient and accepts a completion date before the
+    /// creation date. See [`is_chronologically_valid`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{Date, InvalidState, State};
+    ///
+    /// let (march_1, march_2) = (Date::from_ymd_opt(2011, 3, 1), Date::from_ymd_opt(2011, 3, 2));
+    ///
+    /// assert_eq!(State::complete(march_2, march_1), Ok(State::Complete(march_2, march_1)));
+    /// assert_eq!(State::complete(None, None), Ok(State::Complete(None, None)));
+    /// assert_eq!(State::complete(march_1, march_2), Err(InvalidState::CompletionBeforeCreation));
+    /// assert_eq!(State::complete(None, march_1), Err(InvalidState::CreationWithoutCompletion));
+    /// # }
+    /// ```
+    ///
+    /// [`is_chronologically_valid`]: #method.is_chronologically_valid
+    pub fn complete(
+        completion_date: Option<Date>,
+        creation_date: Option<Date>,
+    ) -> Result<State, InvalidState> {
+        let state = State::Complete(completion_date, creation_date);
+
+        match (completion_date, creation_date) {
+            (None, Some(_)) => Err(InvalidState::CreationWithoutCompletion),
+            _ if !state.is_chronologically_valid() => Err(InvalidState::CompletionBeforeCreation),
+            _ => Ok(state),
         }
     }
 
-    /// Get a reference to the task's description.
-    pub fn description(&self) -> &str {
-        &self.text
+    /// Returns an incomplete state with the provided priority and creation
+    /// date. Every combination is valid.
+    pub fn incomplete(priority: Option<Priority>, creation_date: Option<Date>) -> State {
+        State::Incomplete(priority, creation_date)
     }
 
-    /// Returns `true` if the task is complete, otherwise returns `false`.
-    pub fn is_complete(&self) -> bool {
-        match self.state {
-            State::Complete(_) => true,
-            State::Incomplete(_, _) => false,
+    /// Returns the completion date of a complete state, or `None` if the state
+    /// is incomplete.
+    pub fn completion_date(&self) -> Option<Date> {
+        match *self {
+            State::Complete(date, _) => date,
+            State::Incomplete(_, _) => None,
         }
     }
 
-    /// Get the priority of the task. If the task is complete, the priority
-    /// is guaranteed to be `Option::None`.
-    pub fn priority(&self) -> Option<Priority> {
-        match self.state {
-            State::Complete(_) => None,
-            State::Incomplete(priority, _) => priority,
+    /// Returns the creation date of the state.
+    pub fn creation_date(&self) -> Option<Date> {
+        match *self {
+            State::Complete(_, date) | State::Incomplete(_, date) => date,
         }
     }
 
-    /// This method is useful if you want to refine the data of a task to the
-    /// distinct data of a complete or incomplete task.
+    /// Returns `false` if the state is complete and its completion date is
+    /// before its creation date. A completion date on the same day as the
+    /// creation date is valid.
     ///
     /// ## Example
     ///
@@ -148,27 +527,64 @@ impl<'a> Task<'a> {
     /// # use todotxt::prelude::*;
     /// #
     /// # fn main() {
-    /// # let data = "(A) Thank Mom for the meatballs @phone";
-    /// # let task = data.tasks().next().unwrap();
+    /// let states: Vec<bool> = "
+    ///     x 2011-03-02 2011-03-01 Review Tim's pull request
+    ///     x 2011-03-01 2011-03-01 Call Mom
+    ///     x 2020-01-01 2023-05-05 Pay rent
+    ///     x 2011-03-02 File taxes
+    ///     (A) 2011-03-01 Thank Mom for the meatballs
+    /// "
+    /// .tasks()
+    /// .map(|task| task.state().is_chronologically_valid())
+    /// .collect();
+    ///
+    /// assert_eq!(states, [true, true, false, true, true]);
+    /// # }
+    /// ```
+    pub fn is_chronologically_valid(&self) -> bool {
+        match *self {
+            State::Complete(Some(completion), Some(creation)) => completion >= creation,
+            State::Complete(_, _) | State::Incomplete(_, _) => true,
+        }
+    }
+
+    /// Returns `true` if the state is complete.
+    pub fn is_complete(&self) -> bool {
+        matches!(self, State::Complete(_, _))
+    }
+
+    /// Returns the priority of an incomplete state, or `None` if the state is
+    /// complete.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
     /// #
+    /// # fn main() {
     /// use todotxt::State;
     ///
-    /// match task.state() {
-    ///     State::Complete(dates) => {
-    ///         // Do something with the creation and/or completion date(s)...
-    ///     }
-    ///     State::Incomplete(priority, creation_date) => {
-    ///         // Do something with the priority and/or creation date...
-    ///     }
-    /// }
+    /// let task = "(A) 2011-03-01 Call Mom".tasks().next().unwrap();
+    /// let state = task.state();
+    ///
+    /// assert_eq!(state.priority(), Some(Priority::A));
+    /// assert_eq!(state.creation_date(), task.creation_date());
+    /// assert!(!state.is_complete());
     /// # }
     /// ```
-    pub fn state(&self) -> State {
-        self.state
+    pub fn priority(&self) -> Option<Priority> {
+        match *self {
+            State::Complete(_, _) => None,
+            State::Incomplete(priority, _) => priority,
+        }
     }
+}
 
-    /// Lazily parse and iterate over the tags contained within the description
-    /// of the task.
+impl<'a> Task<'a> {
+    /// Returns an iterator over the text of each context tag in the task's
+    /// description.
     ///
     /// ## Example
     ///
@@ -178,96 +594,1864 @@ impl<'a> Task<'a> {
     /// # use todotxt::prelude::*;
     /// #
     /// # fn main() {
-    /// # let data = "(A) Thank Mom for the meatballs @phone";
-    /// # let task = data.tasks().next().unwrap();
-    /// #
-    /// for tag in task.tags() {
-    ///     println!("{:#?}", tag);
-    /// }
+    /// let data = "(A) Call Mom @phone +Family rec:1w @home +Chores due:2011-03-01";
+    /// let task = data.tasks().next().unwrap();
+    /// let description = task.description();
+    /// let manual: Vec<&str> = task
+    ///     .tags()
+    ///     .filter(|tag| matches!(tag, Tag::Context { .. }))
+    ///     .map(|tag| tag.as_str(description))
+    ///     .collect();
+    ///
+    /// assert_eq!(task.contexts_iter().collect::<Vec<_>>(), ["@phone", "@home"]);
+    /// assert_eq!(task.contexts_iter().collect::<Vec<_>>(), manual);
     /// # }
     /// ```
-    pub fn tags(&self) -> Tags<'_> {
-        let data = self.description();
-        let iter = data.char_indices();
+    pub fn contexts_iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let description = self.description();
 
-        Tags { data, iter }
+        self.tags().filter_map(move |tag| match tag {
+            Tag::Context { .. } => Some(tag.as_str(description)),
+            Tag::Project { .. } | Tag::Special { .. } => None,
+        })
     }
-}
 
-impl<'a> Clone for Task<'a> {
-    fn clone(&self) -> Task<'static> {
-        Task {
-            state: self.state,
-            text: Cow::Owned(String::from(&*self.text)),
+    /// Marks the task as complete on `today`, removing its priority and
+    /// keeping its creation date. Complete tasks are left unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let today = Date::from_ymd_opt(2011, 3, 3).unwrap();
+    /// let mut task = "(A) 2011-03-01 Call Mom @phone".tasks().next().unwrap();
+    ///
+    /// task.complete_on(today);
+    /// assert_eq!(task.to_string(), "x 2011-03-03 2011-03-01 Call Mom @phone");
+    /// # }
+    /// ```
+    pub fn complete_on(&mut self, today: Date) {
+        if let State::Incomplete(_, creation_date) = self.state {
+            self.state = State::Complete(Some(today), creation_date);
         }
     }
-}
-
-impl<'a> Display for Task<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        if self.is_complete() {
-            f.write_str("x ")?;
-        }
 
-        if let Some(priority) = self.priority() {
-            write!(f, "{} ", priority)?;
-        }
+    /// Marks the task as complete on the current date in UTC. See
+    /// [`complete_on`] for details, and to supply a different date.
+    ///
+    /// [`complete_on`]: #method.complete_on
+    #[cfg(feature = "dates")]
+    pub fn complete_today(&mut self) {
+        self.complete_on(time::today_utc());
+    }
 
-        if let Some(completion_date) = self.completion_date() {
-            write!(f, "{} ", completion_date)?;
-        }
+    /// Get the completion date of the task. If the task is incomplete, the
+    /// completion date is guaranteed to be `Option::None`.
+    pub fn completion_date(&self) -> Option<Date> {
+        self.state.completion_date()
+    }
 
-        if let Some(creation_date) = self.creation_date() {
-            write!(f, "{} ", creation_date)?;
-        }
+    /// Get the completion date of the task as another date type, such as
+    /// `time::Date` when the `time` feature is enabled. `None` is returned if
+    /// the task does not have a completion date or it is out of the range of `T`.
+    /// See [`ConvertDate`].
+    ///
+    /// [`ConvertDate`]: trait.ConvertDate.html
+    pub fn completion_date_as<T: ConvertDate>(&self) -> Option<T> {
+        self.completion_date().and_then(T::from_date)
+    }
 
-        f.write_str(self.description())
+    /// Get the creation date of the task.
+    pub fn creation_date(&self) -> Option<Date> {
+        self.state.creation_date()
     }
-}
 
-impl<'a> Parse<'a> for Task<'a> {
-    type Output = Task<'a>;
+    /// Get the creation date of the task as another date type, such as
+    /// `time::Date` when the `time` feature is enabled. `None` is returned if
+    /// the task does not have a creation date or it is out of the range of `T`.
+    /// See [`ConvertDate`].
+    ///
+    /// [`ConvertDate`]: trait.ConvertDate.html
+    pub fn creation_date_as<T: ConvertDate>(&self) -> Option<T> {
+        self.creation_date().and_then(T::from_date)
+    }
 
-    fn parse(input: &'a str) -> nom::IResult<&str, Self::Output> {
-        map!(
-            input.trim(),
-            pair!(State::parse, map!(nom::rest, Cow::Borrowed)),
-            |(state, text)| Task { state, text }
-        )
+    /// Get the creation date of the task, or `default` if it does not have one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let default = Date::from_ymd_opt(2011, 1, 1).unwrap();
+    /// let dated = "2011-03-01 Call Mom".tasks().next().unwrap();
+    /// let undated = "Call Mom".tasks().next().unwrap();
+    ///
+    /// assert_eq!(dated.creation_date_or(default).to_string(), "2011-03-01");
+    /// assert_eq!(undated.creation_date_or(default), default);
+    /// # }
+    /// ```
+    pub fn creation_date_or(&self, default: Date) -> Date {
+        self.creation_date().unwrap_or(default)
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'a> Serialize for Task<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    /// Get the creation date of the task, or the date returned by `f` if it
+    /// does not have one. `f` is only called when the date is missing.
+    pub fn creation_date_or_else<F>(&self, f: F) -> Date
     where
-        S: Serializer,
+        F: FnOnce() -> Date,
     {
-        let mut state = serializer.serialize_struct("Task", 5)?;
-        let tags: Vec<Tag> = self.tags().collect();
-
-        if let Some(completion_date) = self.completion_date() {
-            state.serialize_field("completion_date", &completion_date)?;
-        }
-
-        if let Some(creation_date) = self.creation_date() {
-            state.serialize_field("creation_date", &creation_date)?;
-        }
-
-        state.serialize_field("description", self.description())?;
+        self.creation_date().unwrap_or_else(f)
+    }
 
-        if let Some(priority) = self.priority() {
-            state.serialize_field("priority", &priority)?;
+    /// Returns the number of days between the current local date and the due
+    /// date of the task. The value is positive when the task is due in the
+    /// future, `0` when it is due today, and negative when it is overdue.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::chrono::{Duration, Local};
+    ///
+    /// let today = Local::now().date_naive();
+    /// let data = format!(
+    ///     "File taxes due:{}\nPay rent due:{}\nCall Mom due:{}\nBuy milk",
+    ///     today + Duration::days(3),
+    ///     today,
+    ///     today - Duration::days(2),
+    /// );
+    /// let days: Vec<Option<i64>> = data.tasks().map(|task| task.days_until_due()).collect();
+    ///
+    /// assert_eq!(days, [Some(3), Some(0), Some(-2), None]);
+    /// # }
+    /// ```
+    #[cfg(feature = "dates")]
+    pub fn days_until_due(&self) -> Option<i64> {
+        let due_date = self.due_date()?;
+
+        Some((due_date - time::today()).num_days())
+    }
+
+    /// Get a reference to the task's description.
+    ///
+    /// The description is the text after the prefix of the line, such as `x`,
+    /// a priority, or dates. The spaces and tabs that separate the prefix from
+    /// the description are not part of it, no matter how many there are.
+    /// Whitespace within the description is preserved as written, while
+    /// whitespace at the start and end of the line is not.
+    ///
+    /// Control characters other than tab, such as `\0` or `\x7f`, are removed
+    /// when parsing. The ones that are whitespace, such as `\x0b`, are replaced
+    /// with a space so that the words on either side stay separate.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "(A)  2011-03-01\tCall  Mom   @phone ".tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.description(), "Call  Mom   @phone");
+    /// assert_eq!(task.to_string(), "(A) 2011-03-01 Call  Mom   @phone");
+    /// # }
+    /// ```
+    pub fn description(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the description of the task as bytes, e.g. to pass it across an
+    /// FFI boundary or to compute a checksum. This is the same as
+    /// `description().as_bytes()`, and does not copy the description.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "(A) Call Mom @phone".tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.description_bytes(), b"Call Mom @phone");
+    /// # }
+    /// ```
+    pub fn description_bytes(&self) -> &[u8] {
+        self.description().as_bytes()
+    }
+
+    /// Returns a hash of the task's description, ignoring its state. This is
+    /// useful to quickly detect whether the text of a task has changed, e.g.
+    /// when synchronizing lists.
+    ///
+    /// The hash is computed with the standard library's default hasher. It is
+    /// not collision-resistant, so equal hashes only indicate that the
+    /// descriptions are very likely to be equal. The algorithm may change
+    /// between Rust releases, so hashes should not be persisted.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     (A) Call Mom @phone
+    ///     x 2011-03-02 2011-03-01 Call Mom @phone
+    ///     Call Mom @home
+    /// ";
+    /// let hashes: Vec<u64> = data.tasks().map(|task| task.description_hash()).collect();
+    ///
+    /// assert_eq!(hashes[0], hashes[1]);
+    /// assert_ne!(hashes[0], hashes[2]);
+    /// # }
+    /// ```
+    pub fn description_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        hasher.write(self.description().as_bytes());
+        hasher.finish()
+    }
+
+    /// Returns a value that formats the task with the ANSI colors of
+    /// `scheme`, e.g. for a listing in a terminal. Only escape sequences are
+    /// added, so the text is the same as the task's `Display` output.
+    ///
+    /// The tags are found with [`tags`], so contexts, projects, and the
+    /// `due:` tags of overdue tasks are colored where they appear in the
+    /// description.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::ColorScheme;
+    ///
+    /// let task = "(A) Call Mom @phone".tasks().next().unwrap();
+    ///
+    /// println!("{}", task.display_colored(&ColorScheme::new()));
+    /// assert_eq!(
+    ///     task.display_colored(&ColorScheme::ansi()).to_string(),
+    ///     "\x1b[31m(A)\x1b[0m Call Mom \x1b[32m@phone\x1b[0m"
+    /// );
+    /// assert_eq!(
+    ///     task.display_colored(&ColorScheme::plain()).to_string(),
+    ///     task.to_string()
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`tags`]: #method.tags
+    #[cfg(feature = "color")]
+    pub fn display_colored<'s>(&'s self, scheme: &'s ColorScheme) -> impl Display + 's {
+        Colored::new(self, scheme)
+    }
+
+    /// Get the due date of the task from its `due:` special tag. If the task
+    /// does not have a `due:` tag or the value is not a valid `YYYY-MM-DD`
+    /// date, `None` is returned.
+    ///
+    /// The date may be followed by a time of day, as described in
+    /// [`due_datetime`], in which case only the date is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let data = "Post signs around the neighborhood +GarageSale due:2010-01-02";
+    /// let task = data.tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.due_date(), Date::from_ymd_opt(2010, 1, 2));
+    /// # }
+    /// ```
+    ///
+    /// [`due_datetime`]: #method.due_datetime
+    pub fn due_date(&self) -> Option<Date> {
+        Some(self.due_value()?.date)
+    }
+
+    /// Get the due date of the task as another date type, such as
+    /// `time::Date` when the `time` feature is enabled. `None` is returned if
+    /// the task does not have a due date or it is out of the range of `T`.
+    /// See [`ConvertDate`].
+    ///
+    /// [`ConvertDate`]: trait.ConvertDate.html
+    pub fn due_date_as<T: ConvertDate>(&self) -> Option<T> {
+        self.due_date().and_then(T::from_date)
+    }
+
+    /// Get the due date of the task like [`due_date`], or `default` if it does
+    /// not have a valid one.
+    ///
+    /// [`due_date`]: #method.due_date
+    pub fn due_date_or(&self, default: Date) -> Date {
+        self.due_date().unwrap_or(default)
+    }
+
+    /// Get the due date of the task like [`due_date`], but also resolve
+    /// relative dates such as `due:tomorrow` or `due:3d` against `reference`
+    /// when the value is not a `YYYY-MM-DD` date. See
+    /// [`time::resolve_relative`] for the supported forms.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::chrono::NaiveDate;
+    ///
+    /// let reference = NaiveDate::from_ymd_opt(2011, 3, 1).unwrap();
+    /// let data = "Pay rent due:2011-03-05\nCall Mom due:tomorrow\nFile taxes due:soon";
+    /// let due: Vec<Option<String>> = data
+    ///     .tasks()
+    ///     .map(|task| task.due_date_with(reference).map(|date| date.to_string()))
+    ///     .collect();
+    ///
+    /// assert_eq!(due, [Some("2011-03-05".into()), Some("2011-03-02".into()), None]);
+    /// # }
+    /// ```
+    ///
+    /// [`due_date`]: #method.due_date
+    /// [`time::resolve_relative`]: time/fn.resolve_relative.html
+    #[cfg(feature = "dates")]
+    pub fn due_date_with(&self, reference: Date) -> Option<Date> {
+        self.due_date()
+            .or_else(|| time::resolve_relative(self.get_special("due")?, reference))
+    }
+
+    /// Get the value of the task's `due:` special tag, including its time of
+    /// day if it has one. The time follows the date after a `T` or `_` and is
+    /// written as `HH:MM` or `HH:MM:SS`, e.g. `due:2011-03-01T17:00` or
+    /// `due:2011-03-01_17:00:30`. If the task does not have a `due:` tag or
+    /// the value is invalid, `None` is returned.
+    ///
+    /// The time may be followed by an offset from UTC, which is ignored. See
+    /// [`due_in_zone`] to take the offset into account.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{chrono::NaiveDate, DueDate};
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2011, 3, 1).unwrap();
+    /// let data = "
+    ///     Pay rent due:2011-03-01
+    ///     File taxes due:2011-03-01T17:00
+    ///     Call Mom due:2011-03-01_17:00:30
+    ///     Buy milk due:2011-03-01T25:00
+    /// ";
+    /// let due: Vec<Option<DueDate>> = data.tasks().map(|task| task.due_datetime()).collect();
+    ///
+    /// assert_eq!(due, [
+    ///     Some(DueDate::Date(date)),
+    ///     date.and_hms_opt(17, 0, 0).map(DueDate::DateTime),
+    ///     date.and_hms_opt(17, 0, 30).map(DueDate::DateTime),
+    ///     None,
+    /// ]);
+    /// # }
+    /// ```
+    ///
+    /// [`due_in_zone`]: #method.due_in_zone
+    #[cfg(feature = "dates")]
+    pub fn due_datetime(&self) -> Option<DueDate> {
+        let due = self.due_value()?;
+
+        match due.time {
+            Some((hour, minute, second)) => due
+                .date
+                .and_hms_opt(hour, minute, second)
+                .map(DueDate::DateTime),
+            None => Some(DueDate::Date(due.date)),
+        }
+    }
+
+    /// Get the time that the task is due in the time zone `tz`.
+    ///
+    /// A due time without an offset, e.g. `due:2011-03-01T17:00`, is a local
+    /// time in `tz`. A due time with an offset from UTC, e.g.
+    /// `due:2011-03-01T17:00+02:00` or `due:2011-03-01T15:00Z`, is converted
+    /// to `tz`. `None` is returned if the task does not have a `due:` tag, its
+    /// value is invalid, or it does not have a time of day.
+    ///
+    /// Around daylight saving time transitions, a local time may occur twice
+    /// or not at all. A time that occurs twice is resolved to the earlier of
+    /// the two. A time that does not exist in `tz`, such as one that is
+    /// skipped when clocks move forward, returns `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::chrono::{FixedOffset, Utc};
+    ///
+    /// let zone = FixedOffset::east_opt(2 * 3600).unwrap();
+    /// let data = "
+    ///     File taxes due:2011-03-01T17:00
+    ///     Call Mom due:2011-03-01T17:00-05:00
+    ///     Pay rent due:2011-03-01
+    /// ";
+    /// let due: Vec<Option<String>> = data
+    ///     .tasks()
+    ///     .map(|task| task.due_in_zone(&zone).map(|due| due.to_rfc3339()))
+    ///     .collect();
+    ///
+    /// assert_eq!(due, [
+    ///     Some("2011-03-01T17:00:00+02:00".to_owned()),
+    ///     Some("2011-03-02T00:00:00+02:00".to_owned()),
+    ///     None,
+    /// ]);
+    /// # }
+    /// ```
+    #[cfg(feature = "dates")]
+    pub fn due_in_zone<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        let due = self.due_value()?;
+        let (hour, minute, second) = due.time?;
+        let datetime = due.date.and_hms_opt(hour, minute, second)?;
+
+        match due.offset {
+            Some(offset) => {
+                let offset = FixedOffset::east_opt(offset)?;
+
+                Some(
+                    offset
+                        .from_local_datetime(&datetime)
+                        .single()?
+                        .with_timezone(tz),
+                )
+            }
+            None => tz.from_local_datetime(&datetime).earliest(),
+        }
+    }
+
+    /// Parses the value of the first special tag with the provided key as a
+    /// duration, e.g. `est:90m` or `est:2h`. The value is a whole number
+    /// followed by a unit of `m` (minutes), `h` (hours), `d` (days), or `w`
+    /// (weeks). A number without a unit is a number of minutes. If the task
+    /// does not have the tag or its value is malformed, `None` is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::chrono::Duration;
+    ///
+    /// let task = "Write the report est:2h spent:45 left:soon".tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.duration_tag("est"), Some(Duration::minutes(120)));
+    /// assert_eq!(task.duration_tag("spent"), Some(Duration::minutes(45)));
+    /// assert_eq!(task.duration_tag("left"), None);
+    /// assert_eq!(task.duration_tag("due"), None);
+    /// # }
+    /// ```
+    #[cfg(feature = "dates")]
+    pub fn duration_tag(&self, key: &str) -> Option<Duration> {
+        date::parse_duration(self.get_special(key)?)
+    }
+
+    /// Returns the line that would represent the task in a `done.txt` file if
+    /// it were completed on `completion_date`. The task itself is not
+    /// modified.
+    ///
+    /// Complete tasks are returned as-is. Incomplete tasks are prefixed with
+    /// `x` and the completion date, and their priority is removed since
+    /// complete tasks do not have a priority.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let data = "
+    ///     (A) 2011-03-01 Call Mom @phone
+    ///     x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    /// ";
+    /// let date = Date::from_ymd_opt(2011, 3, 3).unwrap();
+    /// let lines: Vec<String> = data
+    ///     .tasks()
+    ///     .map(|task| task.format_for_done_txt(date))
+    ///     .collect();
+    ///
+    /// assert_eq!(lines, [
+    ///     "x 2011-03-03 2011-03-01 Call Mom @phone",
+    ///     "x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github",
+    /// ]);
+    /// # }
+    /// ```
+    pub fn format_for_done_txt(&self, completion_date: Date) -> String {
+        if self.is_complete() {
+            return self.to_string();
+        }
+
+        match self.creation_date() {
+            Some(creation_date) => format!(
+                "x {} {} {}",
+                completion_date,
+                creation_date,
+                self.description()
+            ),
+            None => format!("x {} {}", completion_date, self.description()),
+        }
+    }
+
+    /// Deserializes a task from a JSON string in the format produced by
+    /// [`to_json`]. Field names may be in snake case or camel case.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github";
+    /// let task = data.tasks().next().unwrap();
+    /// let json = task.to_json().unwrap();
+    ///
+    /// assert_eq!(Task::from_json(&json).unwrap(), task);
+    /// assert!(Task::from_json(r#"{"description":"Call Mom"}"#).is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`to_json`]: #method.to_json
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Task<'static>> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns the value of the first special tag with the provided key. For
+    /// example, the value of `due` in `Pay rent due:2011-03-01` is
+    /// `"2011-03-01"`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Pay rent due:2011-03-01 t:2011-02-25";
+    /// let task = data.tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.get_special("due"), Some("2011-03-01"));
+    /// assert_eq!(task.get_special("t"), Some("2011-02-25"));
+    /// assert_eq!(task.get_special("rec"), None);
+    /// # }
+    /// ```
+    pub fn get_special(&self, key: &str) -> Option<&str> {
+        let description = self.description();
+
+        self.tags().find_map(|tag| match tag {
+            Tag::Special { .. } => {
+                let (name, value) = split_special(&description[tag]);
+
+                if name == key {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+            Tag::Context { .. } | Tag::Project { .. } => None,
+        })
+    }
+
+    /// Returns `true` if the task has every context in `contexts`, e.g.
+    /// `["@phone", "@home"]`. An empty slice returns `true`.
+    ///
+    /// For up to 64 contexts, the tags of the task are only parsed once, and
+    /// parsing stops as soon as every context has been found.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "Call Mom @phone +Family @home".tasks().next().unwrap();
+    ///
+    /// assert!(task.has_all_contexts(&["@phone", "@home"]));
+    /// assert!(!task.has_all_contexts(&["@phone", "@work"]));
+    /// assert!(!task.has_all_contexts(&["+Family"]));
+    /// assert!(task.has_all_contexts(&[]));
+    /// # }
+    /// ```
+    pub fn has_all_contexts(&self, contexts: &[&str]) -> bool {
+        self.has_all_tags(contexts, |tag| matches!(tag, Tag::Context { .. }))
+    }
+
+    /// Returns `true` if the task has every project in `projects`, e.g.
+    /// `["+GarageSale", "+Family"]`. An empty slice returns `true`. See
+    /// [`has_all_contexts`].
+    ///
+    /// [`has_all_contexts`]: #method.has_all_contexts
+    pub fn has_all_projects(&self, projects: &[&str]) -> bool {
+        self.has_all_tags(projects, |tag| matches!(tag, Tag::Project { .. }))
+    }
+
+    /// Returns `true` if the task has at least one of the contexts in
+    /// `contexts`. An empty slice returns `false`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "Call Mom @phone +Family".tasks().next().unwrap();
+    ///
+    /// assert!(task.has_any_context(&["@home", "@phone"]));
+    /// assert!(!task.has_any_context(&["@home", "+Family"]));
+    /// assert!(!task.has_any_context(&[]));
+    /// # }
+    /// ```
+    pub fn has_any_context(&self, contexts: &[&str]) -> bool {
+        self.contexts_iter().any(|value| contexts.contains(&value))
+    }
+
+    /// Returns `true` if the task has a due date and is available. See
+    /// [`is_available`] for more information about availability.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::chrono::{Duration, Local};
+    ///
+    /// let today = Local::now().date_naive();
+    /// let data = format!(
+    ///     "File taxes due:{due} t:{past}\nPay rent due:{due} t:{future}\nCall Mom t:{past}",
+    ///     due = today + Duration::days(7),
+    ///     past = today - Duration::days(1),
+    ///     future = today + Duration::days(1),
+    /// );
+    /// let deadlines: Vec<bool> = data.tasks().map(|task| task.has_deadline()).collect();
+    ///
+    /// assert_eq!(deadlines, [true, false, false]);
+    /// # }
+    /// ```
+    ///
+    /// [`is_available`]: #method.is_available
+    #[cfg(feature = "dates")]
+    pub fn has_deadline(&self) -> bool {
+        self.due_date().is_some() && self.is_available()
+    }
+
+    /// Returns `true` if the task has a special tag with the provided key.
+    ///
+    /// Unlike `get_special(key).is_some()`, the value of each special tag is
+    /// never split out. Iteration stops at the first special tag that
+    /// matches.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Pay rent due:2011-03-01 threshold:2011-02-25";
+    /// let task = data.tasks().next().unwrap();
+    ///
+    /// assert!(task.has_special_key("due"));
+    /// assert!(task.has_special_key("threshold"));
+    /// assert!(!task.has_special_key("t"));
+    /// assert!(!task.has_special_key("due:2011"));
+    /// assert!(!task.has_special_key("rec"));
+    /// # }
+    /// ```
+    pub fn has_special_key(&self, key: &str) -> bool {
+        self.specials_iter()
+            .any(|text| split_special(text).0 == key)
+    }
+
+    /// Returns a new incomplete occurrence of a template task, created on
+    /// `date`. The occurrence keeps the priority and description of the task,
+    /// without the `h:` and `rec:` special tags that make it a template. See
+    /// [`is_template`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Date;
+    ///
+    /// let template = "(B) Water the plants h:1 rec:1w @home".tasks().next().unwrap();
+    /// let task = template.instantiate(Date::from_ymd_opt(2011, 3, 1).unwrap());
+    ///
+    /// assert_eq!(task.to_string(), "(B) 2011-03-01 Water the plants @home");
+    /// assert!(!task.is_template());
+    /// # }
+    /// ```
+    ///
+    /// [`is_template`]: #method.is_template
+    pub fn instantiate(&self, date: Date) -> Task<'static> {
+        let description = self.description();
+        let mut text = String::with_capacity(description.len());
+        let mut end = 0;
+
+        for tag in self.tags() {
+            let is_template_tag = match tag {
+                Tag::Special { .. } => {
+                    matches!(split_special(tag.as_str(description)).0, "h" | "rec")
+                }
+                Tag::Context { .. } | Tag::Project { .. } => false,
+            };
+
+            if is_template_tag {
+                let rest = &description[tag.end()..];
+
+                text.push_str(&description[end..tag.start()]);
+                end = description.len() - rest.trim_start().len();
+            }
+        }
+
+        text.push_str(&description[end..]);
+        text.truncate(text.trim_end().len());
+
+        Task {
+            state: State::Incomplete(self.priority(), Some(date)),
+            text: Cow::Owned(text),
+        }
+    }
+
+    /// Converts the task into a task that owns its description. The
+    /// description is only copied if it is borrowed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task: Task<'static> = {
+    ///     let data = String::from("(A) Call Mom @phone");
+    ///
+    ///     data.tasks().next().unwrap().into_owned()
+    /// };
+    ///
+    /// assert_eq!(task.to_string(), "(A) Call Mom @phone");
+    /// # }
+    /// ```
+    pub fn into_owned(self) -> Task<'static> {
+        Task {
+            state: self.state,
+            text: Cow::Owned(self.text.into_owned()),
+        }
+    }
+
+    /// Returns `true` if the task does not have a threshold date or if its
+    /// threshold date is on or before the current local date. A threshold
+    /// date is specified with the `t:` special tag and indicates that work on
+    /// a task can't begin until that date.
+    #[cfg(feature = "dates")]
+    pub fn is_available(&self) -> bool {
+        self.threshold_date()
+            .is_none_or(|date| date <= time::today())
+    }
+
+    /// Returns `true` if the task is complete, otherwise returns `false`.
+    pub fn is_complete(&self) -> bool {
+        self.state.is_complete()
+    }
+
+    /// Returns `true` if the task has the special tag `h:1`, which hides it
+    /// from views of the list in many todo.txt applications.
+    pub fn is_hidden(&self) -> bool {
+        self.get_special("h") == Some("1")
+    }
+
+    /// Returns `true` if the task is incomplete and its due date has passed.
+    ///
+    /// A task that is due on a date is overdue from the following day. A task
+    /// that is due at a specific time, as described in [`due_datetime`], is
+    /// overdue once that time has passed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::{chrono::NaiveDate, time};
+    ///
+    /// let data = "
+    ///     Pay rent due:2011-03-01
+    ///     File taxes due:2011-03-01T09:00
+    ///     Call Mom due:2011-03-01T17:00
+    ///     x Buy milk due:2011-02-28
+    /// ";
+    /// let now = NaiveDate::from_ymd_opt(2011, 3, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    ///
+    /// time::set_test_now(now);
+    ///
+    /// let overdue: Vec<bool> = data.tasks().map(|task| task.is_overdue()).collect();
+    ///
+    /// assert_eq!(overdue, [false, true, false, false]);
+    /// # time::clear_test_date();
+    /// # }
+    /// ```
+    ///
+    /// [`due_datetime`]: #method.due_datetime
+    #[cfg(feature = "dates")]
+    pub fn is_overdue(&self) -> bool {
+        if self.is_complete() {
+            return false;
+        }
+
+        match self.due_datetime() {
+            Some(DueDate::Date(date)) => date < time::today(),
+            Some(DueDate::DateTime(datetime)) => datetime < time::now(),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the task is hidden and has a valid `rec:` special
+    /// tag. Template tasks are not worked on themselves; instead, a new
+    /// occurrence is created from them with [`instantiate`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "
+    ///     Water the plants h:1 rec:1w
+    ///     Water the plants rec:1w
+    ///     Water the plants h:1
+    ///     Water the plants h:1 rec:weekly
+    /// ";
+    /// let templates: Vec<bool> = data.tasks().map(|task| task.is_template()).collect();
+    ///
+    /// assert_eq!(templates, [true, false, false, false]);
+    /// # }
+    /// ```
+    ///
+    /// [`instantiate`]: #method.instantiate
+    pub fn is_template(&self) -> bool {
+        self.is_hidden() && self.recurrence().is_some()
+    }
+
+    /// Parses the value of the first special tag with the key `T::KEY` using
+    /// the [`ParseSpecial`] implementation of `T`. If the task does not have a
+    /// matching special tag or the value is invalid, `None` is returned.
+    ///
+    /// [`ParseSpecial`]: parser/trait.ParseSpecial.html
+    pub fn parse_special<'t, T>(&'t self) -> Option<T>
+    where
+        T: ParseSpecial<'t>,
+    {
+        T::parse_special(self.get_special(T::KEY)?)
+    }
+
+    /// Get the priority of the task. If the task is complete, the priority
+    /// is guaranteed to be `Option::None`.
+    pub fn priority(&self) -> Option<Priority> {
+        self.state.priority()
+    }
+
+    /// Replaces the priority of an incomplete task. Complete tasks are left
+    /// unchanged, since they do not have a priority.
+    pub(crate) fn set_priority(&mut self, priority: Option<Priority>) {
+        if let State::Incomplete(_, creation_date) = self.state {
+            self.state = State::Incomplete(priority, creation_date);
+        }
+    }
+
+    /// Returns an iterator over the text of each project tag in the task's
+    /// description.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "(A) Call Mom @phone +Family rec:1w @home +Chores due:2011-03-01";
+    /// let task = data.tasks().next().unwrap();
+    /// let description = task.description();
+    /// let manual: Vec<&str> = task
+    ///     .tags()
+    ///     .filter(|tag| matches!(tag, Tag::Project { .. }))
+    ///     .map(|tag| tag.as_str(description))
+    ///     .collect();
+    ///
+    /// assert_eq!(task.projects_iter().collect::<Vec<_>>(), ["+Family", "+Chores"]);
+    /// assert_eq!(task.projects_iter().collect::<Vec<_>>(), manual);
+    /// # }
+    /// ```
+    pub fn projects_iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let description = self.description();
+
+        self.tags().filter_map(move |tag| match tag {
+            Tag::Project { .. } => Some(tag.as_str(description)),
+            Tag::Context { .. } | Tag::Special { .. } => None,
+        })
+    }
+
+    /// Returns the recurrence of the task from its `rec:` special tag. If the
+    /// task does not have a `rec:` tag or its value is invalid, `None` is
+    /// returned. See [`Recurrence`] for the format of the value.
+    ///
+    /// [`Recurrence`]: struct.Recurrence.html
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        self.parse_special()
+    }
+
+    /// Returns the score that `scorer` assigns to the task, for prioritizing
+    /// tasks by more than their priority. See [`default_scorer`] for an
+    /// example of a scorer, and [`TodoList::ranked`] to sort a list by score.
+    ///
+    /// [`default_scorer`]: fn.default_scorer.html
+    /// [`TodoList::ranked`]: struct.TodoList.html#method.ranked
+    pub fn score<F>(&self, scorer: F) -> i64
+    where
+        F: Fn(&Task<'_>) -> i64,
+    {
+        scorer(self)
+    }
+
+    /// Returns a serializable view of the task that writes field names in the
+    /// provided case.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::Case;
+    ///
+    /// let data = "(A) 2011-03-01 Call Mom";
+    /// let task = data.tasks().next().unwrap();
+    ///
+    /// let camel = serde_json::to_string(&task.serialize_with(Case::Camel)).unwrap();
+    /// let snake = serde_json::to_string(&task.serialize_with(Case::Snake)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     camel,
+    ///     r#"{"creationDate":"2011-03-01","description":"Call Mom","priority":"A","tags":[],"type":"INCOMPLETE"}"#
+    /// );
+    /// assert_eq!(
+    ///     snake,
+    ///     r#"{"creation_date":"2011-03-01","description":"Call Mom","priority":"A","tags":[],"type":"INCOMPLETE"}"#
+    /// );
+    /// assert_eq!(snake, serde_json::to_string(&task).unwrap());
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn serialize_with(&self, case: Case) -> WithCase<'_, 'a> {
+        WithCase { case, task: self }
+    }
+
+    /// A shorthand for `task.serialize_with(Case::Camel)`.
+    #[cfg(feature = "serde")]
+    pub fn serialize_camel(&self) -> WithCase<'_, 'a> {
+        self.serialize_with(Case::Camel)
+    }
+
+    /// Returns an iterator over the text of each special tag in the task's
+    /// description. Each item includes the key, e.g. `due:2011-03-01`. See
+    /// [`get_special`] to look up the value of a single special tag.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "(A) Call Mom @phone +Family rec:1w @home +Chores due:2011-03-01";
+    /// let task = data.tasks().next().unwrap();
+    /// let description = task.description();
+    /// let manual: Vec<&str> = task
+    ///     .tags()
+    ///     .filter(|tag| matches!(tag, Tag::Special { .. }))
+    ///     .map(|tag| tag.as_str(description))
+    ///     .collect();
+    ///
+    /// assert_eq!(task.specials_iter().collect::<Vec<_>>(), ["rec:1w", "due:2011-03-01"]);
+    /// assert_eq!(task.specials_iter().collect::<Vec<_>>(), manual);
+    /// # }
+    /// ```
+    ///
+    /// [`get_special`]: #method.get_special
+    pub fn specials_iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let description = self.description();
+
+        self.tags().filter_map(move |tag| match tag {
+            Tag::Special { .. } => Some(tag.as_str(description)),
+            Tag::Context { .. } | Tag::Project { .. } => None,
+        })
+    }
+
+    /// This method is useful if you want to refine the data of a task to the
+    /// distinct data of a complete or incomplete task.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// # let data = "(A) Thank Mom for the meatballs @phone";
+    /// # let task = data.tasks().next().unwrap();
+    /// #
+    /// use todotxt::State;
+    ///
+    /// match task.state() {
+    ///     State::Complete(completion_date, creation_date) => {
+    ///         // Do something with the completion and/or creation date...
+    ///     }
+    ///     State::Incomplete(priority, creation_date) => {
+    ///         // Do something with the priority and/or creation date...
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Returns `true` if the description of the task matches the regular
+    /// expression `pattern`. An error is returned if `pattern` is invalid.
+    ///
+    /// The pattern is compiled every time this method is called. To match many
+    /// tasks against the same pattern, compile it once and use
+    /// [`text_matches`] or [`Iter::filter_regex`] instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "(A) Thank Mom for the meatballs @phone";
+    /// let task = data.tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.text_matches_regex(r"\bMom\b").unwrap(), true);
+    /// assert_eq!(task.text_matches_regex(r"^\(A\)").unwrap(), false);
+    /// assert!(task.text_matches_regex(r"meatballs (").is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`text_matches`]: #method.text_matches
+    /// [`Iter::filter_regex`]: parser/struct.Iter.html#method.filter_regex
+    #[cfg(feature = "regex")]
+    pub fn text_matches_regex(&self, pattern: &str) -> Result<bool, regex::Error> {
+        Ok(self.text_matches(&Regex::new(pattern)?))
+    }
+
+    /// Returns `true` if the description of the task matches `regex`.
+    #[cfg(feature = "regex")]
+    pub fn text_matches(&self, regex: &Regex) -> bool {
+        regex.is_match(self.description())
+    }
+
+    /// Lazily parse and iterate over the tags contained within the description
+    /// of the task.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// # let data = "(A) Thank Mom for the meatballs @phone";
+    /// # let task = data.tasks().next().unwrap();
+    /// #
+    /// for tag in task.tags() {
+    ///     println!("{:#?}", tag);
+    /// }
+    /// # }
+    /// ```
+    pub fn tags(&self) -> Tags<'_> {
+        let iter = self.description().char_indices();
+
+        Tags { iter }
+    }
+
+    /// Get the threshold date of the task from its `t:` special tag. If the
+    /// task does not have a `t:` tag or the value is not a valid `YYYY-MM-DD`
+    /// date, `None` is returned.
+    pub fn threshold_date(&self) -> Option<Date> {
+        self.special_date("t")
+    }
+
+    /// Get the threshold date of the task as another date type, such as
+    /// `time::Date` when the `time` feature is enabled. `None` is returned if
+    /// the task does not have a threshold date or it is out of the range of `T`.
+    /// See [`ConvertDate`].
+    ///
+    /// [`ConvertDate`]: trait.ConvertDate.html
+    pub fn threshold_date_as<T: ConvertDate>(&self) -> Option<T> {
+        self.threshold_date().and_then(T::from_date)
+    }
+
+    /// Get the threshold date of the task like [`threshold_date`], but also
+    /// resolve relative dates such as `t:monday` against `reference`. See
+    /// [`due_date_with`] for details.
+    ///
+    /// [`due_date_with`]: #method.due_date_with
+    /// [`threshold_date`]: #method.threshold_date
+    #[cfg(feature = "dates")]
+    pub fn threshold_date_with(&self, reference: Date) -> Option<Date> {
+        self.threshold_date()
+            .or_else(|| time::resolve_relative(self.get_special("t")?, reference))
+    }
+
+    /// Returns a copy of the task in a representation that uses less memory.
+    /// See [`CompactTask`].
+    ///
+    /// [`CompactTask`]: struct.CompactTask.html
+    pub fn to_compact_repr(&self) -> CompactTask {
+        CompactTask::from(self.clone())
+    }
+
+    /// Serializes the task as a JSON string with snake case field names. See
+    /// [`serialize_with`] to serialize with camel case field names.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "(A) Call Mom @phone".tasks().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     task.to_json().unwrap(),
+    ///     r#"{"description":"Call Mom @phone","priority":"A","tags":[{"type":"CONTEXT","location":{"start":9,"end":15}}],"type":"INCOMPLETE"}"#
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`serialize_with`]: #method.serialize_with
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Serializes the task as a pretty-printed JSON string with snake case
+    /// field names.
+    #[cfg(feature = "serde")]
+    pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Returns the length in bytes of the task's todo.txt representation, which
+    /// is the length of its `Display` output. The length is counted without
+    /// formatting the task into a buffer.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "x  2011-03-02 Call Mom @phone".tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.total_bytes(), "x 2011-03-02 Call Mom @phone".len());
+    /// # }
+    /// ```
+    pub fn total_bytes(&self) -> usize {
+        let mut count = ByteCount(0);
+
+        // Counting bytes never fails.
+        let _ = self.write_line(&mut count);
+        count.0
+    }
+
+    /// Get the due date of the task from its `due:` special tag, or the reason
+    /// it is invalid. `Ok(None)` is returned if the task does not have a
+    /// `due:` tag. Otherwise the result is the same as [`due_date`], with an
+    /// error in place of `None`.
+    ///
+    /// This is useful to warn about malformed dates rather than ignoring them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::chrono::NaiveDate;
+    ///
+    /// let data = "
+    ///     Pay rent due:2011-03-01
+    ///     Call Mom @phone
+    ///     File taxes due:2011-02-30
+    /// ";
+    /// let dates: Vec<_> = data.tasks().map(|task| task.try_get_due_date()).collect();
+    ///
+    /// assert_eq!(dates[0], Ok(NaiveDate::from_ymd_opt(2011, 3, 1)));
+    /// assert_eq!(dates[1], Ok(None));
+    /// assert!(dates[2].is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`due_date`]: #method.due_date
+    #[cfg(feature = "dates")]
+    pub fn try_get_due_date(&self) -> Result<Option<Date>, ParseError> {
+        match self.get_special("due") {
+            Some(value) => match date::parse_due(value) {
+                Some(due) => Ok(Some(due.date)),
+                None => date::try_parse_exact(value).map(Some),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Get the threshold date of the task from its `t:` special tag, or the
+    /// reason it is invalid. See [`try_get_due_date`].
+    ///
+    /// [`try_get_due_date`]: #method.try_get_due_date
+    #[cfg(feature = "dates")]
+    pub fn try_get_threshold_date(&self) -> Result<Option<Date>, ParseError> {
+        match self.get_special("t") {
+            Some(value) => date::try_parse_exact(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns an iterator over the `http://`, `https://`, and `mailto:` URLs
+    /// in the description, as slices of it.
+    ///
+    /// Punctuation around a URL such as `(` and `).` is not part of it, except
+    /// for a closing bracket that matches an opening bracket in the URL. URLs
+    /// are still yielded by [`tags`] as special tags, since they contain a
+    /// colon.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let data = "Read https://blog.example.com/post (via mailto:tim@example.com). +Reading";
+    /// let task = data.tasks().next().unwrap();
+    ///
+    /// assert_eq!(
+    ///     task.urls().collect::<Vec<_>>(),
+    ///     ["https://blog.example.com/post", "mailto:tim@example.com"]
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`tags`]: #method.tags
+    pub fn urls(&self) -> impl Iterator<Item = &str> + '_ {
+        self.description().split_whitespace().filter_map(find_url)
+    }
+
+    /// Returns the problems with the task that are accepted when parsing
+    /// normally but cause the line to be rejected in strict mode. See
+    /// [`Input::try_tasks`].
+    ///
+    /// A word that is almost a date, such as `2024/01/01` or `2024-1-1`, is a
+    /// problem when it is the first word of the description and the task has
+    /// no creation date, since that is where the date would have been parsed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// use todotxt::parser::ParseErrorKind;
+    ///
+    /// let task = "x 2020-01-01 2023-05-05 Pay rent".tasks().next().unwrap();
+    ///
+    /// assert_eq!(task.validate(), [ParseErrorKind::CompletionBeforeCreation]);
+    ///
+    /// let task = "x 2023-05-05 2020-01-01 Pay rent".tasks().next().unwrap();
+    ///
+    /// assert!(task.validate().is_empty());
+    /// # }
+    /// ```
+    ///
+    /// [`Input::try_tasks`]: parser/trait.Input.html#tymethod.try_tasks
+    pub fn validate(&self) -> Vec<ParseErrorKind> {
+        let mut problems = Vec::new();
+
+        if !self.state.is_chronologically_valid() {
+            problems.push(ParseErrorKind::CompletionBeforeCreation);
+        }
+
+        if find_control(self.description()).is_some() {
+            problems.push(ParseErrorKind::ControlCharacter);
+        }
+
+        // A word that is almost a date is left in the description, where the
+        // date would have been parsed if it were valid.
+        if self.creation_date().is_none() {
+            let word = self.description().split_whitespace().next();
+
+            problems.extend(word.and_then(date::diagnose));
+        }
+
+        problems
+    }
+
+    /// Returns a copy of the task with each run of whitespace in its
+    /// description collapsed to a single space, e.g. for tasks imported from
+    /// tools that align their text with spaces. The state of the task is kept
+    /// as is rather than parsed again.
+    ///
+    /// If the description does not change, a task that borrows its description
+    /// from the input still does.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate todotxt;
+    /// #
+    /// # use todotxt::prelude::*;
+    /// #
+    /// # fn main() {
+    /// let task = "(A) Call  Mom\t\t@phone   +Family".tasks().next().unwrap();
+    /// let trimmed = task.with_text_trimmed();
+    ///
+    /// assert_eq!(trimmed.description(), "Call Mom @phone +Family");
+    /// assert_eq!(trimmed.priority(), Some(Priority::A));
+    /// # }
+    /// ```
+    pub fn with_text_trimmed(&self) -> Task<'a> {
+        let text = self
+            .description()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = match self.text {
+            Cow::Borrowed(original) if original == text => Cow::Borrowed(original),
+            _ => Cow::Owned(text),
+        };
+
+        Task {
+            state: self.state,
+            text,
+        }
+    }
+
+    /// Returns the description with the lifetime of the input, if the task
+    /// borrows it.
+    pub(crate) fn borrowed_description(&self) -> Option<&'a str> {
+        match self.text {
+            Cow::Borrowed(text) => Some(text),
+            Cow::Owned(_) => None,
+        }
+    }
+
+    pub(crate) fn from_parts(state: State, text: Cow<'a, str>) -> Self {
+        Task { state, text }
+    }
+
+    pub(crate) fn into_parts(self) -> (State, Cow<'a, str>) {
+        (self.state, self.text)
+    }
+
+    fn has_all_tags<F>(&self, values: &[&str], is_kind: F) -> bool
+    where
+        F: Fn(&Tag) -> bool,
+    {
+        let description = self.description();
+
+        // The values that have been found are tracked in a bit set, so values
+        // are checked 64 at a time without allocating.
+        values.chunks(64).all(|chunk| {
+            let all = u64::MAX >> (64 - chunk.len());
+            let mut found = 0;
+
+            for tag in self.tags().filter(&is_kind) {
+                let text = tag.as_str(description);
+
+                for (index, value) in chunk.iter().enumerate() {
+                    if *value == text {
+                        found |= 1 << index;
+                    }
+                }
+
+                if found == all {
+                    return true;
+                }
+            }
+
+            false
+        })
+    }
+
+    /// Returns the parsed value of the `due:` tag of the task.
+    fn due_value(&self) -> Option<DueValue> {
+        let value = self.get_special("due")?;
+        let due = date::parse_due(value);
+
+        #[cfg(feature = "tracing")]
+        if due.is_none() {
+            warn_invalid_special("due", value);
+        }
+
+        due
+    }
+
+    fn special_date(&self, key: &str) -> Option<Date> {
+        let value = self.get_special(key)?;
+        let date = date::parse_exact(value);
+
+        #[cfg(feature = "tracing")]
+        if date.is_none() {
+            warn_invalid_special(key, value);
         }
 
-        state.serialize_field("tags", &tags)?;
+        date
+    }
+
+    fn write_line<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        // Each part is preceded by a space unless it is the first, so that a
+        // task with an empty description is formatted as e.g. `x` or `(A)`.
+        let mut separator = "";
 
         if self.is_complete() {
-            state.serialize_field("type", "COMPLETE")?;
+            out.write_str("x")?;
+            separator = " ";
+        }
+
+        if let Some(priority) = self.priority() {
+            write!(out, "{}{}", separator, priority)?;
+            separator = " ";
+        }
+
+        if let Some(completion_date) = self.completion_date() {
+            write!(out, "{}{}", separator, completion_date)?;
+            separator = " ";
+        }
+
+        if let Some(creation_date) = self.creation_date() {
+            write!(out, "{}{}", separator, creation_date)?;
+            separator = " ";
+        }
+
+        if self.description().is_empty() {
+            return Ok(());
+        }
+
+        out.write_str(separator)?;
+
+        // Tasks that are built or deserialized may contain control characters,
+        // which are written the same way that they are parsed.
+        write_without_controls(out, self.description())
+    }
+}
+
+impl<'a> AsRef<str> for Task<'a> {
+    /// Returns the description of the task, without its completion mark,
+    /// priority, or dates.
+    fn as_ref(&self) -> &str {
+        self.description()
+    }
+}
+
+impl<'a> Clone for Task<'a> {
+    fn clone(&self) -> Task<'static> {
+        Task {
+            state: self.state,
+            text: Cow::Owned(String::from(&*self.text)),
+        }
+    }
+}
+
+impl<'a> Display for Task<'a> {
+    /// Width, fill, alignment, and precision apply to the whole line, as they
+    /// do for a `str`. Without them, the line is written directly rather than
+    /// through a buffer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.width().is_none() && f.precision().is_none() {
+            return self.write_line(f);
+        }
+
+        let mut line = String::new();
+
+        self.write_line(&mut line)?;
+        f.pad(&line)
+    }
+}
+
+impl<'a> Parse<'a> for Task<'a> {
+    type Output = Task<'a>;
+
+    fn parse(input: &'a str) -> nom::IResult<&'a str, Self::Output> {
+        let input = input.trim();
+
+        if find_control(input).is_none() {
+            let (text, state) = State::parse(input)?;
+            let task = Task {
+                state,
+                text: Cow::Borrowed(text),
+            };
+
+            return Ok((&text[text.len()..], task));
+        }
+
+        // Control characters are removed from the whole line rather than the
+        // description alone, so that e.g. `x\x0b2011-03-01` has a prefix.
+        let rest = &input[input.len()..];
+
+        match Task::parse(&remove_controls(input)) {
+            Ok((_, task)) => Ok((rest, task.into_owned())),
+            // The line is empty without its control characters.
+            Err(_) => Ok((
+                rest,
+                Task {
+                    state: State::Incomplete(None, None),
+                    text: Cow::Borrowed(rest),
+                },
+            )),
+        }
+    }
+}
+
+/// Tasks can be serialized with self-describing formats such as JSON as well
+/// as compact formats such as bincode. Self-describing formats omit the dates
+/// and priority of a task when they are absent. Other formats always receive
+/// every field, in the order `completion_date`, `creation_date`,
+/// `description`, `priority`, `tags`, and `type`, with absent values written
+/// as `None`. In these formats, the `type` of a task and its tags are written
+/// as the index of the variant (e.g. `0` for `COMPLETE` and `1` for
+/// `INCOMPLETE`).
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # use todotxt::prelude::*;
+/// #
+/// # fn main() {
+/// use todotxt::Date;
+///
+/// type Location = (usize, usize);
+/// type Fields = (
+///     Option<Date>,
+///     Option<Date>,
+///     String,
+///     Option<u32>,
+///     Vec<(u32, Location)>,
+///     u32,
+/// );
+///
+/// let data = "(B) 2011-03-01 Schedule Goodwill pickup +GarageSale";
+/// let task = data.tasks().next().unwrap();
+/// let bytes = bincode::serialize(&task).unwrap();
+/// let fields: Fields = bincode::deserialize(&bytes).unwrap();
+///
+/// assert_eq!(
+///     fields,
+///     (
+///         None,
+///         Date::from_ymd_opt(2011, 3, 1),
+///         "Schedule Goodwill pickup +GarageSale".to_owned(),
+///         Some(Priority::B as u32),
+///         vec![(1, (25, 36))],
+///         1,
+///     )
+/// );
+/// # }
+/// ```
+#[cfg(feature = "serde")]
+impl<'a> Serialize for Task<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_with(Case::default()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'t, 'a> Serialize for WithCase<'t, 'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let WithCase { case, task } = *self;
+        let completion_date = task.completion_date();
+        let creation_date = task.creation_date();
+        let priority = task.priority();
+
+        // Self-describing formats omit absent fields entirely. Formats that
+        // rely on the position of a field (e.g. bincode) always receive every
+        // field so that the serialized struct has a fixed shape.
+        let skip = serializer.is_human_readable();
+        let len = if skip {
+            3 + [
+                completion_date.is_some(),
+                creation_date.is_some(),
+                priority.is_some(),
+            ]
+            .iter()
+            .filter(|present| **present)
+            .count()
+        } else {
+            6
+        };
+
+        let mut state = serializer.serialize_struct("Task", len)?;
+        let key = case.pick("completion_date", "completionDate");
+
+        serialize_optional_field(&mut state, key, completion_date, skip)?;
+
+        let key = case.pick("creation_date", "creationDate");
+
+        serialize_optional_field(&mut state, key, creation_date, skip)?;
+        state.serialize_field("description", task.description())?;
+        serialize_optional_field(&mut state, "priority", priority, skip)?;
+        state.serialize_field("tags", &TagList(task.tags()))?;
+
+        if task.is_complete() {
+            state.serialize_field("type", &StateKind::Complete)?;
         } else {
-            state.serialize_field("type", "INCOMPLETE")?;
+            state.serialize_field("type", &StateKind::Incomplete)?;
         }
 
         state.end()
     }
 }
+
+/// Tags are parsed twice, once to count them for formats that need the length
+/// of a sequence up front and once to serialize them, which is cheaper than
+/// allocating a `Vec` for every task.
+#[cfg(feature = "serde")]
+impl<'t> Serialize for TagList<'t> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.clone().count()))?;
+
+        for tag in self.0.clone() {
+            seq.serialize_element(&tag)?;
+        }
+
+        seq.end()
+    }
+}
+
+/// Returns the URL in `word` without the punctuation around it, if the word
+/// contains one. See [`Task::urls`].
+fn find_url(word: &str) -> Option<&str> {
+    const SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
+
+    let url = word.trim_start_matches(['(', '[', '{', '<', '"', '\'']);
+    let scheme = SCHEMES.iter().find(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })?;
+    let mut end = url.len();
+
+    // Trailing punctuation is removed a character at a time, keeping closing
+    // brackets that are balanced within the URL, e.g. in `.../Rust_(language)`.
+    while let Some(last) = url[..end].chars().next_back() {
+        let opening = match last {
+            ')' => '(',
+            ']' => '[',
+            '}' => '{',
+            '.' | ',' | ';' | ':' | '!' | '?' | '>' | '"' | '\'' => {
+                end -= 1;
+                continue;
+            }
+            _ => break,
+        };
+
+        if url[..end].matches(opening).count() >= url[..end].matches(last).count() {
+            break;
+        }
+
+        end -= 1;
+    }
+
+    if end > scheme.len() {
+        Some(&url[..end])
+    } else {
+        None
+    }
+}
+
+/// Returns the byte index of the first control character in `text` other than
+/// tab.
+pub(crate) fn find_control(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    // C0 controls and DEL are single bytes, and C1 controls are encoded as
+    // 0xC2 followed by a byte from 0x80 to 0x9F.
+    (0..bytes.len()).find(|&index| match bytes[index] {
+        b'\t' => false,
+        0x00..=0x1f | 0x7f => true,
+        0xc2 => matches!(bytes.get(index + 1), Some(0x80..=0x9f)),
+        _ => false,
+    })
+}
+
+/// Writes `text` without the control characters other than tab, replacing the
+/// ones that are whitespace with a space, as [`remove_controls`] does.
+pub(crate) fn write_without_controls<W: fmt::Write>(out: &mut W, text: &str) -> fmt::Result {
+    let mut rest = text;
+
+    while let Some(index) = find_control(rest) {
+        let control = rest[index..].chars().next().unwrap_or_default();
+
+        out.write_str(&rest[..index])?;
+
+        if control.is_whitespace() {
+            out.write_str(" ")?;
+        }
+
+        rest = &rest[index + control.len_utf8()..];
+    }
+
+    out.write_str(rest)
+}
+
+/// Removes the control characters from `text` other than tab, replacing the
+/// ones that are whitespace with a space.
+fn remove_controls(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            '\t' => Some(c),
+            _ if c.is_control() && c.is_whitespace() => Some(' '),
+            _ if c.is_control() => None,
+            _ => Some(c),
+        })
+        .collect()
+}
+
+/// Splits the text of a special tag into its key and value.
+pub(crate) fn split_special(text: &str) -> (&str, &str) {
+    let index = text.find(':').unwrap_or(text.len());
+
+    (&text[..index], &text[index + 1..])
+}
+
+/// Emits a warning that the value of the special tag `key` is not valid.
+#[cfg(feature = "tracing")]
+fn warn_invalid_special(key: &str, value: &str) {
+    let value = crate::parser::truncate_for_trace(value);
+
+    tracing::warn!(key, value, "invalid special tag value");
+}
+
+#[cfg(feature = "serde")]
+fn serialize_optional_field<S, T>(
+    state: &mut S,
+    key: &'static str,
+    value: Option<T>,
+    skip: bool,
+) -> Result<(), S::Error>
+where
+    S: SerializeStruct,
+    T: Serialize,
+{
+    match value {
+        None if skip => state.skip_field(key),
+        value => state.serialize_field(key, &value),
+    }
+}
+
+/// Generates a date with a four digit year, the only years that can be
+/// represented in a todo.txt file.
+#[cfg(feature = "arbitrary")]
+fn arbitrary_date(u: &mut Unstructured<'_>) -> arbitrary::Result<Date> {
+    let min = Date::from_ymd_opt(1000, 1, 1).unwrap();
+    let max = Date::from_ymd_opt(9999, 12, 31).unwrap();
+    let days = u.int_in_range(0..=(max - min).num_days())?;
+
+    Ok(min + Duration::days(days))
+}
@@ -0,0 +1,172 @@
+//! The current date and time, as seen by the methods of this crate that
+//! depend on them.
+//!
+//! Methods such as [`Task::is_available`] and [`TodoList::statistics`] read
+//! the current date through [`today`] rather than asking the system clock
+//! directly. Tests can fix the date with [`set_test_date`] to make those
+//! methods deterministic. Dates that are relative to another date, such as
+//! `due:tomorrow`, are resolved with [`resolve_relative`].
+//!
+//! ## Example
+//!
+//! ```
+//! # extern crate todotxt;
+//! #
+//! # use todotxt::prelude::*;
+//! #
+//! # fn main() {
+//! use todotxt::{chrono::NaiveDate, time};
+//!
+//! let task = "Pay rent due:2011-03-05".tasks().next().unwrap();
+//!
+//! time::set_test_date(NaiveDate::from_ymd_opt(2011, 3, 1).unwrap());
+//! assert_eq!(task.days_until_due(), Some(4));
+//!
+//! time::clear_test_date();
+//! # }
+//! ```
+//!
+//! [`Task::is_available`]: ../struct.Task.html#method.is_available
+//! [`TodoList::statistics`]: ../struct.TodoList.html#method.statistics
+//! [`resolve_relative`]: fn.resolve_relative.html
+//! [`today`]: fn.today.html
+//! [`set_test_date`]: fn.set_test_date.html
+
+use chrono::{Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, Utc, Weekday};
+use std::cell::Cell;
+
+thread_local! {
+    static TEST_NOW: Cell<Option<NaiveDateTime>> = const { Cell::new(None) };
+}
+
+/// Stops overriding the current date on this thread. See [`set_test_date`].
+///
+/// [`set_test_date`]: fn.set_test_date.html
+pub fn clear_test_date() {
+    TEST_NOW.with(|now| now.set(None));
+}
+
+/// Returns the current local date and time, or the value set by
+/// [`set_test_now`] or [`set_test_date`] on this thread.
+///
+/// [`set_test_date`]: fn.set_test_date.html
+/// [`set_test_now`]: fn.set_test_now.html
+pub fn now() -> NaiveDateTime {
+    TEST_NOW
+        .with(Cell::get)
+        .unwrap_or_else(|| Local::now().naive_local())
+}
+
+/// Resolves a relative date, as written by tools such as topydo, against
+/// `reference`. Returns `None` if `value` is not a relative date.
+///
+/// The following forms are supported, ignoring case:
+///
+/// - `today`, `tomorrow`, and `yesterday`.
+/// - The name of a weekday, e.g. `monday` or `mon`, which resolves to the
+///   next occurrence of that day after `reference`. A weekday never resolves
+///   to `reference` itself.
+/// - A number of days, weeks, months, or years after `reference`, e.g. `3d`,
+///   `2w`, `1m`, or `1y`. Months and years that end on a day that does not
+///   exist are clamped to the last day of the month.
+///
+/// Dates in the `YYYY-MM-DD` format are not relative and return `None`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate todotxt;
+/// #
+/// # fn main() {
+/// use todotxt::{chrono::NaiveDate, time};
+///
+/// // 2011-03-01 is a Tuesday.
+/// let reference = NaiveDate::from_ymd_opt(2011, 3, 1).unwrap();
+/// let resolve = |value| time::resolve_relative(value, reference).map(|date| date.to_string());
+///
+/// assert_eq!(resolve("tomorrow").as_deref(), Some("2011-03-02"));
+/// assert_eq!(resolve("Monday").as_deref(), Some("2011-03-07"));
+/// assert_eq!(resolve("tue").as_deref(), Some("2011-03-08"));
+/// assert_eq!(resolve("3d").as_deref(), Some("2011-03-04"));
+/// assert_eq!(resolve("1m").as_deref(), Some("2011-04-01"));
+/// assert_eq!(resolve("2011-03-05"), None);
+/// # }
+/// ```
+pub fn resolve_relative(value: &str, reference: NaiveDate) -> Option<NaiveDate> {
+    if value.eq_ignore_ascii_case("today") {
+        return Some(reference);
+    } else if value.eq_ignore_ascii_case("tomorrow") {
+        return reference.succ_opt();
+    } else if value.eq_ignore_ascii_case("yesterday") {
+        return reference.pred_opt();
+    } else if let Ok(weekday) = value.parse::<Weekday>() {
+        let days =
+            (weekday.num_days_from_monday() + 6 - reference.weekday().num_days_from_monday()) % 7
+                + 1;
+
+        return reference.checked_add_days(Days::new(u64::from(days)));
+    }
+
+    let (count, unit) = match *value.as_bytes().last()? {
+        unit if unit.is_ascii() => (&value[..value.len() - 1], unit.to_ascii_lowercase()),
+        _ => return None,
+    };
+
+    if count.is_empty() || !count.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let count: u32 = count.parse().ok()?;
+
+    match unit {
+        b'd' => reference.checked_add_days(Days::new(u64::from(count))),
+        b'w' => reference.checked_add_days(Days::new(u64::from(count) * 7)),
+        b'm' => reference.checked_add_months(Months::new(count)),
+        b'y' => reference.checked_add_months(Months::new(count.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// Overrides the current date on this thread until [`clear_test_date`] is
+/// called. This affects [`today`], [`now`], and every method of this crate
+/// that depends on the current date.
+///
+/// The override is thread-local so that tests running in parallel do not
+/// affect each other. It is intended for tests; it has no effect on work
+/// done by other threads, such as rayon's thread pool.
+///
+/// [`clear_test_date`]: fn.clear_test_date.html
+/// [`now`]: fn.now.html
+/// [`today`]: fn.today.html
+pub fn set_test_date(date: NaiveDate) {
+    set_test_now(date.and_time(Default::default()));
+}
+
+/// Overrides the current date and time on this thread. This is the same as
+/// [`set_test_date`], except that [`now`] returns `now` rather than midnight.
+///
+/// [`now`]: fn.now.html
+/// [`set_test_date`]: fn.set_test_date.html
+pub fn set_test_now(now: NaiveDateTime) {
+    TEST_NOW.with(|test_now| test_now.set(Some(now)));
+}
+
+/// Returns the current local date, or the date set by [`set_test_date`] or
+/// [`set_test_now`] on this thread.
+///
+/// [`set_test_date`]: fn.set_test_date.html
+/// [`set_test_now`]: fn.set_test_now.html
+pub fn today() -> NaiveDate {
+    now().date()
+}
+
+/// Returns the current date in UTC, or the date set by [`set_test_date`] or
+/// [`set_test_now`] on this thread.
+///
+/// [`set_test_date`]: fn.set_test_date.html
+/// [`set_test_now`]: fn.set_test_now.html
+pub(crate) fn today_utc() -> NaiveDate {
+    TEST_NOW
+        .with(Cell::get)
+        .map_or_else(|| Utc::now().date_naive(), |now| now.date())
+}
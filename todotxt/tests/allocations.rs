@@ -0,0 +1,86 @@
+//! Counts the heap allocations made while serializing and formatting tasks.
+
+#![cfg(feature = "serde")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Write,
+};
+use todotxt::prelude::*;
+
+/// Counts the allocations made by the current thread so that tests running in
+/// parallel don't affect each other.
+struct Counter;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[global_allocator]
+static ALLOCATOR: Counter = Counter;
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Returns the number of allocations made by `f`.
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+static DATA: &str = include_str!("../../fixtures/todo.txt");
+
+#[test]
+fn serializing_a_task_does_not_allocate() {
+    // Buffers large enough that the serializers never need to grow them.
+    let mut json = Vec::with_capacity(4096);
+    let mut bincode = Vec::with_capacity(4096);
+
+    for task in DATA.tasks() {
+        json.clear();
+        bincode.clear();
+
+        let count = allocations(|| {
+            serde_json::to_writer(&mut json, &task).unwrap();
+            serde_json::to_writer(&mut json, &task.serialize_camel()).unwrap();
+        });
+
+        assert_eq!(count, 0, "serializing {:?} allocated", task.to_string());
+
+        // Dates are serialized with `collect_str`, which bincode implements by
+        // formatting the date into a `String` of its own.
+        if task.completion_date().is_none() && task.creation_date().is_none() {
+            let count = allocations(|| {
+                bincode::serialize_into(&mut bincode, &task).unwrap();
+            });
+
+            assert_eq!(count, 0, "serializing {:?} allocated", task.to_string());
+        }
+    }
+}
+
+#[test]
+fn formatting_a_task_does_not_allocate() {
+    let mut output = String::with_capacity(4096);
+
+    for task in DATA.tasks() {
+        output.clear();
+
+        let count = allocations(|| {
+            write!(output, "{} {:?}", task, task).unwrap();
+        });
+
+        assert_eq!(count, 0, "formatting {:?} allocated", task.to_string());
+    }
+}
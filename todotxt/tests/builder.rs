@@ -0,0 +1,65 @@
+//! Tests for creating and completing tasks programmatically.
+
+use todotxt::{prelude::*, Date, TaskBuilder};
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn built_tasks_format_as_todo_txt_lines() {
+    let today = date(2011, 3, 1);
+    let task = TaskBuilder::new("  Schedule Goodwill pickup +GarageSale @phone\n")
+        .priority(Priority::B)
+        .created_on(today)
+        .build();
+
+    assert_eq!(
+        task.to_string(),
+        "(B) 2011-03-01 Schedule Goodwill pickup +GarageSale @phone"
+    );
+    assert_eq!(task.to_string().tasks().next(), Some(task));
+    assert_eq!(TaskBuilder::new("Call Mom").build().to_string(), "Call Mom");
+}
+
+#[test]
+fn completing_a_task_stamps_the_completion_date() {
+    let today = date(2011, 3, 3);
+    let data = "
+        (A) 2011-03-01 Call Mom @phone
+        Post signs around the neighborhood +GarageSale
+        x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    ";
+    let lines: Vec<String> = data
+        .tasks()
+        .map(|mut task| {
+            task.complete_on(today);
+            task.to_string()
+        })
+        .collect();
+
+    assert_eq!(
+        lines,
+        [
+            "x 2011-03-03 2011-03-01 Call Mom @phone",
+            "x 2011-03-03 Post signs around the neighborhood +GarageSale",
+            "x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github",
+        ]
+    );
+}
+
+#[cfg(feature = "dates")]
+#[test]
+fn today_is_the_current_utc_date() {
+    use todotxt::chrono::Utc;
+
+    let before = Utc::now().date_naive();
+    let mut task = TaskBuilder::new("Call Mom").created_today().build();
+    let after = Utc::now().date_naive();
+
+    task.complete_today();
+
+    for date in &[task.creation_date(), task.completion_date()] {
+        assert!(matches!(date, Some(date) if (before..=after).contains(date)));
+    }
+}
@@ -0,0 +1,98 @@
+//! Snapshot tests for the escape sequences of colored tasks.
+
+#![cfg(feature = "color")]
+
+use todotxt::{prelude::*, ColorScheme};
+
+fn colored(line: &str, scheme: &ColorScheme) -> String {
+    let task = line.tasks().next().unwrap();
+    let colored = task.display_colored(scheme).to_string();
+
+    colored
+}
+
+#[test]
+fn parts_of_incomplete_tasks_are_colored() {
+    let scheme = ColorScheme::ansi();
+
+    assert_eq!(
+        colored("(A) 2011-03-01 Call Mom @phone +Family rec:1w", &scheme),
+        "\x1b[31m(A)\x1b[0m 2011-03-01 Call Mom \x1b[32m@phone\x1b[0m \
+         \x1b[34m+Family\x1b[0m rec:1w"
+    );
+    assert_eq!(
+        colored("(C) @home", &scheme),
+        "\x1b[33m(C)\x1b[0m \x1b[32m@home\x1b[0m"
+    );
+    assert_eq!(colored("Pay rent", &scheme), "Pay rent");
+}
+
+#[test]
+fn complete_tasks_are_dimmed() {
+    assert_eq!(
+        colored("x 2011-03-02 Call Mom @phone", &ColorScheme::ansi()),
+        "\x1b[2mx 2011-03-02 Call Mom @phone\x1b[0m"
+    );
+}
+
+#[cfg(feature = "dates")]
+#[test]
+fn due_tags_of_overdue_tasks_are_highlighted() {
+    use todotxt::{chrono::NaiveDate, time};
+
+    time::set_test_date(NaiveDate::from_ymd_opt(2011, 3, 5).unwrap());
+
+    let scheme = ColorScheme::ansi().overdue("7");
+
+    assert_eq!(
+        colored("Pay rent due:2011-03-01 t:2011-02-01", &scheme),
+        "Pay rent \x1b[7mdue:2011-03-01\x1b[0m t:2011-02-01"
+    );
+    assert_eq!(
+        colored("Pay rent due:2011-03-09", &scheme),
+        "Pay rent due:2011-03-09"
+    );
+
+    time::clear_test_date();
+}
+
+#[test]
+fn only_escape_sequences_are_added() {
+    let data = "
+        (A) 2011-03-01 Call Mom @phone +Family
+        x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+        (B)   Schedule\tGoodwill pickup +GarageSale @phone
+        Café\0 crème @home due:2011-03-01
+        (D)
+    ";
+
+    for task in data.tasks() {
+        let colored = task.display_colored(&ColorScheme::ansi()).to_string();
+        let stripped = colored
+            .split("\x1b[")
+            .enumerate()
+            .map(|(index, part)| match index {
+                0 => part,
+                _ => &part[part.find('m').unwrap() + 1..],
+            })
+            .collect::<String>();
+
+        assert_eq!(stripped, task.to_string());
+        assert_eq!(
+            task.display_colored(&ColorScheme::plain()).to_string(),
+            task.to_string()
+        );
+    }
+}
+
+#[test]
+fn no_color_disables_the_default_scheme() {
+    std::env::set_var("NO_COLOR", "1");
+    assert_eq!(ColorScheme::new(), ColorScheme::plain());
+
+    std::env::set_var("NO_COLOR", "");
+    assert_eq!(ColorScheme::default(), ColorScheme::ansi());
+
+    std::env::remove_var("NO_COLOR");
+    assert_eq!(ColorScheme::new(), ColorScheme::ansi());
+}
@@ -0,0 +1,82 @@
+//! Tests for lines that contain control characters, such as embedded NULs.
+
+use todotxt::{parser::ParseErrorKind, prelude::*, TaskBuilder};
+
+#[test]
+fn control_characters_are_removed_when_parsing() {
+    let cases = [
+        ("Call\0 Mom @phone", "Call Mom @phone"),
+        ("Call\x0bMom @phone", "Call Mom @phone"),
+        ("Call Mom\x7f @phone", "Call Mom @phone"),
+        ("Call Mom\u{85}@phone", "Call Mom @phone"),
+        ("Call\tMom \0", "Call\tMom"),
+        ("\0\0\0", ""),
+    ];
+
+    for (line, description) in &cases {
+        let task = line.tasks().next().unwrap();
+
+        assert_eq!(task.description(), *description, "{:?}", line);
+        assert_eq!(task.to_string(), *description, "{:?}", line);
+    }
+}
+
+#[test]
+fn control_characters_do_not_hide_prefixes() {
+    let task = "x\x0b2011-03-02\0 2011-03-01 Call Mom"
+        .tasks()
+        .next()
+        .unwrap();
+
+    assert!(task.is_complete());
+    assert_eq!(task.completion_date().unwrap().to_string(), "2011-03-02");
+    assert_eq!(task.creation_date().unwrap().to_string(), "2011-03-01");
+    assert_eq!(task.description(), "Call Mom");
+    assert_eq!(task.to_string(), "x 2011-03-02 2011-03-01 Call Mom");
+}
+
+#[test]
+fn formatted_tasks_never_contain_control_characters() {
+    let task = TaskBuilder::new("Call\x0bMom\0 @phone\x7f\u{9f}")
+        .priority(Priority::A)
+        .build();
+    let line = task.to_string();
+
+    assert_eq!(line, "(A) Call Mom @phone");
+    assert_eq!(line.tasks().next().unwrap().to_string(), line);
+    assert_eq!(task.validate(), [ParseErrorKind::ControlCharacter]);
+}
+
+#[test]
+fn control_characters_are_rejected_in_strict_mode() {
+    let data = "Call Mom\nPay\0 rent\nx 2011-03-02 Review\x7f PR\n";
+    let results: Vec<_> = data.try_tasks().collect();
+
+    assert!(results[0].is_ok());
+
+    for (result, line, text) in &[(&results[1], 2, "\0"), (&results[2], 3, "\x7f")] {
+        let error = result.as_ref().unwrap_err();
+
+        assert_eq!(error.kind(), ParseErrorKind::ControlCharacter);
+        assert_eq!(error.line(), *line);
+        assert_eq!(&data[error.span()], *text);
+    }
+}
+
+#[test]
+fn special_values_are_borrowed_from_lines_with_control_characters() {
+    let data = "Call\0 Mom due:2011-03-01\nPay rent due:2011-03-02";
+    let values: Vec<(&str, String)> = data
+        .tasks()
+        .flat_map_special("due")
+        .map(|(value, task)| (value, task.to_string()))
+        .collect();
+
+    assert_eq!(
+        values,
+        [
+            ("2011-03-01", "Call Mom due:2011-03-01".to_owned()),
+            ("2011-03-02", "Pay rent due:2011-03-02".to_owned()),
+        ]
+    );
+}
@@ -0,0 +1,28 @@
+//! Tests for using tasks where strings are expected.
+
+use todotxt::prelude::*;
+
+fn mentions<S: AsRef<str>>(text: S, word: &str) -> bool {
+    text.as_ref().split_whitespace().any(|other| other == word)
+}
+
+#[test]
+fn tasks_are_referenced_as_their_description() {
+    let task = "x 2011-03-02 (A) Call Mom @phone".tasks().next().unwrap();
+    let text: &str = task.as_ref();
+
+    assert_eq!(text, task.description());
+    assert!(text.starts_with("(A) Call"));
+    assert!(text.contains("Mom"));
+    assert!(!text.starts_with('x'));
+}
+
+#[test]
+fn tasks_are_accepted_by_generic_string_functions() {
+    let tasks: Vec<Task> = "(A) Call Mom @phone\nPay rent".tasks().collect();
+    let phone: Vec<bool> = tasks.iter().map(|task| mentions(task, "@phone")).collect();
+
+    assert_eq!(phone, [true, false]);
+    assert!("Please Pay rent today".contains(tasks[1].as_ref()));
+    assert!(tasks[0].as_ref().ends_with("@phone"));
+}
@@ -0,0 +1,65 @@
+//! Tests for decoding todo.txt files that are not UTF-8.
+
+use std::borrow::Cow;
+use todotxt::{parser::decode_lossy, prelude::*};
+
+const DATA: &str = "(A) Buy café for the “meeting” @store\nx 2011-03-02 Pay rent €800\n";
+
+/// `DATA` encoded as Windows-1252.
+const WINDOWS_1252: &[u8] =
+    b"(A) Buy caf\xe9 for the \x93meeting\x94 @store\nx 2011-03-02 Pay rent \x80800\n";
+
+fn utf_16(bom: [u8; 2], to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    let mut bytes = bom.to_vec();
+
+    bytes.extend(DATA.encode_utf16().flat_map(to_bytes));
+    bytes
+}
+
+fn lines(data: &str) -> Vec<String> {
+    data.tasks().map(|task| task.to_string()).collect()
+}
+
+#[test]
+fn every_encoding_parses_into_the_same_tasks() {
+    let mut utf_8_bom = vec![0xEF, 0xBB, 0xBF];
+
+    utf_8_bom.extend(DATA.as_bytes());
+
+    let encodings: [(&str, Vec<u8>); 5] = [
+        ("UTF-8", DATA.as_bytes().to_vec()),
+        ("UTF-8 with BOM", utf_8_bom),
+        ("UTF-16LE", utf_16([0xFF, 0xFE], u16::to_le_bytes)),
+        ("UTF-16BE", utf_16([0xFE, 0xFF], u16::to_be_bytes)),
+        ("Windows-1252", WINDOWS_1252.to_vec()),
+    ];
+
+    for (name, bytes) in &encodings {
+        assert_eq!(decode_lossy(bytes), DATA, "{}", name);
+        assert_eq!(lines(&decode_lossy(bytes)), lines(DATA), "{}", name);
+    }
+}
+
+#[test]
+fn utf_8_is_borrowed() {
+    let mut utf_8_bom = vec![0xEF, 0xBB, 0xBF];
+
+    utf_8_bom.extend(DATA.as_bytes());
+
+    assert!(matches!(decode_lossy(DATA.as_bytes()), Cow::Borrowed(DATA)));
+    assert!(matches!(decode_lossy(&utf_8_bom), Cow::Borrowed(DATA)));
+    assert!(matches!(decode_lossy(WINDOWS_1252), Cow::Owned(_)));
+}
+
+#[test]
+fn malformed_utf_16_is_replaced() {
+    // An unpaired surrogate and a trailing byte.
+    let bytes = [0xFF, 0xFE, b'h', 0, b'i', 0, 0x00, 0xD8, b'!', 0, b'?'];
+
+    assert_eq!(decode_lossy(&bytes), "hi\u{fffd}!\u{fffd}");
+}
+
+#[test]
+fn latin_1_and_undefined_windows_1252_bytes_are_kept() {
+    assert_eq!(decode_lossy(b"\xa0\xff\x81\x9d"), "\u{a0}ÿ\u{81}\u{9d}");
+}
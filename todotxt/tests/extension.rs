@@ -0,0 +1,74 @@
+//! Tests for parsing an extended line format with the public parsers.
+
+use todotxt::{
+    nom::{self, IResult},
+    parser::{parse_one, Parse},
+    prelude::*,
+    Date, State,
+};
+
+/// An estimate such as `~3` between the prefixes and the description.
+struct Estimate;
+
+impl<'a> Parse<'a> for Estimate {
+    type Output = u32;
+
+    fn parse(input: &'a str) -> IResult<&'a str, u32> {
+        let digits = input.strip_prefix('~').unwrap_or("");
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+
+        match digits[..end].parse() {
+            Ok(points) => Ok((digits[end..].trim_start(), points)),
+            Err(_) => Err(nom::Err::Error(nom::Context::Code(
+                input,
+                nom::ErrorKind::Digit,
+            ))),
+        }
+    }
+}
+
+fn parse_extended(line: &str) -> Option<(State, Option<u32>, &str)> {
+    let (rest, state) = State::parse(line.trim()).ok()?;
+
+    match Estimate::parse(rest) {
+        Ok((description, points)) => Some((state, Some(points), description)),
+        Err(_) => Some((state, None, rest)),
+    }
+}
+
+#[test]
+fn custom_fields_follow_the_prefixes() {
+    let date = Date::from_ymd_opt(2011, 3, 1);
+
+    assert_eq!(
+        parse_extended("(A) 2011-03-01 ~3 Call Mom @phone"),
+        Some((
+            State::Incomplete(Some(Priority::A), date),
+            Some(3),
+            "Call Mom @phone"
+        ))
+    );
+    assert_eq!(
+        parse_extended("x 2011-03-02 ~12 Pay rent"),
+        Some((
+            State::Complete(Date::from_ymd_opt(2011, 3, 2), None),
+            Some(12),
+            "Pay rent"
+        ))
+    );
+    assert_eq!(
+        parse_extended("~ Call Mom"),
+        Some((State::Incomplete(None, None), None, "~ Call Mom"))
+    );
+}
+
+#[test]
+fn parse_one_ignores_the_rest_of_the_input() {
+    assert_eq!(parse_one::<Estimate>("~3 Call Mom"), Some(3));
+    assert_eq!(parse_one::<Estimate>("3 Call Mom"), None);
+    assert_eq!(parse_one::<Priority>("(B)x"), Some(Priority::B));
+    assert_eq!(parse_one::<Priority>("(A"), None);
+    assert_eq!(parse_one::<Date>("2011-02-30"), None);
+}
@@ -0,0 +1,235 @@
+//! Tests for reading and writing todo.txt files.
+
+use std::{env, fs, path::PathBuf};
+use todotxt::{prelude::*, LineEnding, TodoList, WriteOptions};
+
+const DATA: &str = "
+    (A) 2011-03-01 Call Mom @phone
+    x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    Post signs around the neighborhood +GarageSale due:2011-03-05
+";
+
+/// Returns a path in the temporary directory that is unique to the test.
+fn path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("todotxt-{}-{}.txt", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn written_lists_are_read_back_unchanged() {
+    let path = path("round-trip");
+    let list: TodoList = DATA.tasks().collect();
+
+    list.write_todo_txt(&path).unwrap();
+
+    assert_eq!(TodoList::read_todo_txt(&path).unwrap(), list);
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "(A) 2011-03-01 Call Mom @phone\n\
+         x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github\n\
+         Post signs around the neighborhood +GarageSale due:2011-03-05\n"
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn writing_replaces_the_file_without_leaving_a_temporary_file() {
+    let path = path("replace");
+    let list: TodoList = "Pay rent".tasks().collect();
+
+    fs::write(&path, DATA).unwrap();
+    list.write_todo_txt(&path).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Pay rent\n");
+    assert!(!path.with_extension("txt.tmp").exists());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn appended_tasks_follow_the_existing_ones() {
+    let path = path("append");
+    let tasks: Vec<Task> = "Pay rent\nFile taxes".tasks().collect();
+
+    TodoList::append_todo_txt(&path, &tasks[..1]).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Pay rent\n");
+
+    // A line break is added when the file does not end with one.
+    fs::write(&path, "Call Mom").unwrap();
+    TodoList::append_todo_txt(&path, &tasks).unwrap();
+    TodoList::append_todo_txt(&path, &[]).unwrap();
+
+    let list = TodoList::read_todo_txt(&path).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "Call Mom\nPay rent\nFile taxes\n"
+    );
+    assert_eq!(list.len(), 3);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn reading_fails_for_missing_files() {
+    let path = path("missing");
+
+    assert_eq!(
+        TodoList::read_todo_txt(&path).unwrap_err().kind(),
+        std::io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn reading_fails_for_lines_that_are_not_tasks() {
+    let path = path("not-a-task");
+    let data = "(A) Call Mom @phone\n(B\n\nPay rent @home\n";
+
+    fs::write(&path, data).unwrap();
+
+    // Writing back what was read must not drop the `(B` line.
+    let result = TodoList::read_todo_txt(&path).and_then(|list| list.write_todo_txt(&path));
+    let error = result.unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "line 2: the line is not a task");
+    assert_eq!(fs::read_to_string(&path).unwrap(), data);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn files_that_are_not_utf_8_are_decoded() {
+    let path = path("windows-1252");
+
+    fs::write(&path, b"Call \xff Mom\n(A) Buy caf\xe9 @store\n").unwrap();
+
+    let list = TodoList::read_todo_txt(&path).unwrap();
+    let lines: Vec<String> = list.iter().map(|task| task.to_string()).collect();
+
+    assert_eq!(lines, ["Call ÿ Mom", "(A) Buy café @store"]);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn line_endings_are_kept_when_writing_back() {
+    let cases: [(&str, &[u8]); 4] = [
+        ("lf", b"(A) Call Mom @phone\nx 2011-03-02 Pay rent\n"),
+        ("crlf", b"(A) Call Mom @phone\r\nx 2011-03-02 Pay rent\r\n"),
+        ("no-newline", b"(A) Call Mom @phone\nx 2011-03-02 Pay rent"),
+        (
+            "crlf-no-newline",
+            b"(A) Call Mom @phone\r\nx 2011-03-02 Pay rent",
+        ),
+    ];
+
+    for (name, bytes) in &cases {
+        let path = path(name);
+
+        fs::write(&path, bytes).unwrap();
+        TodoList::read_todo_txt(&path)
+            .unwrap()
+            .write_todo_txt(&path)
+            .unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), *bytes, "{}", name);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn mixed_line_endings_are_normalized_to_the_dominant_one() {
+    let path = path("mixed");
+
+    fs::write(&path, "Call Mom\r\nPay rent\nFile taxes\r\n").unwrap();
+
+    let list = TodoList::read_todo_txt(&path).unwrap();
+
+    assert_eq!(list.line_ending(), LineEnding::CrLf);
+    assert!(list.trailing_newline());
+
+    list.write_todo_txt(&path).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "Call Mom\r\nPay rent\r\nFile taxes\r\n"
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn write_options_override_the_line_endings_of_the_list() {
+    let path = path("options");
+
+    fs::write(&path, "Call Mom\r\nPay rent").unwrap();
+
+    let list = TodoList::read_todo_txt(&path).unwrap();
+    let options = WriteOptions::new()
+        .line_ending(LineEnding::Lf)
+        .trailing_newline(true);
+
+    list.write_todo_txt_with(&path, options).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Call Mom\nPay rent\n");
+
+    let list: TodoList = list.to_vec().into();
+
+    assert_eq!(list.line_ending(), LineEnding::Lf);
+    assert!(list.trailing_newline());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unmodified_lines_are_kept_when_requested() {
+    let path = path("unmodified");
+    let data = "(A) Call Mom @phone   \n  Pay rent\t\nFile  taxes \n(B)  Buy milk  \n";
+
+    fs::write(&path, data).unwrap();
+
+    let mut list = TodoList::read_todo_txt(&path).unwrap();
+    let options = WriteOptions::new().keep_unmodified_lines(true);
+
+    list[1] = "(C) Pay rent".tasks().next().unwrap();
+    list.write_todo_txt_with(&path, options).unwrap();
+
+    let written = fs::read_to_string(&path).unwrap();
+    let changed: Vec<(&str, &str)> = data
+        .lines()
+        .zip(written.lines())
+        .filter(|(before, after)| before != after)
+        .collect();
+
+    assert_eq!(changed, [("  Pay rent\t", "(C) Pay rent")]);
+    assert_eq!(written.lines().count(), 4);
+
+    list.write_todo_txt(&path).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "(A) Call Mom @phone\n(C) Pay rent\nFile  taxes\n(B) Buy milk\n"
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unmodified_lines_follow_their_tasks() {
+    let path = path("unmodified-sorted");
+
+    fs::write(&path, "Pay rent  \nCall Mom \nCall Mom\t\n").unwrap();
+
+    let mut list = TodoList::read_todo_txt(&path).unwrap();
+    let options = WriteOptions::new().keep_unmodified_lines(true);
+
+    list.sort_by_key(|task| task.description().to_owned());
+    list.write_todo_txt_with(&path, options).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "Call Mom \nCall Mom\t\nPay rent  \n"
+    );
+
+    fs::remove_file(&path).unwrap();
+}
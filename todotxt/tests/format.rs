@@ -0,0 +1,51 @@
+//! Tests for formatting tasks and priorities with width, fill, alignment, and
+//! precision flags.
+
+use todotxt::prelude::*;
+
+#[test]
+fn width_and_alignment_apply_to_the_whole_line() {
+    let task = "(A) 2011-03-01 Call Mom @phone".tasks().next().unwrap();
+    let width = 34;
+
+    assert_eq!(format!("{}", task), "(A) 2011-03-01 Call Mom @phone");
+    assert_eq!(
+        format!("{:<34}|", task),
+        "(A) 2011-03-01 Call Mom @phone    |"
+    );
+    assert_eq!(
+        format!("{:>width$}", task, width = width),
+        "    (A) 2011-03-01 Call Mom @phone"
+    );
+    assert_eq!(
+        format!("{:*^34}", task),
+        "**(A) 2011-03-01 Call Mom @phone**"
+    );
+    assert_eq!(format!("{:10}", task), "(A) 2011-03-01 Call Mom @phone");
+}
+
+#[test]
+fn precision_truncates_the_line() {
+    let task = "x 2011-03-02 2011-03-01 Review Tim's pull request"
+        .tasks()
+        .next()
+        .unwrap();
+
+    assert_eq!(format!("{:.20}", task), "x 2011-03-02 2011-03");
+    assert_eq!(format!("{:.0}", task), "");
+    assert_eq!(format!("{:>8.4}|", task), "    x 20|");
+
+    // Precision counts characters rather than bytes.
+    let task = "Café crème @home".tasks().next().unwrap();
+
+    assert_eq!(format!("{:.9}", task), "Café crèm");
+    assert_eq!(format!("{:-<18}", task), "Café crème @home--");
+}
+
+#[test]
+fn priorities_honor_the_flags() {
+    assert_eq!(format!("{}", Priority::C), "(C)");
+    assert_eq!(format!("{:>5}", Priority::A), "  (A)");
+    assert_eq!(format!("{:-<5}", Priority::Z), "(Z)--");
+    assert_eq!(format!("{:.2}", Priority::B), "(B");
+}
@@ -0,0 +1,32 @@
+//! Tests for grouping the tasks of a list by context.
+
+use todotxt::{prelude::*, TodoList};
+
+const DATA: &str = "
+    (A) Thank Mom for the meatballs @phone
+    Schedule Goodwill pickup +GarageSale @phone @errands
+    Post signs around the neighborhood +GarageSale
+    x 2011-03-02 Pick up milk @errands @errands
+    Call the bank @Phone
+";
+
+#[test]
+fn tasks_with_several_contexts_are_in_each_group() {
+    let list: TodoList = DATA.tasks().collect();
+    let groups = list.by_context();
+
+    assert_eq!(groups["@phone"], [0, 1]);
+    assert_eq!(groups["@errands"], [1, 3]);
+    assert_eq!(groups["@Phone"], [4]);
+    assert_eq!(groups.len(), 3);
+}
+
+#[test]
+fn tasks_without_contexts_are_not_grouped() {
+    let list: TodoList = DATA.tasks().collect();
+    let groups = list.by_context();
+
+    assert!(groups.values().all(|indices| !indices.contains(&2)));
+    assert!(!groups.contains_key(""));
+    assert!(TodoList::new().by_context().is_empty());
+}
@@ -0,0 +1,55 @@
+//! Tests for keeping the indentation of lines that express sub-tasks.
+
+use todotxt::prelude::*;
+
+const DATA: &str = "\
+Plan the garage sale +GarageSale
+  (B) Schedule Goodwill pickup
+    Call Goodwill @phone
+\tPost signs around the neighborhood
+\t\tPrint signs
+\t  x 2011-03-02 Buy tape
+  \tPrice items";
+
+#[test]
+fn indentation_is_the_leading_spaces_and_tabs() {
+    let indentation: Vec<&str> = DATA
+        .indented_tasks()
+        .map(|indented| indented.indentation())
+        .collect();
+
+    assert_eq!(
+        indentation,
+        ["", "  ", "    ", "\t", "\t\t", "\t  ", "  \t"]
+    );
+}
+
+#[test]
+fn indented_tasks_are_parsed_from_the_trimmed_line() {
+    let tasks: Vec<Task> = DATA
+        .indented_tasks()
+        .map(|indented| indented.into_task())
+        .collect();
+
+    assert_eq!(tasks, DATA.tasks().collect::<Vec<_>>());
+    assert_eq!(tasks[1].priority(), Some(Priority::B));
+    assert!(tasks[5].is_complete());
+}
+
+#[test]
+fn indented_tasks_are_formatted_with_their_indentation() {
+    let lines: Vec<String> = DATA
+        .indented_tasks()
+        .map(|indented| indented.to_string())
+        .collect();
+
+    assert_eq!(lines, DATA.lines().collect::<Vec<_>>());
+
+    let data = "\r\n  Call Mom  \r\n\n\t \x0b Pay rent\n";
+    let lines: Vec<String> = data
+        .indented_tasks()
+        .map(|indented| indented.to_string())
+        .collect();
+
+    assert_eq!(lines, ["  Call Mom", "\t Pay rent"]);
+}
@@ -0,0 +1,37 @@
+//! Guards against regressions in the size of the types that are created for
+//! every line of input.
+
+#![cfg(all(feature = "dates", target_pointer_width = "64"))]
+
+use std::{borrow::Cow, mem::size_of};
+use todotxt::{CompactTask, State, Task};
+
+/// A task is a 12 byte state and a 24 byte `Cow<str>`, padded to 40 bytes.
+///
+/// Replacing the `Cow` with a `&str` and an optional boxed override would be
+/// 32 bytes rather than 24, and an enum of `&str` and `Box<str>` is the same
+/// size as `Cow<str>` since neither variant leaves a niche for the tag. Both
+/// still branch in `description`, so the `Cow` stays.
+#[test]
+fn task_is_five_words() {
+    assert_eq!(size_of::<State>(), 12);
+    assert_eq!(size_of::<Cow<'_, str>>(), 24);
+    assert_eq!(size_of::<Task<'_>>(), 40);
+}
+
+/// A compact task is two 4 byte dates, a 16 byte `Box<str>`, and 2 bytes of
+/// flags, padded to 32 bytes. It saves a word per task, as well as the spare
+/// capacity of an owned `Cow<str>`.
+#[test]
+fn compact_task_is_four_words() {
+    assert_eq!(size_of::<CompactTask>(), 32);
+    assert_eq!(size_of::<Task<'_>>() - size_of::<CompactTask>(), 8);
+}
+
+/// A strict mode error is its kind, a line number, and a byte range, padded
+/// to 32 bytes. The context of an error is only kept with `verbose-errors`.
+#[cfg(not(feature = "verbose-errors"))]
+#[test]
+fn parse_error_is_four_words() {
+    assert_eq!(size_of::<todotxt::parser::ParseError>(), 32);
+}
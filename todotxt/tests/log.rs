@@ -0,0 +1,50 @@
+//! Tests for emitting parsed tasks to the `log` crate.
+
+#![cfg(feature = "log")]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+use todotxt::prelude::*;
+
+/// Records the target, level, and message of every log record.
+struct Recorder {
+    records: Mutex<Vec<(String, Level, String)>>,
+}
+
+static RECORDER: Recorder = Recorder {
+    records: Mutex::new(Vec::new()),
+};
+
+impl Log for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn flush(&self) {}
+
+    fn log(&self, record: &Record<'_>) {
+        self.records.lock().unwrap().push((
+            record.target().to_owned(),
+            record.level(),
+            record.args().to_string(),
+        ));
+    }
+}
+
+#[test]
+fn log_tasks_emits_each_task_once() {
+    let data = include_str!("../../fixtures/todo.txt");
+
+    log::set_logger(&RECORDER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let tasks: Vec<Task> = data.tasks().log_tasks(Level::Debug).collect();
+    let records = RECORDER.records.lock().unwrap();
+    let expected: Vec<(String, Level, String)> = tasks
+        .iter()
+        .map(|task| ("todotxt::parser".to_owned(), Level::Debug, task.to_string()))
+        .collect();
+
+    assert_eq!(tasks.len(), 10);
+    assert_eq!(*records, expected);
+}
@@ -0,0 +1,168 @@
+//! Tests for configuring a `Parser`.
+
+use std::{env, fs};
+use todotxt::{
+    parser::{ParseMode, Parser},
+    prelude::*,
+};
+
+const DATA: &str = "
+    (A) Call Mom @phone
+    x 2011-03-02 Review Tim's pull request +TodoTxtTouch @github
+
+    Water the plants h:1 rec:1w
+    x 2011-03-01 2011-03-02 Pay rent
+    Post signs\0 around the neighborhood +GarageSale
+    2024/01/01 File taxes
+";
+
+fn descriptions(parser: Parser) -> Vec<String> {
+    parser
+        .parse_str(DATA)
+        .map(|task| task.description().to_owned())
+        .collect()
+}
+
+#[test]
+fn the_default_parser_matches_input() {
+    assert_eq!(Parser::default(), Parser::new());
+    assert_eq!(
+        Parser::default().parse_str(DATA).collect::<Vec<_>>(),
+        DATA.tasks().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        descriptions(Parser::new().mode(ParseMode::Lenient)).len(),
+        6
+    );
+}
+
+#[test]
+fn complete_tasks_can_be_skipped() {
+    assert_eq!(
+        descriptions(Parser::new().skip_complete(true)),
+        [
+            "Call Mom @phone",
+            "Water the plants h:1 rec:1w",
+            "Post signs around the neighborhood +GarageSale",
+            "2024/01/01 File taxes",
+        ]
+    );
+}
+
+#[test]
+fn hidden_tasks_can_be_skipped() {
+    let tasks = descriptions(Parser::new().skip_hidden(true));
+
+    assert_eq!(tasks.len(), 5);
+    assert!(!tasks.iter().any(|task| task.contains("h:1")));
+}
+
+#[test]
+fn strict_mode_skips_rejected_lines() {
+    let parser = Parser::new().mode(ParseMode::Strict);
+
+    assert_eq!(
+        descriptions(parser),
+        [
+            "Call Mom @phone",
+            "Review Tim's pull request +TodoTxtTouch @github",
+            "Water the plants h:1 rec:1w",
+        ]
+    );
+    assert_eq!(
+        parser.parse_str(DATA).collect::<Vec<_>>(),
+        DATA.try_tasks().filter_map(Result::ok).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn options_apply_in_both_directions_and_with_progress() {
+    let parser = Parser::new()
+        .skip_complete(true)
+        .skip_hidden(true)
+        .mode(ParseMode::Strict);
+    let forward: Vec<Task> = parser.parse_str(DATA).collect();
+    let mut backward: Vec<Task> = parser.parse_str(DATA).rev().collect();
+
+    backward.reverse();
+
+    assert_eq!(forward.len(), 1);
+    assert_eq!(forward, backward);
+    assert_eq!(
+        parser.parse_str(DATA).with_progress(|_, _| {}).count(),
+        forward.len()
+    );
+    assert_eq!(parser.parse_str(DATA).flat_map_special("rec").count(), 0);
+}
+
+#[test]
+fn files_are_parsed_with_the_options() {
+    let path = env::temp_dir().join(format!("todotxt-{}-parser.txt", std::process::id()));
+
+    fs::write(&path, DATA).unwrap();
+
+    let tasks = Parser::new().skip_complete(true).parse_file(&path).unwrap();
+
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(tasks.len(), 4);
+    assert!(tasks.iter().all(|task| !task.is_complete()));
+    assert!(Parser::new().parse_file(&path).is_err());
+}
+
+#[test]
+fn malformed_lines_are_reported_when_skipped() {
+    use std::cell::{Cell, RefCell};
+
+    let calls = Cell::new(0);
+    let skipped = RefCell::new(Vec::new());
+    let parser = Parser::new().mode(ParseMode::Strict).skip_hidden(true);
+    let tasks: Vec<Task> = parser
+        .parse_str(DATA)
+        .warn_on_skip(|line| {
+            calls.set(calls.get() + 1);
+            skipped.borrow_mut().push(line.to_owned());
+        })
+        .collect();
+
+    // The hidden task and the blank line are skipped on purpose.
+    assert_eq!(tasks, parser.parse_str(DATA).collect::<Vec<_>>());
+    assert_eq!(calls.get(), 3);
+    assert_eq!(
+        *skipped.borrow(),
+        [
+            "x 2011-03-01 2011-03-02 Pay rent",
+            "Post signs\0 around the neighborhood +GarageSale",
+            "2024/01/01 File taxes",
+        ]
+    );
+}
+
+#[test]
+fn lines_that_fail_to_parse_are_reported_and_skipped() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let data = "Call Mom\n\n   \n(A\n(B\nPay rent\n";
+    let tasks: Vec<Task> = data
+        .tasks()
+        .warn_on_skip(|line| {
+            assert!(line == "(A" || line == "(B", "{:?}", line);
+            calls.set(calls.get() + 1);
+        })
+        .collect();
+
+    assert_eq!(calls.get(), 2);
+    assert_eq!(
+        tasks.iter().map(Task::description).collect::<Vec<_>>(),
+        ["Call Mom", "Pay rent"]
+    );
+
+    // Lenient parsing doesn't reject any line that parses.
+    let count = DATA
+        .tasks()
+        .warn_on_skip(|line| panic!("{:?}", line))
+        .count();
+
+    assert_eq!(count, 6);
+}
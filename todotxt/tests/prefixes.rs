@@ -0,0 +1,197 @@
+//! Tests for lines that consist of a prefix, such as `x` or `(A)`, without a
+//! description.
+
+use todotxt::{prelude::*, Date, State};
+
+#[test]
+fn prefix_only_lines_have_empty_descriptions() {
+    let data = "x\nx \n(B)\n(B) \nx 2011-03-02 2011-03-01\n(A) 2011-03-01\n2011-03-01";
+    let tasks: Vec<Task> = data.tasks().collect();
+
+    assert_eq!(tasks.len(), 7);
+    assert!(tasks.iter().all(|task| task.description().is_empty()));
+
+    assert!(tasks[0].is_complete());
+    assert!(tasks[1].is_complete());
+    assert_eq!(tasks[2].priority(), Some(Priority::B));
+    assert_eq!(tasks[3].priority(), Some(Priority::B));
+    assert!(!tasks[3].is_complete());
+    assert!(tasks[4].is_complete());
+    assert_eq!(
+        tasks[4].completion_date().unwrap().to_string(),
+        "2011-03-02"
+    );
+    assert_eq!(tasks[5].priority(), Some(Priority::A));
+    assert_eq!(tasks[6].creation_date().unwrap().to_string(), "2011-03-01");
+}
+
+#[test]
+fn prefix_only_lines_format_without_trailing_spaces() {
+    let lines: Vec<String> = "x \n(B) \nx 2011-03-02 2011-03-01 \n(A) 2011-03-01 "
+        .tasks()
+        .map(|task| task.to_string())
+        .collect();
+
+    assert_eq!(
+        lines,
+        ["x", "(B)", "x 2011-03-02 2011-03-01", "(A) 2011-03-01"]
+    );
+
+    for line in &lines {
+        let task = line.tasks().next().unwrap();
+        assert_eq!(task.to_string().tasks().next(), Some(task));
+    }
+}
+
+#[test]
+fn prefixes_must_be_followed_by_whitespace() {
+    let tasks: Vec<Task> = "xx\n(B)x\nx 2011-03-02x".tasks().collect();
+
+    assert_eq!(tasks[0].description(), "xx");
+    assert!(!tasks[0].is_complete());
+    assert_eq!(tasks[1].description(), "(B)x");
+    assert_eq!(tasks[1].priority(), None);
+    assert_eq!(tasks[2].description(), "2011-03-02x");
+    assert!(tasks[2].is_complete());
+    assert_eq!(tasks[2].completion_date(), None);
+}
+
+#[test]
+fn complete_tasks_have_zero_one_or_two_dates() {
+    let date = |day| Date::from_ymd_opt(2011, 3, day);
+    let cases = [
+        ("x Review PR", None, None),
+        ("x 2011-03-02 Review PR", date(2), None),
+        ("x 2011-03-02 2011-03-01 Review PR", date(2), date(1)),
+    ];
+
+    for (line, completion_date, creation_date) in &cases {
+        let task = line.tasks().next().unwrap();
+
+        assert_eq!(
+            task.state(),
+            State::Complete(*completion_date, *creation_date)
+        );
+        assert_eq!(task.description(), "Review PR");
+        assert_eq!(task.to_string(), *line);
+    }
+}
+
+#[test]
+fn completion_marker_is_a_lowercase_x_followed_by_whitespace() {
+    let data = "xylophone lessons\nx2020-01-01 call mom\nX 2020-01-01 foo";
+    let tasks: Vec<Task> = data.tasks().collect();
+
+    assert!(tasks.iter().all(|task| !task.is_complete()));
+    assert!(tasks.iter().all(|task| task.creation_date().is_none()));
+    assert_eq!(
+        tasks.iter().map(Task::description).collect::<Vec<_>>(),
+        data.lines().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        tasks.iter().map(Task::to_string).collect::<Vec<_>>(),
+        data.lines().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn only_lines_starting_with_x_are_complete() {
+    let date = |day| Date::from_ymd_opt(2024, 1, day);
+    let cases = [
+        (
+            "2024-01-02 2024-01-03 standup notes review",
+            State::Incomplete(None, date(2)),
+            "2024-01-03 standup notes review",
+        ),
+        (
+            "(A) 2024-01-02 2024-01-03 standup notes review",
+            State::Incomplete(Some(Priority::A), date(2)),
+            "2024-01-03 standup notes review",
+        ),
+        // The legacy form of a complete task without `x` is kept intact.
+        (
+            "2024-01-03 2024-01-02 standup notes review",
+            State::Incomplete(None, date(3)),
+            "2024-01-02 standup notes review",
+        ),
+    ];
+
+    for (line, state, description) in &cases {
+        let task = line.tasks().next().unwrap();
+
+        assert_eq!(task.state(), *state);
+        assert_eq!(task.description(), *description);
+        assert_eq!(task.to_string(), *line);
+    }
+}
+
+#[test]
+fn descriptions_start_after_the_separating_whitespace() {
+    let cases = [
+        ("(A)  two  spaces", "two  spaces", "(A) two  spaces"),
+        (
+            "(A)\ttab\tseparated",
+            "tab\tseparated",
+            "(A) tab\tseparated",
+        ),
+        (
+            "x \t2011-03-02 \t 2011-03-01\t\tmixed",
+            "mixed",
+            "x 2011-03-02 2011-03-01 mixed",
+        ),
+        (
+            "2011-03-01   Call Mom   ",
+            "Call Mom",
+            "2011-03-01 Call Mom",
+        ),
+        (
+            "Call  Mom \t@phone\t ",
+            "Call  Mom \t@phone",
+            "Call  Mom \t@phone",
+        ),
+    ];
+
+    for (line, description, formatted) in &cases {
+        let task = line.tasks().next().unwrap();
+
+        assert_eq!(task.description().as_bytes(), description.as_bytes());
+        assert_eq!(task.to_string().as_bytes(), formatted.as_bytes());
+    }
+}
+
+#[test]
+fn malformed_priorities_are_part_of_the_description() {
+    let cases = [
+        ("(a) Call Mom", "(a) Call Mom"),
+        ("(AB) Call Mom", "(AB) Call Mom"),
+        ("(A Call Mom", "(A Call Mom"),
+        ("( A) Call Mom", "( A) Call Mom"),
+        ("A) Call Mom", "A) Call Mom"),
+        ("(É) Call Mom", "(É) Call Mom"),
+        ("x (A) Call Mom", "(A) Call Mom"),
+    ];
+
+    for (line, description) in &cases {
+        let task = line.tasks().next().unwrap();
+
+        assert_eq!(task.priority(), None, "{:?}", line);
+        assert_eq!(task.description(), *description, "{:?}", line);
+    }
+}
+
+#[test]
+fn unfinished_priorities_end_parsing() {
+    // A line that ends partway through a priority can't be parsed, which ends
+    // iteration as any line that fails to parse does.
+    for data in &["(\nCall Mom", "(A\nCall Mom", "Pay rent\n(B\nCall Mom"] {
+        let tasks: Vec<String> = data.tasks().map(|task| task.to_string()).collect();
+        let expected: Vec<&str> = data
+            .lines()
+            .take_while(|line| !line.starts_with('('))
+            .collect();
+
+        assert_eq!(tasks, expected, "{:?}", data);
+    }
+
+    assert_eq!("(\0".tasks().next().unwrap().to_string(), "");
+}
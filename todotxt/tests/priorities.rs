@@ -0,0 +1,113 @@
+//! Tests for comparing priorities and shifting the priorities of a list.
+
+use todotxt::{prelude::*, TodoList};
+
+const DATA: &str = "
+    (A) Thank Mom for the meatballs @phone
+    (B) Schedule Goodwill pickup +GarageSale @phone
+    (Y) Post signs around the neighborhood +GarageSale
+    (Z) Eskimo pies @GroceryStore
+    Pay rent
+    x 2011-03-02 Review Tim's pull request +TodoTxtTouch @github
+";
+
+fn priorities(list: &TodoList) -> Vec<Option<Priority>> {
+    list.iter().map(Task::priority).collect()
+}
+
+#[test]
+fn raising_priorities_clamps_at_a() {
+    let mut list: TodoList = DATA.tasks().collect();
+
+    assert_eq!(list.rotate_priorities(-1), 3);
+    assert_eq!(
+        priorities(&list),
+        [
+            Some(Priority::A),
+            Some(Priority::A),
+            Some(Priority::X),
+            Some(Priority::Y),
+            None,
+            None,
+        ]
+    );
+
+    assert_eq!(list.rotate_priorities(-30), 2);
+    assert!(list
+        .iter()
+        .take(4)
+        .all(|t| t.priority() == Some(Priority::A)));
+}
+
+#[test]
+fn lowering_priorities_clamps_at_z() {
+    let mut list: TodoList = DATA.tasks().collect();
+
+    assert_eq!(list.rotate_priorities(1), 3);
+    assert_eq!(
+        priorities(&list),
+        [
+            Some(Priority::B),
+            Some(Priority::C),
+            Some(Priority::Z),
+            Some(Priority::Z),
+            None,
+            None,
+        ]
+    );
+    assert_eq!(
+        list[1].to_string(),
+        "(C) Schedule Goodwill pickup +GarageSale @phone"
+    );
+}
+
+#[test]
+fn rotating_by_zero_changes_nothing() {
+    let mut list: TodoList = DATA.tasks().collect();
+    let before = list.clone();
+
+    assert_eq!(list.rotate_priorities(0), 0);
+    assert_eq!(list, before);
+    assert!(list[5].is_complete());
+}
+
+#[test]
+fn optional_priorities_are_compared_with_none_last() {
+    use std::cmp::Ordering;
+
+    let ordered: Vec<Option<Priority>> = "(A) (B) (C) (D) (E) (F) (G) (H) (I) (J) (K) (L) (M) (N) (O) (P) (Q) (R) (S) (T) (U) (V) (W) (X) (Y) (Z) _"
+        .split(' ')
+        .map(|text| text.tasks().next().unwrap().priority())
+        .collect();
+
+    assert_eq!(ordered.len(), 27);
+    assert_eq!(ordered[26], None);
+
+    for (i, a) in ordered.iter().enumerate() {
+        for (j, b) in ordered.iter().enumerate() {
+            assert_eq!(Priority::cmp_opt(*a, *b), i.cmp(&j), "{:?} {:?}", a, b);
+        }
+    }
+
+    assert_eq!(Priority::cmp_opt(None, None), Ordering::Equal);
+    assert_eq!(Priority::cmp_opt(Some(Priority::Z), None), Ordering::Less);
+}
+
+#[test]
+fn sorting_with_cmp_opt_lists_the_highest_priority_first() {
+    let mut list: TodoList = DATA.tasks().collect();
+
+    list.sort_by(|a, b| Priority::cmp_opt(a.priority(), b.priority()));
+
+    assert_eq!(
+        priorities(&list),
+        [
+            Some(Priority::A),
+            Some(Priority::B),
+            Some(Priority::Y),
+            Some(Priority::Z),
+            None,
+            None,
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+//! Tests for reporting the progress of parsing an input.
+
+use std::cell::RefCell;
+use todotxt::prelude::*;
+
+fn reports(data: &str, interval: usize) -> (usize, Vec<(usize, usize)>) {
+    let reports = RefCell::new(Vec::new());
+    let count = data
+        .tasks()
+        .with_progress_interval(interval, |parsed, scanned| {
+            reports.borrow_mut().push((parsed, scanned));
+        })
+        .count();
+
+    (count, reports.into_inner())
+}
+
+#[test]
+fn progress_is_reported_at_each_interval_and_at_the_end() {
+    let data = "(A) Call Mom @phone\n".repeat(250);
+    let (count, reports) = reports(&data, 100);
+
+    assert_eq!(count, 250);
+    assert_eq!(reports, [(100, 100), (200, 200), (250, 250)]);
+}
+
+#[test]
+fn final_totals_are_not_reported_twice() {
+    let data = "Call Mom\n\n\nPay rent\n".repeat(5);
+    let (count, reports) = reports(&data, 4);
+
+    assert_eq!(count, 10);
+    assert_eq!(reports, [(2, 4), (4, 8), (6, 12), (8, 16), (10, 20)]);
+}
+
+#[test]
+fn blank_lines_are_scanned_but_not_parsed() {
+    assert_eq!(
+        reports("\n  \nCall Mom\n\t\n", 3),
+        (1, vec![(1, 3), (1, 4)])
+    );
+    assert_eq!(reports("", 100), (0, vec![(0, 0)]));
+    assert_eq!(reports("Call Mom", 0), (1, vec![(1, 1)]));
+}
+
+#[test]
+fn the_default_interval_is_one_hundred_lines() {
+    let data = "Call Mom\n".repeat(301);
+    let calls = RefCell::new(0);
+    let count = data
+        .tasks()
+        .with_progress(|_, _| *calls.borrow_mut() += 1)
+        .count();
+
+    assert_eq!(count, 301);
+    assert_eq!(calls.into_inner(), 4);
+}
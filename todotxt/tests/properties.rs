@@ -0,0 +1,766 @@
+//! Property tests for the invariants between parsing and formatting tasks.
+
+use proptest::prelude::*;
+use todotxt::{parser::interleave_sorted, prelude::*, CompactTask, Date, State, TaskBuilder};
+
+mod strategies {
+    use super::*;
+
+    pub fn priority() -> impl Strategy<Value = Priority> {
+        use Priority::*;
+
+        prop::sample::select(vec![
+            A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+        ])
+    }
+
+    pub fn date() -> impl Strategy<Value = Date> {
+        (1000i32..=9999, 1u32..=12, 1u32..=31)
+            .prop_filter_map("invalid date", |(y, m, d)| Date::from_ymd_opt(y, m, d))
+    }
+
+    pub fn tag() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "@[a-zA-Z0-9]{1,8}",
+            r"\+[a-zA-Z0-9.]{1,8}",
+            "[a-z]{1,5}:[a-z0-9-]{1,10}",
+        ]
+    }
+
+    /// A description that can't be mistaken for a task prefix.
+    pub fn description() -> impl Strategy<Value = String> {
+        let word = prop_oneof!["[a-zA-Z0-9'.,!?]{1,8}", tag()];
+
+        (
+            "[a-wyzA-Z][a-zA-Z0-9]{0,8}",
+            prop::collection::vec(word, 0..6),
+        )
+            .prop_map(|(first, rest)| {
+                let mut words = vec![first];
+
+                words.extend(rest);
+                words.join(" ")
+            })
+    }
+
+    /// A description made of arbitrary unicode words.
+    pub fn unicode_description() -> impl Strategy<Value = String> {
+        prop::collection::vec(r"[^\s]{1,8}", 1..8).prop_map(|words| words.join(" "))
+    }
+
+    /// A state that upholds the invariants of the specification.
+    pub fn state() -> impl Strategy<Value = State> {
+        let complete = prop_oneof![
+            Just(State::Complete(None, None)),
+            date().prop_map(|date| State::Complete(Some(date), None)),
+            (date(), date()).prop_map(|(a, b)| State::Complete(Some(a.max(b)), Some(a.min(b)))),
+        ];
+        let incomplete = (prop::option::of(priority()), prop::option::of(date()))
+            .prop_map(|(priority, date)| State::Incomplete(priority, date));
+
+        prop_oneof![complete, incomplete]
+    }
+
+    /// Formats a state and description as a line of a todo.txt file.
+    pub fn format(state: State, description: &str) -> String {
+        let prefix = match state {
+            State::Complete(completion, creation) => {
+                let completion = completion.map(|d| format!("{} ", d)).unwrap_or_default();
+                let creation = creation.map(|d| format!("{} ", d)).unwrap_or_default();
+
+                format!("x {}{}", completion, creation)
+            }
+            State::Incomplete(priority, date) => {
+                let priority = priority.map(|p| format!("{} ", p)).unwrap_or_default();
+                let date = date.map(|d| format!("{} ", d)).unwrap_or_default();
+
+                priority + &date
+            }
+        };
+
+        prefix + description
+    }
+
+    /// Input that is hard to split into lines, with blank lines, carriage
+    /// returns, and ASCII and unicode whitespace at the edges of lines.
+    pub fn raw_input() -> impl Strategy<Value = String> {
+        let space = prop_oneof![
+            Just(""),
+            Just(" "),
+            Just("\t"),
+            Just("\r"),
+            Just("\u{b}"),
+            Just("\u{c}"),
+            Just("\u{a0}"),
+            Just("\u{3000}"),
+        ];
+        let line = (
+            space.clone(),
+            prop_oneof![Just(String::new()), line()],
+            space,
+        );
+
+        prop::collection::vec(line, 0..16).prop_map(|lines| {
+            lines
+                .into_iter()
+                .map(|(before, line, after)| format!("{}{}{}", before, line, after))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    /// A word that may appear where the parser looks for a completion marker,
+    /// priority, or date, including near misses with non-ASCII characters.
+    pub fn prefix_field() -> impl Strategy<Value = String> {
+        prop_oneof![
+            Just("x".to_owned()),
+            priority().prop_map(|p| p.to_string()),
+            date().prop_map(|d| d.to_string()),
+            date().prop_map(|d| format!("{}\u{301}", d)),
+            date().prop_map(|d| format!("{}😀", d)),
+            date().prop_map(|d| d.to_string().replace('0', "０")),
+            priority().prop_map(|p| format!("{}\u{301}", p)),
+            r"[０-９]{4}-[０-９]{2}-[０-９]{2}",
+            r"\(\PC\)",
+            r"\PC{1,10}",
+        ]
+        .prop_filter("prefix fields are single words", |field| {
+            !field.is_empty() && !field.contains(char::is_whitespace)
+        })
+    }
+
+    /// A line that may or may not be a well-formed task.
+    pub fn line() -> impl Strategy<Value = String> {
+        let prefix = prop_oneof![
+            Just(String::new()),
+            Just("x ".to_owned()),
+            priority().prop_map(|p| format!("{} ", p)),
+            date().prop_map(|d| format!("{} ", d)),
+        ];
+
+        (prop::collection::vec(prefix, 0..4), description())
+            .prop_map(|(prefixes, description)| prefixes.concat() + &description)
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn parse_format_is_identity(state in strategies::state(), description in strategies::description()) {
+        let line = strategies::format(state, &description);
+        let task = line.tasks().next().expect("line does not contain a task");
+
+        prop_assert_eq!(task.state(), state);
+        prop_assert_eq!(task.description(), description.as_str());
+        prop_assert_eq!(task.to_string(), line);
+    }
+
+    #[test]
+    fn format_parse_is_idempotent(line in strategies::line()) {
+        let once = line.tasks().next().expect("line does not contain a task").to_string();
+        let twice = once.tasks().next().expect("line does not contain a task").to_string();
+
+        prop_assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn non_ascii_prefixes_are_left_in_the_description(
+        fields in prop::collection::vec(strategies::prefix_field(), 1..=3),
+    ) {
+        let line = fields.join(" ") + " foo";
+        let task = line.tasks().next().expect("line does not contain a task");
+
+        prop_assert!(line.ends_with(task.description()));
+        prop_assert!(task.description().ends_with("foo"));
+
+        let non_ascii = |text: &str| text.chars().filter(|c| !c.is_ascii()).count();
+
+        prop_assert_eq!(non_ascii(task.description()), non_ascii(&line));
+
+        let once = task.to_string();
+        let twice = once.tasks().next().expect("line does not contain a task").to_string();
+
+        prop_assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn compact_tasks_round_trip(state in strategies::state(), description in strategies::description()) {
+        let line = strategies::format(state, &description);
+        let task = line.tasks().next().expect("line does not contain a task");
+        let compact = task.to_compact_repr();
+
+        prop_assert_eq!(compact.description(), task.description());
+        prop_assert_eq!(Task::from(compact), task);
+    }
+
+    #[test]
+    fn tags_lie_on_char_boundaries(description in strategies::unicode_description()) {
+        for task in description.tasks() {
+            let description = task.description();
+
+            for tag in task.tags() {
+                prop_assert!(tag.start() < tag.end());
+                prop_assert!(tag.end() <= description.len());
+                prop_assert!(description.is_char_boundary(tag.start()));
+                prop_assert!(description.is_char_boundary(tag.end()));
+            }
+        }
+    }
+
+    #[test]
+    fn tags_match_reference_implementation(
+        description in prop_oneof![strategies::description(), strategies::unicode_description()],
+    ) {
+        for task in description.tasks() {
+            let tags: Vec<Tag> = task.tags().collect();
+
+            prop_assert_eq!(tags, reference_tags(task.description()));
+        }
+    }
+
+    #[test]
+    fn skip_to_a_tag_yields_the_tags_from_it(description in strategies::unicode_description()) {
+        for task in description.tasks() {
+            let tags: Vec<Tag> = task.tags().collect();
+
+            for (index, tag) in tags.iter().enumerate() {
+                let rest: Vec<Tag> = task.tags().skip_to(tag.start()).collect();
+
+                prop_assert_eq!(&rest[..], &tags[index..]);
+            }
+        }
+    }
+
+    #[test]
+    fn tag_set_queries_match_each_tag(
+        words in prop::collection::vec("[@+][a-c]{1,2}|call", 1..12),
+        query in prop::collection::vec("[@+][a-c]{1,2}", 0..100),
+    ) {
+        let description = words.join(" ");
+        let task = description.tasks().next().unwrap();
+        let query: Vec<&str> = query.iter().map(String::as_str).collect();
+        let contexts: Vec<&str> = task.contexts_iter().collect();
+        let projects: Vec<&str> = task.projects_iter().collect();
+
+        prop_assert_eq!(
+            task.has_all_contexts(&query),
+            query.iter().all(|value| contexts.contains(value))
+        );
+        prop_assert_eq!(
+            task.has_all_projects(&query),
+            query.iter().all(|value| projects.contains(value))
+        );
+        prop_assert_eq!(
+            task.has_any_context(&query),
+            query.iter().any(|value| contexts.contains(value))
+        );
+    }
+
+    #[test]
+    fn with_and_without_special_partition_the_tasks(
+        lines in prop::collection::vec(strategies::line(), 0..16),
+        key in "[a-z]{1,2}",
+    ) {
+        let input = lines.join("\n");
+        let with: Vec<Task> = input.tasks().with_special(&key).collect();
+        let without: Vec<Task> = input.tasks().without_special(&key).collect();
+
+        prop_assert_eq!(with.len() + without.len(), input.tasks().count());
+        prop_assert!(with.iter().all(|task| task.get_special(&key).is_some()));
+        prop_assert!(without.iter().all(|task| task.get_special(&key).is_none()));
+    }
+
+    #[test]
+    fn skip_first_n_complete_skips_only_complete_tasks(
+        lines in prop::collection::vec(strategies::line(), 0..16),
+        n in 0..8usize,
+    ) {
+        let input = lines.join("\n");
+        let tasks: Vec<Task> = input.tasks().skip_first_n_complete(n).collect();
+        let (complete, incomplete): (Vec<Task>, Vec<Task>) =
+            input.tasks().partition(Task::is_complete);
+
+        prop_assert!(incomplete.iter().all(|task| tasks.contains(task)));
+        prop_assert_eq!(tasks.len(), input.tasks().count() - n.min(complete.len()));
+        prop_assert_eq!(
+            tasks.iter().filter(|task| task.is_complete()).collect::<Vec<_>>(),
+            complete.iter().skip(n).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn skip_before_date_matches_filter_on_sorted_input(
+        mut tasks in prop::collection::vec((strategies::date(), strategies::description()), 0..16),
+        date in strategies::date(),
+    ) {
+        tasks.sort_by_key(|(created, _)| *created);
+
+        let input: Vec<String> = tasks
+            .iter()
+            .map(|(created, description)| format!("{} {}", created, description))
+            .collect();
+        let input = input.join("\n");
+        let skipped: Vec<Task> = input.tasks().skip_before_date(date).collect();
+        let filtered: Vec<Task> = input
+            .tasks()
+            .filter(|task| task.creation_date().is_some_and(|created| created >= date))
+            .collect();
+
+        prop_assert_eq!(skipped, filtered);
+    }
+
+    #[test]
+    fn take_until_date_and_skip_before_date_split_sorted_input(
+        mut tasks in prop::collection::vec((strategies::date(), strategies::description()), 0..16),
+        date in strategies::date(),
+    ) {
+        tasks.sort_by_key(|(created, _)| *created);
+
+        let input: Vec<String> = tasks
+            .iter()
+            .map(|(created, description)| format!("{} {}", created, description))
+            .collect();
+        let input = input.join("\n");
+        let taken: Vec<Task> = input.tasks().take_until_date(date).collect();
+        let filtered: Vec<Task> = input
+            .tasks()
+            .filter(|task| task.creation_date().is_some_and(|created| created < date))
+            .collect();
+        let skipped: Vec<Task> = input.tasks().skip_before_date(date).collect();
+
+        prop_assert_eq!(&taken, &filtered);
+        prop_assert_eq!(
+            taken.into_iter().chain(skipped).collect::<Vec<_>>(),
+            input.tasks().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn enumerate_complete_numbers_complete_tasks_in_order(
+        lines in prop::collection::vec(strategies::line(), 0..16),
+    ) {
+        let input = lines.join("\n");
+        let numbered: Vec<(usize, Task)> = input.tasks().enumerate_complete().collect();
+        let complete: Vec<Task> = input.tasks().filter(Task::is_complete).collect();
+
+        prop_assert!(numbered.iter().enumerate().all(|(expected, (index, _))| *index == expected));
+        prop_assert_eq!(numbered.into_iter().map(|(_, task)| task).collect::<Vec<_>>(), complete);
+    }
+
+    #[test]
+    fn interleaving_sorted_lists_sorts_them(
+        mut a in prop::collection::vec(strategies::line(), 0..12),
+        mut b in prop::collection::vec(strategies::line(), 0..12),
+    ) {
+        let priority = |line: &String| line.tasks().next().and_then(|task| task.priority());
+
+        a.sort_by_key(|line| std::cmp::Reverse(priority(line)));
+        b.sort_by_key(|line| std::cmp::Reverse(priority(line)));
+
+        let (a, b) = (a.join("\n"), b.join("\n"));
+        let merged: Vec<Task> =
+            interleave_sorted(a.tasks(), b.tasks(), priority_first).collect();
+        let mut expected: Vec<Task> = a.tasks().chain(b.tasks()).collect();
+
+        expected.sort_by(priority_first);
+        prop_assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn batches_contain_every_task_in_order(
+        lines in prop::collection::vec(strategies::line(), 0..24),
+        chunk_size in 1usize..8,
+    ) {
+        let input = lines.join("\n");
+        let batches: Vec<Vec<Task>> = input.tasks().batch_parse(chunk_size).collect();
+        let count = input.tasks().count();
+
+        prop_assert_eq!(batches.len(), count.div_ceil(chunk_size));
+
+        if let Some((last, rest)) = batches.split_last() {
+            prop_assert!(rest.iter().all(|batch| batch.len() == chunk_size));
+            prop_assert!(!last.is_empty() && last.len() <= chunk_size);
+        }
+
+        prop_assert_eq!(batches.concat(), input.tasks().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn flat_map_special_yields_each_matching_tag(
+        lines in prop::collection::vec(strategies::line(), 0..16),
+        key in "[a-z]{1,2}",
+    ) {
+        let input = lines.join("\n");
+        let pairs: Vec<(&str, Task)> = input.tasks().flat_map_special(&key).collect();
+        let mut expected = Vec::new();
+
+        for task in input.tasks() {
+            for special in task.specials_iter() {
+                let value = special
+                    .strip_prefix(key.as_str())
+                    .and_then(|rest| rest.strip_prefix(':'));
+
+                if let Some(value) = value {
+                    expected.push((value.to_owned(), task.clone()));
+                }
+            }
+        }
+
+        let actual: Vec<(String, Task)> = pairs
+            .into_iter()
+            .map(|(value, task)| (value.to_owned(), task))
+            .collect();
+
+        prop_assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn description_hashes_ignore_state(
+        states in (strategies::state(), strategies::state()),
+        descriptions in (strategies::description(), strategies::description()),
+    ) {
+        let a = strategies::format(states.0, &descriptions.0);
+        let b = strategies::format(states.1, &descriptions.0);
+        let c = strategies::format(states.1, &descriptions.1);
+        let hash = |line: &str| line.tasks().next().unwrap().description_hash();
+
+        prop_assert_eq!(hash(&a), hash(&b));
+        prop_assert_eq!(hash(&a) == hash(&c), descriptions.0 == descriptions.1);
+    }
+
+    #[test]
+    fn byte_lengths_match_the_formatted_task(line in strategies::line()) {
+        let task = line.tasks().next().unwrap();
+
+        prop_assert_eq!(task.description_bytes().len(), task.description().len());
+        prop_assert_eq!(task.description_bytes(), task.description().as_bytes());
+        prop_assert_eq!(task.total_bytes(), task.to_string().len());
+    }
+
+    #[test]
+    fn trimmed_tasks_have_the_tags_of_their_words(
+        state in strategies::state(),
+        words in prop::collection::vec((strategies::tag(), "[ \t]{1,3}"), 1..8),
+    ) {
+        let spaced: String = words.iter().map(|(word, space)| format!("{}{}", word, space)).collect();
+        let line = strategies::format(state, &format!("Call {}", spaced));
+        let task = line.tasks().next().unwrap();
+        let trimmed = task.with_text_trimmed();
+        let tags: Vec<&str> = trimmed.tags().map(|tag| &trimmed.description()[tag]).collect();
+
+        prop_assert_eq!(trimmed.state(), task.state());
+        prop_assert_eq!(tags, words.iter().map(|(word, _)| word.as_str()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn forward_and_backward_iteration_agree(lines in prop::collection::vec(strategies::line(), 0..16)) {
+        let input = lines.join("\n");
+        let forward: Vec<Task> = input.tasks().collect();
+        let mut backward: Vec<Task> = input.tasks().rev().collect();
+
+        backward.reverse();
+        prop_assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn dates_are_only_parsed_from_their_canonical_form(prefix in "[0-9+é -]{0,12}") {
+        let line = format!("{} write a parser", prefix);
+        let task = line.tasks().next().expect("line does not contain a task");
+
+        if let Some(date) = task.creation_date() {
+            let canonical = format!("{} ", date);
+
+            prop_assert!(line.starts_with(&canonical));
+        }
+    }
+
+    #[test]
+    fn last_task_matches_last(input in strategies::raw_input()) {
+        prop_assert_eq!(input.tasks().last_task(), input.tasks().collect::<Vec<_>>().pop());
+    }
+
+    #[test]
+    fn line_scanning_matches_str_lines(input in strategies::raw_input()) {
+        prop_assert_eq!(input.tasks().collect::<Vec<_>>(), tasks_from_str_lines(&input));
+
+        let mut backward: Vec<Task> = input.tasks().rev().collect();
+
+        backward.reverse();
+        prop_assert_eq!(backward, tasks_from_str_lines(&input));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn chunked_parallel_parsing_preserves_order(
+        input in strategies::raw_input(),
+        chunk_size in 1usize..128,
+    ) {
+        let chunked: Vec<Task> = input.par_tasks_chunked(chunk_size).collect();
+
+        prop_assert_eq!(chunked, input.tasks().collect::<Vec<_>>());
+    }
+}
+
+/// Finds the tags in `description` the way `Tags` did before it classified
+/// each word while scanning for the end of the word.
+fn reference_tags(description: &str) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    let mut word_start = None;
+
+    for (index, item) in description
+        .char_indices()
+        .chain(Some((description.len(), ' ')))
+    {
+        match (word_start, item.is_whitespace()) {
+            (None, false) => word_start = Some(index),
+            (Some(start), true) => {
+                let (word, end) = (&description[start..index], index);
+
+                if word.len() < 2 {
+                    // A lone `@`, `+`, or `:` is not a tag.
+                } else if word.starts_with('@') {
+                    tags.push(Tag::Context { start, end });
+                } else if word.starts_with('+') {
+                    tags.push(Tag::Project { start, end });
+                } else if word.contains(':') {
+                    tags.push(Tag::Special { start, end });
+                }
+
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    tags
+}
+
+fn priority_first(a: &Task, b: &Task) -> std::cmp::Ordering {
+    Priority::cmp_opt(a.priority(), b.priority())
+}
+
+#[test]
+fn interleaving_keeps_the_order_of_equal_tasks() {
+    let a = "(A) Call Mom\n(B) Pay rent\n(B) File taxes\nWater the plants";
+    let b = "(B) Schedule Goodwill pickup\nPost signs";
+    let merged: Vec<String> = interleave_sorted(a.tasks(), b.tasks(), priority_first)
+        .map(|task| task.description().to_owned())
+        .collect();
+
+    assert_eq!(
+        merged,
+        [
+            "Call Mom",
+            "Pay rent",
+            "File taxes",
+            "Schedule Goodwill pickup",
+            "Water the plants",
+            "Post signs",
+        ]
+    );
+
+    let one: Vec<Task> = interleave_sorted(a.tasks(), "".tasks(), priority_first).collect();
+    let other: Vec<Task> = interleave_sorted("".tasks(), a.tasks(), priority_first).collect();
+
+    assert_eq!(one, a.tasks().collect::<Vec<_>>());
+    assert_eq!(other, one);
+    assert_eq!(
+        interleave_sorted("".tasks(), "".tasks(), priority_first).count(),
+        0
+    );
+}
+
+#[test]
+fn the_last_batch_has_the_remaining_tasks() {
+    let data = "Call Mom\nPay rent\n\nFile taxes\nx Water the plants\nPost signs\nBuy milk\nWalk";
+    let sizes: Vec<usize> = data
+        .tasks()
+        .batch_parse(3)
+        .map(|batch| batch.len())
+        .collect();
+
+    assert_eq!(sizes, [3, 3, 1]);
+    assert_eq!(data.tasks().batch_parse(7).count(), 1);
+    assert_eq!("".tasks().batch_parse(3).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be greater than 0")]
+fn batches_can_not_be_empty() {
+    let _ = "Call Mom".tasks().batch_parse(0);
+}
+
+#[test]
+fn skip_before_date_stops_at_the_first_later_task() {
+    let data = "
+        Call Mom
+        2011-03-01 Schedule Goodwill pickup
+        2011-03-03 Post signs around the neighborhood
+        2011-03-02 Pay rent
+        Water the plants
+    ";
+    let date = Date::from_ymd_opt(2011, 3, 2).unwrap();
+    let tasks: Vec<String> = data
+        .tasks()
+        .skip_before_date(date)
+        .map(|task| task.to_string())
+        .collect();
+
+    // The tasks after the first one created on or after the date are not
+    // checked, so unsorted input yields tasks that `filter` would skip.
+    assert_eq!(
+        tasks,
+        [
+            "2011-03-03 Post signs around the neighborhood",
+            "2011-03-02 Pay rent",
+            "Water the plants",
+        ]
+    );
+    assert_eq!(
+        data.tasks()
+            .skip_before_date(date)
+            .skip_before_date(date)
+            .count(),
+        3
+    );
+    assert_eq!(
+        "2011-03-01 Call Mom".tasks().skip_before_date(date).count(),
+        0
+    );
+}
+
+#[test]
+fn take_until_date_stops_at_the_first_later_task() {
+    let data = "
+        Call Mom
+        2011-03-01 Schedule Goodwill pickup
+        2011-03-03 Post signs around the neighborhood
+        2011-03-01 Pay rent
+    ";
+    let date = Date::from_ymd_opt(2011, 3, 2).unwrap();
+    let tasks: Vec<String> = data
+        .tasks()
+        .take_until_date(date)
+        .map(|task| task.to_string())
+        .collect();
+
+    assert_eq!(tasks, ["Call Mom", "2011-03-01 Schedule Goodwill pickup"]);
+    assert_eq!(
+        "2011-03-02 Call Mom".tasks().take_until_date(date).count(),
+        0
+    );
+}
+
+#[test]
+fn tags_match_reference_implementation_on_fixture() {
+    for task in include_str!("../../fixtures/todo.txt").tasks() {
+        assert_eq!(
+            task.tags().collect::<Vec<_>>(),
+            reference_tags(task.description())
+        );
+    }
+}
+
+/// Parses `input` the way the sequential iterator did before it scanned for
+/// line breaks itself.
+fn tasks_from_str_lines(input: &str) -> Vec<Task<'_>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map_while(|line| line.tasks().next())
+        .collect()
+}
+
+#[test]
+fn line_scanning_matches_str_lines_on_fixture() {
+    let data = include_str!("../../fixtures/todo.txt");
+
+    assert_eq!(data.tasks().collect::<Vec<_>>(), tasks_from_str_lines(data));
+}
+
+/// Minimal failing input found by `tags_lie_on_char_boundaries` when the end of
+/// a word was computed as the start of its last character plus one byte.
+#[test]
+fn tags_end_after_multibyte_characters() {
+    let data = "&— write about the café +blog—post @home—office";
+    let task = data.tasks().next().unwrap();
+    let description = task.description();
+    let tags: Vec<&str> = task.tags().map(|tag| &description[tag]).collect();
+
+    assert_eq!(tags, ["+blog—post", "@home—office"]);
+}
+
+/// Lines with non-ASCII characters where a completion marker, priority, or
+/// date is expected. The parser must reject the prefix without slicing inside
+/// a character and keep the text in the description.
+#[test]
+fn non_ascii_prefixes_are_rejected_cleanly() {
+    let cases = [
+        (
+            "２０２４-01-01 fullwidth digits",
+            None,
+            "２０２４-01-01 fullwidth digits",
+        ),
+        (
+            "2024-01-0１ fullwidth digit",
+            None,
+            "2024-01-0１ fullwidth digit",
+        ),
+        ("(A) åäö… 2011-03-01", None, "åäö… 2011-03-01"),
+        ("(Å) Call Mom", None, "(Å) Call Mom"),
+        ("(A)\u{301} Call Mom", None, "(A)\u{301} Call Mom"),
+        ("x\u{301} Call Mom", None, "x\u{301} Call Mom"),
+        (
+            "2011-03-01\u{301} Call Mom",
+            None,
+            "2011-03-01\u{301} Call Mom",
+        ),
+        ("(B) 2011-03-01😀 Call Mom", None, "2011-03-01😀 Call Mom"),
+        ("😀 2011-03-01 Call Mom", None, "😀 2011-03-01 Call Mom"),
+        (
+            "x 2011-03-02 2011-03-0😀 Call Mom",
+            Date::from_ymd_opt(2011, 3, 2),
+            "2011-03-0😀 Call Mom",
+        ),
+    ];
+
+    for (line, date, description) in &cases {
+        let task = line.tasks().next().unwrap();
+        let parsed = if task.is_complete() {
+            task.completion_date()
+        } else {
+            task.creation_date()
+        };
+
+        assert_eq!(parsed, *date, "{}", line);
+        assert_eq!(task.description(), *description, "{}", line);
+    }
+}
+
+#[test]
+fn compact_tasks_round_trip_at_the_edges_of_the_date_range() {
+    let dates = if cfg!(feature = "dates") {
+        vec![
+            (-262_143, 1, 1),
+            (262_142, 12, 31),
+            (1970, 1, 1),
+            (1969, 12, 31),
+        ]
+    } else {
+        vec![(0, 1, 1), (9999, 12, 31), (1970, 1, 1), (2000, 2, 29)]
+    };
+
+    for (year, month, day) in dates {
+        let date = Date::from_ymd_opt(year, month, day);
+        let mut task = TaskBuilder::new("Call Mom")
+            .created_on(date.unwrap())
+            .build();
+
+        assert_eq!(Task::from(CompactTask::from(task.clone())), task);
+        task.complete_on(date.unwrap());
+        assert_eq!(Task::from(task.to_compact_repr()), task);
+        assert_eq!(task.completion_date(), date);
+    }
+}
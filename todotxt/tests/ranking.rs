@@ -0,0 +1,58 @@
+//! Tests for ranking tasks by a custom score.
+
+use todotxt::{prelude::*, TodoList};
+
+const DATA: &str = "
+    (C) Post signs around the neighborhood +GarageSale
+    (A) 2011-01-01 Thank Mom for the meatballs @phone due:2011-03-01
+    Call Mom due:2011-03-01
+    (A) Schedule Goodwill pickup +GarageSale due:2011-03-31
+    (B) 2010-01-01 File taxes
+    x 2011-03-02 (A) Review Tim's pull request due:2011-02-01
+";
+
+#[test]
+fn ranked_tasks_are_sorted_by_descending_score() {
+    let list: TodoList = DATA.tasks().collect();
+    let ranked = list.ranked(|task| task.description().len() as i64);
+
+    assert_eq!(ranked.len(), list.len());
+    assert!(ranked.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+    assert!(ranked
+        .iter()
+        .all(|(score, task)| *score == task.score(|task| task.description().len() as i64)));
+
+    // Ties keep the order of the list.
+    let ranked = list.ranked(|_| 0);
+
+    assert!(ranked.iter().zip(list.iter()).all(|((_, a), b)| *a == b));
+}
+
+#[cfg(feature = "dates")]
+#[test]
+fn default_scorer_ranks_overdue_high_priority_tasks_first() {
+    use todotxt::{chrono::NaiveDate, default_scorer, time};
+
+    time::set_test_date(NaiveDate::from_ymd_opt(2011, 3, 15).unwrap());
+
+    let list: TodoList = DATA.tasks().collect();
+    let ranked: Vec<(i64, &str)> = list
+        .ranked(default_scorer)
+        .into_iter()
+        .map(|(score, task)| (score, task.description()))
+        .collect();
+
+    time::clear_test_date();
+
+    assert_eq!(
+        ranked,
+        [
+            (136, "Thank Mom for the meatballs @phone due:2011-03-01"),
+            (100, "Call Mom due:2011-03-01"),
+            (50, "File taxes"),
+            (26, "Schedule Goodwill pickup +GarageSale due:2011-03-31"),
+            (24, "Post signs around the neighborhood +GarageSale"),
+            (0, "(A) Review Tim's pull request due:2011-02-01"),
+        ]
+    );
+}
@@ -0,0 +1,38 @@
+//! Tests for reading tasks a line at a time from a `BufRead`, as standard input
+//! is read.
+
+use std::io::{self, Cursor};
+use todotxt::{parser::ReadIter, prelude::*};
+
+#[test]
+fn tasks_are_read_a_line_at_a_time() {
+    let data = "(A) Call Mom @phone\r\n\n   \nx 2011-03-02 Pay rent\nFile taxes";
+    let tasks: Vec<Task<'static>> = ReadIter::new(Cursor::new(data.as_bytes()))
+        .collect::<io::Result<_>>()
+        .unwrap();
+
+    assert_eq!(tasks, data.tasks().collect::<Vec<_>>());
+}
+
+#[test]
+fn the_reader_is_only_read_as_far_as_needed() {
+    let data = "Call Mom\nPay rent\nFile taxes\n";
+    let mut iter = ReadIter::new(Cursor::new(data.as_bytes()));
+
+    assert_eq!(iter.next().unwrap().unwrap().description(), "Call Mom");
+    assert_eq!(iter.into_inner().position(), 9);
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let data: &[u8] = b"Call Mom\nPay \xff rent\nFile taxes\n";
+    let results: Vec<_> = ReadIter::new(Cursor::new(data)).collect();
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert_eq!(
+        results[1].as_ref().unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(results[2].as_ref().unwrap().description(), "File taxes");
+}
@@ -0,0 +1,197 @@
+//! Tests for parsing and applying `rec:` special tags.
+
+use todotxt::{parser::ParseSpecial, Recurrence, RecurrenceUnit};
+
+#[test]
+fn recurrences_are_parsed_from_rec_tags() {
+    let cases = [
+        ("1d", Some(Recurrence::new(1, RecurrenceUnit::Day, false))),
+        ("+2w", Some(Recurrence::new(2, RecurrenceUnit::Week, true))),
+        (
+            "12m",
+            Some(Recurrence::new(12, RecurrenceUnit::Month, false)),
+        ),
+        ("+1y", Some(Recurrence::new(1, RecurrenceUnit::Year, true))),
+        ("0d", None),
+        ("d", None),
+        ("+d", None),
+        ("++1d", None),
+        ("-1d", None),
+        ("1", None),
+        ("1x", None),
+        ("1D", None),
+        ("1dé", None),
+        ("", None),
+    ];
+
+    for (value, expected) in &cases {
+        let recurrence = Recurrence::parse_special(value);
+
+        assert_eq!(recurrence, *expected, "{}", value);
+
+        if let Some(recurrence) = recurrence {
+            assert_eq!(recurrence.to_string(), *value);
+        }
+    }
+}
+
+#[cfg(feature = "dates")]
+mod next_occurrence {
+    use todotxt::{Date, Recurrence, RecurrenceUnit};
+
+    fn date(value: &str) -> Date {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn strict_recurrences_advance_from_the_due_date() {
+        use RecurrenceUnit::*;
+
+        // (amount, unit, due date, completion date, strict, non-strict)
+        let cases = [
+            (
+                3,
+                Day,
+                "2011-03-01",
+                "2011-03-04",
+                "2011-03-04",
+                "2011-03-07",
+            ),
+            (
+                1,
+                Week,
+                "2011-03-01",
+                "2011-03-04",
+                "2011-03-08",
+                "2011-03-11",
+            ),
+            (
+                2,
+                Week,
+                "2011-03-01",
+                "2011-02-27",
+                "2011-03-15",
+                "2011-03-13",
+            ),
+            (
+                1,
+                Month,
+                "2011-03-01",
+                "2011-03-04",
+                "2011-04-01",
+                "2011-04-04",
+            ),
+            (
+                1,
+                Month,
+                "2011-01-31",
+                "2011-01-30",
+                "2011-02-28",
+                "2011-02-28",
+            ),
+            (
+                1,
+                Month,
+                "2012-01-31",
+                "2012-02-02",
+                "2012-02-29",
+                "2012-03-02",
+            ),
+            (
+                1,
+                Year,
+                "2011-03-01",
+                "2011-03-04",
+                "2012-03-01",
+                "2012-03-04",
+            ),
+            (
+                1,
+                Year,
+                "2012-02-29",
+                "2012-03-01",
+                "2013-02-28",
+                "2013-03-01",
+            ),
+        ];
+
+        for (amount, unit, due, completed, strict, relaxed) in &cases {
+            let due = Some(date(due));
+            let completed = date(completed);
+            let message = format!("{}{:?} due {:?}", amount, unit, due);
+
+            assert_eq!(
+                Recurrence::new(*amount, *unit, true).next_occurrence(due, completed),
+                Some(date(strict)),
+                "strict {}",
+                message
+            );
+            assert_eq!(
+                Recurrence::new(*amount, *unit, false).next_occurrence(due, completed),
+                Some(date(relaxed)),
+                "non-strict {}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn strict_recurrences_without_a_due_date_advance_from_the_completion_date() {
+        let completed = date("2011-03-04");
+
+        for unit in &[
+            RecurrenceUnit::Day,
+            RecurrenceUnit::Week,
+            RecurrenceUnit::Month,
+            RecurrenceUnit::Year,
+        ] {
+            assert_eq!(
+                Recurrence::new(1, *unit, true).next_occurrence(None, completed),
+                Recurrence::new(1, *unit, false).next_occurrence(None, completed),
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_occurrences_are_none() {
+        let recurrence = Recurrence::new(u32::MAX, RecurrenceUnit::Year, false);
+
+        assert_eq!(recurrence.next_occurrence(None, date("2011-03-04")), None);
+    }
+}
+
+#[test]
+fn templates_are_instantiated_without_template_tags() {
+    use todotxt::{prelude::*, Date, TodoList};
+
+    let data = "
+        Water the plants h:1 rec:1w @home
+        (A) Thank Mom for the meatballs @phone
+        Call Mom rec:1w
+        Check the smoke alarms h:1
+        (B) h:1 Pay rent rec:+1m   due:2011-03-01
+        Plan the garage sale h:1 rec:monthly +GarageSale
+        x Water the lawn h:1 rec:2d
+    ";
+    let list: TodoList = data.tasks().collect();
+    let date = Date::from_ymd_opt(2011, 3, 1).unwrap();
+    let tasks: Vec<String> = list
+        .instantiate_templates(date)
+        .iter()
+        .map(Task::to_string)
+        .collect();
+
+    assert_eq!(list.templates().count(), 3);
+    assert_eq!(
+        tasks,
+        [
+            "2011-03-01 Water the plants @home",
+            "(B) 2011-03-01 Pay rent due:2011-03-01",
+            "2011-03-01 Water the lawn",
+        ]
+    );
+    assert!(list
+        .instantiate_templates(date)
+        .iter()
+        .all(|task| !task.is_template() && !task.is_complete()));
+}
@@ -0,0 +1,124 @@
+//! Golden tests for rendering tasks as a plain-text table.
+
+use todotxt::{
+    prelude::*,
+    report::{render_ls, render_table, Column, LsOptions},
+    Date,
+};
+
+static DATA: &str = include_str!("../../fixtures/todo.txt");
+
+fn numbered(data: &str) -> Vec<(usize, Task<'_>)> {
+    data.lines()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index + 1, line.tasks().next()?)))
+        .collect()
+}
+
+fn columns() -> [Column; 6] {
+    [
+        Column::Line,
+        Column::Priority,
+        Column::DueDate,
+        Column::Age(Date::from_ymd_opt(2011, 3, 10).unwrap()),
+        Column::Projects,
+        Column::Description,
+    ]
+}
+
+#[test]
+fn fixture_renders_at_eighty_columns() {
+    let expected = " #  Pri  Due         Age  Projects       Description
+ 1  (A)                                  Thank Mom for the meatballs @phone
+ 2  (B)                   +GarageSale    Schedule Goodwill pickup +GarageSale @…
+ 3                        +GarageSale    Post signs around the neighborhood +Ga…
+ 4                                       @GroceryStore Eskimo pies
+ 5                    8d  +TodoTxt       Document +TodoTxt task format
+ 6  (A)               8d                 Call Mom
+ 7  (A)                                  Call Mom 2011-03-02
+ 8                                       Call Mom
+ 9                    9d  +TodoTxtTouch  Review Tim's pull request +TodoTxtTouc…
+10       2010-01-02       +GarageSale    Post signs around the neighborhood +Ga…
+";
+    let table = render_table(&numbered(DATA), &columns(), 80);
+
+    assert_eq!(table, expected);
+    assert!(table.lines().all(|line| line.chars().count() <= 80));
+}
+
+#[test]
+fn fixture_renders_at_sixty_columns() {
+    let expected = " #  Pri  Due         Age  Projects       Description
+ 1  (A)                                  Thank Mom for the…
+ 2  (B)                   +GarageSale    Schedule Goodwill…
+ 3                        +GarageSale    Post signs around…
+ 4                                       @GroceryStore Eski…
+ 5                    8d  +TodoTxt       Document +TodoTxt…
+ 6  (A)               8d                 Call Mom
+ 7  (A)                                  Call Mom 2011-03-02
+ 8                                       Call Mom
+ 9                    9d  +TodoTxtTouch  Review Tim's pull…
+10       2010-01-02       +GarageSale    Post signs around…
+";
+
+    assert_eq!(render_table(&numbered(DATA), &columns(), 60), expected);
+}
+
+#[test]
+fn wide_characters_take_two_columns() {
+    let tasks = numbered("(A) 買い物リストを作る @home\nCall Mom");
+    let table = render_table(&tasks, &[Column::Priority, Column::Description], 16);
+
+    assert_eq!(table, "Pri  Description\n(A)  買い物リス…\n     Call Mom\n");
+}
+
+#[test]
+fn fixture_is_listed_like_todo_sh() {
+    let expected = "\
+06 (A) 2011-03-02 Call Mom
+07 (A) Call Mom 2011-03-02
+01 (A) Thank Mom for the meatballs @phone
+02 (B) Schedule Goodwill pickup +GarageSale @phone
+05 2011-03-02 Document +TodoTxt task format
+04 @GroceryStore Eskimo pies
+03 Post signs around the neighborhood +GarageSale
+10 Post signs around the neighborhood +GarageSale due:2010-01-02
+09 x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+08 x 2011-03-03 Call Mom
+--
+TODO: 10 of 10 tasks shown
+";
+
+    assert_eq!(render_ls(DATA, None, LsOptions::new()), expected);
+}
+
+#[test]
+fn padding_depends_on_the_lines_of_the_file() {
+    let garage_sale = |task: &Task| task.projects_iter().any(|project| project == "+GarageSale");
+    let expected = "\
+02 (B) Schedule Goodwill pickup +GarageSale @phone
+03 Post signs around the neighborhood +GarageSale
+10 Post signs around the neighborhood +GarageSale due:2010-01-02
+--
+TODO: 3 of 10 tasks shown
+";
+
+    assert_eq!(
+        render_ls(DATA, Some(&garage_sale), LsOptions::new()),
+        expected
+    );
+}
+
+#[test]
+fn blank_lines_are_numbered_but_not_counted() {
+    let data = "call Mom\n\n  \nBuy milk\n\n\n\n\n\n\nPay rent\n";
+
+    assert_eq!(
+        render_ls(data, None, LsOptions::new().label("DONE")),
+        "04 Buy milk\n01 call Mom\n11 Pay rent\n--\nDONE: 3 of 3 tasks shown\n"
+    );
+    assert_eq!(
+        render_ls(data, None, LsOptions::new().sort(false).summary(false)),
+        "01 call Mom\n04 Buy milk\n11 Pay rent\n"
+    );
+}
@@ -0,0 +1,192 @@
+//! Tests for the serialized representation of the types in this crate.
+
+#![cfg(feature = "serde")]
+
+use todotxt::{prelude::*, Date, State, TodoList};
+
+fn date(y: i32, m: u32, d: u32) -> Date {
+    Date::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn state_round_trips_through_json() {
+    let cases = [
+        (State::Complete(None, None), r#"{"type":"COMPLETE"}"#),
+        (
+            State::Complete(Some(date(2011, 3, 2)), None),
+            r#"{"completion_date":"2011-03-02","type":"COMPLETE"}"#,
+        ),
+        (
+            State::Complete(Some(date(2011, 3, 2)), Some(date(2011, 3, 1))),
+            r#"{"completion_date":"2011-03-02","creation_date":"2011-03-01","type":"COMPLETE"}"#,
+        ),
+        (State::Incomplete(None, None), r#"{"type":"INCOMPLETE"}"#),
+        (
+            State::Incomplete(Some(Priority::A), None),
+            r#"{"priority":"A","type":"INCOMPLETE"}"#,
+        ),
+        (
+            State::Incomplete(None, Some(date(2011, 3, 1))),
+            r#"{"creation_date":"2011-03-01","type":"INCOMPLETE"}"#,
+        ),
+        (
+            State::Incomplete(Some(Priority::B), Some(date(2011, 3, 1))),
+            r#"{"creation_date":"2011-03-01","priority":"B","type":"INCOMPLETE"}"#,
+        ),
+    ];
+
+    for (state, json) in &cases {
+        assert_eq!(serde_json::to_string(state).unwrap(), *json);
+        assert_eq!(serde_json::from_str::<State>(json).unwrap(), *state);
+    }
+}
+
+#[test]
+fn state_rejects_invalid_json() {
+    let cases = [
+        r#"{"priority":"A","type":"COMPLETE"}"#,
+        r#"{"creation_date":"2011-03-01","type":"COMPLETE"}"#,
+        r#"{"completion_date":"2011-03-02","type":"INCOMPLETE"}"#,
+        r#"{"type":"UNKNOWN"}"#,
+    ];
+
+    for json in &cases {
+        assert!(serde_json::from_str::<State>(json).is_err(), "{}", json);
+    }
+}
+
+#[test]
+fn tasks_round_trip_through_json() {
+    let data = include_str!("../../fixtures/todo.txt");
+    let tasks: Vec<Task> = data.tasks().collect();
+    let json = serde_json::to_string(&tasks).unwrap();
+
+    assert_eq!(serde_json::from_str::<Vec<Task>>(&json).unwrap(), tasks);
+
+    for task in &tasks {
+        let json = serde_json::to_string(&task.serialize_camel()).unwrap();
+
+        assert_eq!(serde_json::from_str::<Task>(&json).unwrap(), *task);
+    }
+}
+
+#[test]
+fn from_json_is_the_inverse_of_to_json() {
+    for task in include_str!("../../fixtures/todo.txt").tasks() {
+        assert_eq!(Task::from_json(&task.to_json().unwrap()).unwrap(), task);
+        assert_eq!(
+            Task::from_json(&task.to_json_pretty().unwrap()).unwrap(),
+            task
+        );
+    }
+}
+
+#[test]
+fn lists_round_trip_through_json_arrays() {
+    let list: TodoList = include_str!("../../fixtures/todo.txt").tasks().collect();
+    let json = list.to_json_array().unwrap();
+
+    assert_eq!(json, serde_json::to_string(&*list).unwrap());
+    assert_eq!(TodoList::from_json_array(&json).unwrap(), list);
+    assert_eq!(TodoList::from_json_array("[]").unwrap(), TodoList::new());
+}
+
+#[test]
+fn tasks_round_trip_through_bincode() {
+    let data = include_str!("../../fixtures/todo.txt");
+    let tasks: Vec<Task> = data.tasks().collect();
+    let bytes = bincode::serialize(&tasks).unwrap();
+
+    assert_eq!(bincode::deserialize::<Vec<Task>>(&bytes).unwrap(), tasks);
+}
+
+#[derive(Debug, serde::Deserialize, PartialEq, serde::Serialize)]
+struct NumericRow {
+    #[serde(with = "todotxt::priority::serde_numeric")]
+    priority: Priority,
+    #[serde(default, with = "todotxt::priority::serde_numeric::option")]
+    optional: Option<Priority>,
+}
+
+fn all_priorities() -> Vec<Priority> {
+    "(A) (B) (C) (D) (E) (F) (G) (H) (I) (J) (K) (L) (M) (N) (O) (P) (Q) (R) (S) (T) (U) (V) (W) (X) (Y) (Z)"
+        .split(' ')
+        .map(|text| text.tasks().next().unwrap().priority().unwrap())
+        .collect()
+}
+
+#[test]
+fn numeric_priorities_round_trip_through_json() {
+    for (index, priority) in all_priorities().into_iter().enumerate() {
+        let row = NumericRow {
+            priority,
+            optional: Some(priority),
+        };
+        let json = serde_json::to_string(&row).unwrap();
+        let number = index + 1;
+
+        assert_eq!(
+            json,
+            format!(r#"{{"priority":{},"optional":{}}}"#, number, number)
+        );
+        assert_eq!(serde_json::from_str::<NumericRow>(&json).unwrap(), row);
+    }
+
+    let row = NumericRow {
+        priority: Priority::A,
+        optional: None,
+    };
+    let json = serde_json::to_string(&row).unwrap();
+
+    assert_eq!(json, r#"{"priority":1,"optional":null}"#);
+    assert_eq!(serde_json::from_str::<NumericRow>(&json).unwrap(), row);
+    assert_eq!(
+        serde_json::from_str::<NumericRow>(r#"{"priority":1}"#).unwrap(),
+        row
+    );
+}
+
+#[test]
+fn numeric_priorities_must_be_from_1_to_26() {
+    for json in &[
+        r#"{"priority":0}"#,
+        r#"{"priority":27}"#,
+        r#"{"priority":256}"#,
+        r#"{"priority":-1}"#,
+        r#"{"priority":"A"}"#,
+        r#"{"priority":1,"optional":0}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<NumericRow>(json).is_err(),
+            "{}",
+            json
+        );
+    }
+
+    let error = serde_json::from_str::<NumericRow>(r#"{"priority":27}"#).unwrap_err();
+
+    assert!(error.to_string().contains("an integer from 1 to 26"));
+}
+
+#[test]
+fn numeric_and_letter_priorities_sort_the_same_way() {
+    let mut by_letter = all_priorities();
+    let mut by_number = by_letter.clone();
+
+    by_letter.reverse();
+    by_number.reverse();
+    by_letter.sort_by_key(|priority| serde_json::to_string(priority).unwrap());
+    by_number.sort_by_key(|&priority| {
+        let row = NumericRow {
+            priority,
+            optional: None,
+        };
+        let json = serde_json::to_value(row).unwrap();
+
+        json["priority"].as_u64().unwrap()
+    });
+
+    assert_eq!(by_letter, by_number);
+    assert_eq!(by_number[0], Priority::A);
+    assert!(by_number.windows(2).all(|pair| pair[0] > pair[1]));
+}
@@ -0,0 +1,145 @@
+//! Tests for sorting tasks by one or more keys.
+
+use todotxt::{prelude::*, SortKey, TodoList, ValueKind};
+
+fn sorted(data: &str, keys: &[SortKey]) -> Vec<String> {
+    let mut list: TodoList = data.tasks().collect();
+
+    list.sort_by_keys(keys);
+    list.iter().map(|task| task.to_string()).collect()
+}
+
+#[test]
+fn integer_tag_values_sort_numerically_with_missing_values_last() {
+    let data = "
+        Call Mom order:10
+        Pay rent
+        File taxes order:2
+        Water the plants order:soon
+        Buy milk order:-1
+    ";
+    let keys = [SortKey::TagValue {
+        key: "order",
+        parse: ValueKind::Integer,
+    }];
+
+    assert_eq!(
+        sorted(data, &keys),
+        [
+            "Buy milk order:-1",
+            "File taxes order:2",
+            "Call Mom order:10",
+            "Pay rent",
+            "Water the plants order:soon",
+        ]
+    );
+}
+
+#[test]
+fn due_dates_sort_from_the_earliest_with_missing_dates_last() {
+    let data = "
+        Call Mom due:2011-03-05
+        Pay rent due:tomorrow
+        File taxes due:2011-03-01T17:00
+        Water the plants
+        Buy milk due:2011-03-02
+    ";
+    let by_due_date = sorted(data, &[SortKey::DueDate]);
+
+    assert_eq!(
+        by_due_date,
+        [
+            "File taxes due:2011-03-01T17:00",
+            "Buy milk due:2011-03-02",
+            "Call Mom due:2011-03-05",
+            "Pay rent due:tomorrow",
+            "Water the plants",
+        ]
+    );
+
+    // Parsed as a plain date, a due time makes the value unparseable.
+    let keys = [SortKey::TagValue {
+        key: "due",
+        parse: ValueKind::Date,
+    }];
+
+    assert_eq!(
+        sorted(data, &keys),
+        [
+            "Buy milk due:2011-03-02",
+            "Call Mom due:2011-03-05",
+            "Pay rent due:tomorrow",
+            "File taxes due:2011-03-01T17:00",
+            "Water the plants",
+        ]
+    );
+}
+
+#[test]
+fn text_tag_values_sort_byte_by_byte() {
+    let data = "
+        Call Mom area:home
+        Pay rent
+        File taxes area:finance
+        Water the plants area:Garden
+    ";
+    let keys = [SortKey::TagValue {
+        key: "area",
+        parse: ValueKind::Text,
+    }];
+
+    assert_eq!(
+        sorted(data, &keys),
+        [
+            "Water the plants area:Garden",
+            "File taxes area:finance",
+            "Call Mom area:home",
+            "Pay rent",
+        ]
+    );
+}
+
+#[test]
+fn ties_are_broken_by_the_next_key() {
+    let data = "
+        (B) Pay rent order:1
+        Water the plants
+        (A) Call Mom order:2
+        File taxes order:1 due:2011-03-02
+        (C) Buy milk order:1 due:2011-03-01
+        Post signs
+    ";
+    let keys = [
+        SortKey::TagValue {
+            key: "order",
+            parse: ValueKind::Integer,
+        },
+        SortKey::DueDate,
+        SortKey::Priority,
+    ];
+
+    assert_eq!(
+        sorted(data, &keys),
+        [
+            "(C) Buy milk order:1 due:2011-03-01",
+            "File taxes order:1 due:2011-03-02",
+            "(B) Pay rent order:1",
+            "(A) Call Mom order:2",
+            "Water the plants",
+            "Post signs",
+        ]
+    );
+
+    // Tasks that are equal by every key keep their order.
+    assert_eq!(sorted(data, &[]), sorted(data, &[SortKey::CreationDate]));
+}
+
+#[test]
+fn compare_all_stops_at_the_first_unequal_key() {
+    let tasks: Vec<Task> = "(A) Call Mom\n(B) Buy milk".tasks().collect();
+    let (a, b) = (&tasks[0], &tasks[1]);
+
+    assert!(SortKey::compare_all(&[SortKey::Priority, SortKey::Description], a, b).is_lt());
+    assert!(SortKey::compare_all(&[SortKey::Description, SortKey::Priority], a, b).is_gt());
+    assert!(SortKey::compare_all(&[SortKey::CreationDate], a, b).is_eq());
+}
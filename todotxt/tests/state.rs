@@ -0,0 +1,125 @@
+//! Tests for constructing states and reading their parts.
+
+use todotxt::{prelude::*, Date, InvalidState, State};
+
+fn date(day: u32) -> Option<Date> {
+    Date::from_ymd_opt(2011, 3, day)
+}
+
+#[test]
+fn complete_states_require_ordered_dates() {
+    let cases = [
+        (None, None, Ok(State::Complete(None, None))),
+        (date(2), None, Ok(State::Complete(date(2), None))),
+        (date(2), date(1), Ok(State::Complete(date(2), date(1)))),
+        (date(1), date(1), Ok(State::Complete(date(1), date(1)))),
+        (
+            date(1),
+            date(2),
+            Err(InvalidState::CompletionBeforeCreation),
+        ),
+        (None, date(1), Err(InvalidState::CreationWithoutCompletion)),
+    ];
+
+    for (completion_date, creation_date, expected) in &cases {
+        assert_eq!(
+            State::complete(*completion_date, *creation_date),
+            *expected,
+            "{:?} {:?}",
+            completion_date,
+            creation_date
+        );
+    }
+}
+
+#[test]
+fn incomplete_states_accept_any_combination() {
+    for priority in &[None, Some(Priority::A), Some(Priority::Z)] {
+        for creation_date in &[None, date(1)] {
+            let state = State::incomplete(*priority, *creation_date);
+
+            assert_eq!(state, State::Incomplete(*priority, *creation_date));
+            assert_eq!(state.priority(), *priority);
+            assert_eq!(state.creation_date(), *creation_date);
+            assert_eq!(state.completion_date(), None);
+            assert!(!state.is_complete());
+        }
+    }
+}
+
+#[test]
+fn complete_states_have_no_priority() {
+    for (completion_date, creation_date) in &[(None, None), (date(2), None), (date(2), date(1))] {
+        let state = State::complete(*completion_date, *creation_date).unwrap();
+
+        assert_eq!(state.priority(), None);
+        assert_eq!(state.completion_date(), *completion_date);
+        assert_eq!(state.creation_date(), *creation_date);
+        assert!(state.is_complete());
+    }
+}
+
+#[test]
+fn task_accessors_match_their_state() {
+    let data = "
+        (A) 2011-03-01 Call Mom
+        (B) Pay rent
+        2011-03-01 File taxes
+        x 2011-03-02 2011-03-01 Review Tim's pull request
+        x 2011-03-02 Water the plants
+        x Buy milk
+    ";
+
+    for task in data.tasks() {
+        let state = task.state();
+
+        assert_eq!(task.priority(), state.priority());
+        assert_eq!(task.completion_date(), state.completion_date());
+        assert_eq!(task.creation_date(), state.creation_date());
+        assert_eq!(task.is_complete(), state.is_complete());
+    }
+}
+
+#[test]
+fn invalid_states_describe_the_problem() {
+    assert_eq!(
+        InvalidState::CompletionBeforeCreation.to_string(),
+        "the completion date is before the creation date"
+    );
+    assert_eq!(
+        InvalidState::CreationWithoutCompletion.to_string(),
+        "a complete task can't have a creation date without a completion date"
+    );
+}
+
+#[test]
+fn missing_dates_fall_back_to_defaults() {
+    let default = date(31).unwrap();
+    let dated = "2011-03-01 Call Mom due:2011-03-05".tasks().next().unwrap();
+    let undated = "Call Mom due:soon".tasks().next().unwrap();
+
+    assert_eq!(dated.creation_date_or(default), date(1).unwrap());
+    assert_eq!(dated.due_date_or(default), date(5).unwrap());
+    assert_eq!(undated.creation_date_or(default), default);
+    assert_eq!(undated.due_date_or(default), default);
+
+    assert_eq!(
+        dated.creation_date_or_else(|| unreachable!("the task has a creation date")),
+        date(1).unwrap()
+    );
+    assert_eq!(undated.creation_date_or_else(|| default), default);
+}
+
+#[test]
+fn tag_dates_must_be_padded_with_or_without_chrono() {
+    let padded = "Pay rent due:2011-03-01 t:2011-03-01"
+        .tasks()
+        .next()
+        .unwrap();
+    let unpadded = "Pay rent due:2011-3-1 t:2011-3-1".tasks().next().unwrap();
+
+    assert_eq!(padded.due_date(), date(1));
+    assert_eq!(padded.threshold_date(), date(1));
+    assert_eq!(unpadded.due_date(), None);
+    assert_eq!(unpadded.threshold_date(), None);
+}
@@ -0,0 +1,218 @@
+//! Tests for computing statistics from a stream of tasks.
+
+#![cfg(feature = "dates")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Write,
+    io::Cursor,
+};
+use todotxt::{chrono::Duration, prelude::*, time, Date, TaskStats};
+
+/// Tracks the peak number of bytes allocated by the current thread so that
+/// tests running in parallel don't affect each other.
+struct Peak;
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+#[global_allocator]
+static ALLOCATOR: Peak = Peak;
+
+unsafe impl GlobalAlloc for Peak {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.with(|current| {
+            current.set(current.get() + layout.size());
+            current.get()
+        });
+
+        PEAK.with(|peak| peak.set(peak.get().max(current)));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // Memory may be freed by a different thread than allocated it.
+        CURRENT.with(|current| current.set(current.get().saturating_sub(layout.size())));
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Returns the peak number of bytes allocated by `f` at any one time.
+fn peak_allocated<F: FnOnce()>(f: F) -> usize {
+    let start = CURRENT.with(Cell::get);
+
+    PEAK.with(|peak| peak.set(start));
+    f();
+    PEAK.with(Cell::get) - start
+}
+
+/// Generates `count` lines that cycle through the same handful of tags.
+fn generate(count: usize) -> String {
+    let mut data = String::new();
+
+    for index in 0..count {
+        let _ = writeln!(
+            data,
+            "({}) 2011-03-01 Call Mom about item {} @phone{} +Project{} due:2011-03-{:02}",
+            (b'A' + (index % 26) as u8) as char,
+            index,
+            index % 3,
+            index % 5,
+            index % 28 + 1,
+        );
+    }
+
+    data
+}
+
+fn today() -> Date {
+    Date::from_ymd_opt(2011, 3, 10).unwrap()
+}
+
+#[test]
+fn from_reader_matches_from_tasks() {
+    let data = include_str!("../../fixtures/todo.txt");
+    let tasks: Vec<Task> = data.tasks().collect();
+    let stats = TaskStats::from_reader(Cursor::new(data), today()).unwrap();
+
+    assert_eq!(stats, TaskStats::from_tasks(&tasks, today()));
+
+    let data = generate(1000);
+    let tasks: Vec<Task> = data.tasks().collect();
+    let stats = TaskStats::from_reader(Cursor::new(&data), today()).unwrap();
+
+    assert_eq!(stats, TaskStats::from_tasks(&tasks, today()));
+
+    // Both stop at a line that fails to parse.
+    let data = "(A) Call Mom @phone\n\n(B\nPay rent @home\n";
+    let tasks: Vec<Task> = data.tasks().collect();
+    let stats = TaskStats::from_reader(Cursor::new(data), today()).unwrap();
+
+    assert_eq!(stats, TaskStats::from_tasks(&tasks, today()));
+    assert_eq!(stats.total(), 1);
+}
+
+#[test]
+fn reduce_to_stats_matches_from_tasks() {
+    let data = generate(1000);
+    let tasks: Vec<Task> = data.tasks().collect();
+
+    time::set_test_date(today());
+    assert_eq!(
+        data.tasks().reduce_to_stats(),
+        TaskStats::from_tasks(&tasks, today())
+    );
+    time::clear_test_date();
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_reduce_to_stats_matches_reduce_to_stats() {
+    let fixture = include_str!("../../fixtures/todo.txt");
+    let data = format!("{}{}", fixture, generate(10_000));
+
+    // The test date is only set on this thread, so this also checks that the
+    // date is not read from rayon's threads.
+    time::set_test_date(today());
+
+    let stats = data.par_tasks().par_reduce_to_stats();
+
+    assert_eq!(stats, data.tasks().reduce_to_stats());
+
+    // Both stop at a line that fails to parse.
+    let data = format!("{}(B\n{}", generate(5_000), generate(5_000));
+    let stats = data.par_tasks().par_reduce_to_stats();
+
+    assert_eq!(stats, data.tasks().reduce_to_stats());
+    assert_eq!(
+        "(A) Call Mom @phone\n(B\nPay rent @home\n"
+            .par_tasks()
+            .par_reduce_to_stats()
+            .total(),
+        1
+    );
+    time::clear_test_date();
+}
+
+#[test]
+fn from_reader_uses_bounded_memory() {
+    let small = generate(1_000);
+    let large = generate(100_000);
+
+    let small_peak = peak_allocated(|| {
+        TaskStats::from_reader(Cursor::new(&small), today()).unwrap();
+    });
+    let large_peak = peak_allocated(|| {
+        TaskStats::from_reader(Cursor::new(&large), today()).unwrap();
+    });
+
+    // The input is 100 times larger, but only the line buffer may grow since
+    // the lines of the larger input are a few bytes longer.
+    assert!(
+        large_peak <= small_peak + 256,
+        "{} > {}",
+        large_peak,
+        small_peak
+    );
+}
+
+#[test]
+fn from_reader_rejects_invalid_utf8() {
+    let data: &[u8] = b"(A) Call Mom\n\xff\n";
+
+    assert!(TaskStats::from_reader(data, today()).is_err());
+}
+
+#[test]
+fn durations_are_parsed_from_special_tags() {
+    let cases = [
+        ("est:2h", Some(Duration::minutes(120))),
+        ("est:90m", Some(Duration::minutes(90))),
+        ("est:90", Some(Duration::minutes(90))),
+        ("est:1d", Some(Duration::hours(24))),
+        ("est:2w", Some(Duration::days(14))),
+        ("est:0h", Some(Duration::zero())),
+        ("est:abc", None),
+        ("est:h", None),
+        ("est:1.5h", None),
+        ("est:-1h", None),
+        ("est:1H", None),
+        ("est:99999999999999999w", None),
+    ];
+
+    for (tag, expected) in &cases {
+        let data = format!("Write the report {}", tag);
+        let task = data.tasks().next().unwrap();
+
+        assert_eq!(task.duration_tag("est"), *expected, "{}", tag);
+    }
+}
+
+#[test]
+fn durations_are_summed_across_tasks() {
+    let data = "
+        (A) Thank Mom for the meatballs @phone est:15m
+        (B) Schedule Goodwill pickup +GarageSale @phone est:2h
+        Post signs around the neighborhood +GarageSale est:1d
+        @GroceryStore Eskimo pies est:abc
+        x Buy milk
+    ";
+    let tasks: Vec<Task> = data.tasks().collect();
+    let sum = TaskStats::sum_durations(&tasks, "est");
+
+    assert_eq!(sum.total(), Duration::minutes(15 + 120 + 24 * 60));
+    assert_eq!(sum.count(), 3);
+    assert_eq!(sum.malformed(), 1);
+
+    let garage_sale = tasks
+        .iter()
+        .filter(|task| task.has_all_projects(&["+GarageSale"]));
+
+    assert_eq!(
+        TaskStats::sum_durations(garage_sale, "est").total(),
+        Duration::hours(26)
+    );
+}
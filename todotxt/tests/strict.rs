@@ -0,0 +1,133 @@
+//! Tests for the reasons that strict mode gives for words that are almost
+//! dates.
+
+use todotxt::{parser::ParseErrorKind, prelude::*};
+
+#[test]
+fn malformed_dates_have_specific_kinds_and_spans() {
+    let cases = [
+        (
+            "2024/01/01 Call Mom",
+            ParseErrorKind::InvalidDateSeparator,
+            0..10,
+        ),
+        (
+            "(A) 2024.01.01 Call Mom",
+            ParseErrorKind::InvalidDateSeparator,
+            4..14,
+        ),
+        ("2024-1-1 Call Mom", ParseErrorKind::UnpaddedDate, 0..8),
+        ("x 2024-01-5 Call Mom", ParseErrorKind::UnpaddedDate, 2..11),
+        ("2024-13-01 Call Mom", ParseErrorKind::InvalidMonth, 0..10),
+        ("2023-02-29 Call Mom", ParseErrorKind::InvalidDay, 0..10),
+        ("24-01-01 Call Mom", ParseErrorKind::TwoDigitYear, 0..8),
+        (
+            "x 2024-01-02 24/1/1 Call Mom",
+            ParseErrorKind::InvalidDateSeparator,
+            13..19,
+        ),
+        (
+            "  2024-00-01\tCall Mom",
+            ParseErrorKind::InvalidMonth,
+            2..12,
+        ),
+    ];
+
+    for (line, kind, span) in &cases {
+        let error = line.try_tasks().next().unwrap().unwrap_err();
+
+        assert_eq!(error.kind(), *kind, "{}", line);
+        assert_eq!(error.span(), *span, "{}", line);
+        assert_eq!(line.tasks().next().unwrap().validate(), [*kind]);
+    }
+}
+
+#[test]
+fn words_that_are_not_almost_dates_are_accepted() {
+    let data = "\
+2024-01-01 Call Mom
+x 2024-01-02 2024-01-01 Call Mom
+2024-01-01 2024/01/01 is a date in the description
+1/2/3 is not a date
+3/4 cup of flour
+2024-01-01-02 is not a date
+10000-01-01 is not a date
+2024—01—01 is not a date either
+Call Mom on 2024/01/01";
+
+    for result in data.try_tasks() {
+        assert!(result.is_ok(), "{:?}", result);
+    }
+}
+
+#[cfg(feature = "verbose-errors")]
+#[test]
+fn errors_have_the_context_they_occurred_in() {
+    let cases: [(&str, &[&str]); 8] = [
+        (
+            "2024/01/01 Call Mom",
+            &[
+                "while parsing creation date",
+                "while parsing date separator",
+            ],
+        ),
+        (
+            "(A) 2024-13-01 Call Mom",
+            &["while parsing creation date", "while parsing month"],
+        ),
+        (
+            "x 2024-01-5 Call Mom",
+            &["while parsing completion date", "while parsing day"],
+        ),
+        (
+            "x 2024-01-02 2023-2-29 Call Mom",
+            &["while parsing creation date", "while parsing month"],
+        ),
+        (
+            "24-01-01 Call Mom",
+            &["while parsing creation date", "while parsing year"],
+        ),
+        (
+            "2023-02-29 Call Mom",
+            &["while parsing creation date", "while parsing day"],
+        ),
+        (
+            "x 2020-01-01 2023-05-05 Pay rent",
+            &["while parsing completion date"],
+        ),
+        ("Pay\0 rent", &["while parsing line"]),
+    ];
+
+    for (line, context) in &cases {
+        let error = line.try_tasks().next().unwrap().unwrap_err();
+
+        assert_eq!(error.context(), *context, "{}", line);
+    }
+
+    let error = "Call Mom\n2024-13-01 Pay rent"
+        .try_tasks()
+        .nth(1)
+        .unwrap()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "line 2: the month of a date must be from 01 to 12 \
+         (while parsing creation date, while parsing month)"
+    );
+}
+
+#[test]
+fn lines_that_fail_to_parse_are_errors() {
+    let data = "(A\nCall Mom\n";
+    let results: Vec<_> = data.try_tasks().collect();
+
+    assert_eq!(results.len(), 2);
+
+    let error = results[0].as_ref().unwrap_err();
+
+    assert_eq!(error.kind(), ParseErrorKind::Unparseable);
+    assert_eq!(error.line(), 1);
+    assert_eq!(&data[error.span()], "(A");
+    assert_eq!(results[1].as_ref().unwrap().description(), "Call Mom");
+}
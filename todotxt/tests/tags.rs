@@ -0,0 +1,201 @@
+//! Tests for querying the tags of a task through a `Tags` iterator.
+
+use todotxt::prelude::*;
+
+const DATA: &str = "Call Mom @phone +Family rec:1w @home +Chores due:2011-03-01";
+
+#[test]
+fn any_and_all_stop_at_the_first_decisive_tag() {
+    let task = DATA.tasks().next().unwrap();
+    let mut calls = 0;
+    let mut tags = task.tags();
+
+    assert!(tags.any(|tag| {
+        calls += 1;
+        matches!(tag, Tag::Project { .. })
+    }));
+    assert_eq!(calls, 2);
+    assert_eq!(tags.count(), 4);
+
+    calls = 0;
+    tags = task.tags();
+
+    assert!(!tags.all(|tag| {
+        calls += 1;
+        matches!(tag, Tag::Context { .. })
+    }));
+    assert_eq!(calls, 2);
+    assert_eq!(tags.count(), 4);
+}
+
+#[test]
+fn any_context_and_any_project_stop_at_the_first_match() {
+    let task = DATA.tasks().next().unwrap();
+    let mut tags = task.tags();
+
+    assert!(tags.any_context("@home"));
+    assert_eq!(tags.count(), 2);
+
+    let mut tags = task.tags();
+
+    assert!(tags.any_project("+Family"));
+    assert_eq!(tags.count(), 4);
+
+    let mut tags = task.tags();
+
+    assert!(!tags.any_project("@phone"));
+    assert_eq!(tags.count(), 0);
+}
+
+#[test]
+fn any_context_matches_has_any_context() {
+    let task = DATA.tasks().next().unwrap();
+
+    for value in &["@phone", "@home", "@work", "+Family", "@", "phone", "@phon"] {
+        assert_eq!(
+            task.tags().any_context(value),
+            task.has_any_context(&[value]),
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn skip_to_resumes_at_a_byte_offset() {
+    let task = DATA.tasks().next().unwrap();
+    let description = task.description();
+    let all: Vec<Tag> = task.tags().collect();
+
+    for offset in (0..=description.len()).filter(|&i| description.is_char_boundary(i)) {
+        let at_word_start = description[..offset]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+
+        if at_word_start {
+            let tags: Vec<Tag> = task.tags().skip_to(offset).collect();
+
+            assert!(all.ends_with(&tags), "{}", offset);
+            assert!(tags.iter().all(|tag| tag.start() >= offset));
+        }
+    }
+
+    let mut tags = task.tags();
+
+    tags.next();
+    assert_eq!(tags.skip_to(0).count(), all.len() - 1);
+}
+
+#[test]
+#[should_panic(expected = "char boundary")]
+fn skip_to_panics_inside_a_char() {
+    let task = "Café @home".tasks().next().unwrap();
+
+    task.tags().skip_to(4);
+}
+
+#[test]
+fn tags_are_found_after_trimming_whitespace() {
+    let task = "(B) Call  Mom \t@phone   +Family  rec:1w\t\t@home"
+        .tasks()
+        .next()
+        .unwrap();
+    let trimmed = task.with_text_trimmed();
+    let description = trimmed.description();
+    let tags: Vec<&str> = trimmed.tags().map(|tag| &description[tag]).collect();
+
+    assert_eq!(description, "Call Mom @phone +Family rec:1w @home");
+    assert_eq!(tags, ["@phone", "+Family", "rec:1w", "@home"]);
+    assert_eq!(trimmed.get_special("rec"), Some("1w"));
+    assert_eq!(trimmed.priority(), Some(Priority::B));
+
+    // A description that is already trimmed is still borrowed from the input.
+    let data = "Call Mom @phone";
+    let task = data.tasks().next().unwrap().with_text_trimmed();
+
+    assert_eq!(task.description().as_ptr(), data.as_ptr());
+}
+
+#[test]
+fn tags_are_collected_with_their_values() {
+    let task = DATA.tasks().next().unwrap();
+    let description = task.description();
+    let tags = task.tags().into_vec_with_values(description);
+
+    assert_eq!(tags.len(), task.tags().count_fast());
+    assert_eq!(
+        tags.iter().map(|(_, value)| *value).collect::<Vec<_>>(),
+        [
+            "@phone",
+            "+Family",
+            "rec:1w",
+            "@home",
+            "+Chores",
+            "due:2011-03-01"
+        ]
+    );
+    assert!(tags.iter().all(|(tag, value)| &description[*tag] == *value));
+
+    let mut rest = task.tags();
+
+    rest.nth(3);
+    assert_eq!(rest.count_fast(), 2);
+    assert_eq!(rest.next(), Some(tags[4].0));
+    assert_eq!(rest.count_fast(), 1);
+    assert_eq!(rest.into_vec_with_values(description), [tags[5]]);
+    assert_eq!(
+        "Call Mom @ + :".tasks().next().unwrap().tags().count_fast(),
+        0
+    );
+}
+
+#[test]
+fn offset_tags_keep_their_kind() {
+    let tags = [
+        Tag::Context { start: 4, end: 10 },
+        Tag::Project { start: 4, end: 10 },
+        Tag::Special { start: 4, end: 10 },
+    ];
+
+    for tag in &tags {
+        let forward = tag.offset_by(6).unwrap();
+        let back = tag.offset_by(-4).unwrap();
+
+        assert_eq!((forward.start(), forward.end()), (10, 16));
+        assert_eq!((back.start(), back.end()), (0, 6));
+        assert_eq!(
+            std::mem::discriminant(&forward),
+            std::mem::discriminant(tag)
+        );
+        assert_eq!(std::mem::discriminant(&back), std::mem::discriminant(tag));
+        assert_eq!(tag.offset_by(0), Some(*tag));
+        assert_eq!(tag.offset_by(-5), None);
+        assert_eq!(tag.offset_by(isize::MIN), None);
+        assert_eq!(
+            tag.offset_by(isize::MAX).unwrap().start(),
+            4 + isize::MAX as usize
+        );
+    }
+
+    assert_eq!(
+        Tag::Special {
+            start: 0,
+            end: usize::MAX
+        }
+        .offset_by(1),
+        None
+    );
+}
+
+#[test]
+fn offset_tags_follow_edits_to_the_description() {
+    let task = "Call Mom @phone +Family".tasks().next().unwrap();
+    let description = format!("Please {}", task.description());
+    let tags: Vec<&str> = task
+        .tags()
+        .map(|tag| tag.offset_by(7).unwrap().as_str(&description))
+        .collect();
+
+    assert_eq!(tags, ["@phone", "+Family"]);
+}
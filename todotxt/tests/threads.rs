@@ -0,0 +1,79 @@
+//! Compile-time guarantees that tasks and their iterators can be sent and
+//! shared between threads.
+
+use std::thread;
+use todotxt::{
+    parser::{IndentedIter, Iter, ParseError, ParseErrorKind, Parser, TryIter},
+    prelude::*,
+    CompactTask, State, Tags, TaskBuilder, TodoList,
+};
+
+fn assert_send<T: Send>() {}
+
+fn assert_sync<T: Sync>() {}
+
+fn assert_send_sync<T: Send + Sync>() {
+    assert_send::<T>();
+    assert_sync::<T>();
+}
+
+#[test]
+fn public_types_are_send_and_sync() {
+    assert_send_sync::<Task<'static>>();
+    assert_send_sync::<Task<'_>>();
+    assert_send_sync::<Tag>();
+    assert_send_sync::<Tags<'_>>();
+    assert_send_sync::<Priority>();
+    assert_send_sync::<State>();
+    assert_send_sync::<Iter<'_>>();
+    assert_send_sync::<TryIter<'_>>();
+    assert_send_sync::<IndentedIter<'_>>();
+    assert_send_sync::<Parser>();
+    assert_send_sync::<ParseError>();
+    assert_send_sync::<ParseErrorKind>();
+    assert_send_sync::<CompactTask>();
+    assert_send_sync::<TaskBuilder>();
+    assert_send_sync::<TodoList<'_>>();
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_iterators_are_send_and_sync() {
+    use todotxt::parser::{ParallelChunks, ParallelIter};
+
+    assert_send_sync::<ParallelIter<'_>>();
+    assert_send_sync::<ParallelChunks<'_>>();
+}
+
+#[test]
+fn owned_tasks_move_between_threads() {
+    let tasks: Vec<Task<'static>> = "(A) Call Mom @phone\nPay rent"
+        .tasks()
+        .map(Task::into_owned)
+        .collect();
+    let lines = thread::spawn(move || {
+        tasks
+            .iter()
+            .map(|task| task.to_string())
+            .collect::<Vec<_>>()
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(lines, ["(A) Call Mom @phone", "Pay rent"]);
+}
+
+#[test]
+fn borrowed_tasks_are_shared_with_scoped_threads() {
+    let data = String::from("(A) Call Mom @phone\nPay rent +Home");
+    let tasks: Vec<Task> = data.tasks().collect();
+    let mut iter = data.tasks();
+
+    thread::scope(|scope| {
+        let tags = scope.spawn(|| tasks.iter().map(|task| task.tags().count()).sum::<usize>());
+        let first = scope.spawn(move || iter.next().map(|task| task.to_string()));
+
+        assert_eq!(tags.join().unwrap(), 2);
+        assert_eq!(first.join().unwrap().unwrap(), "(A) Call Mom @phone");
+    });
+}
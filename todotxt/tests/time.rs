@@ -0,0 +1,393 @@
+//! Tests for methods that depend on the current date.
+
+#![cfg(feature = "dates")]
+
+use todotxt::{
+    chrono::{Datelike, FixedOffset, LocalResult, NaiveDateTime, TimeZone},
+    prelude::*,
+    time, Date, DueDate, TaskBuilder, TodoList,
+};
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn the_test_date_overrides_today() {
+    time::set_test_date(date(2011, 3, 1));
+
+    assert_eq!(time::today(), date(2011, 3, 1));
+    assert_eq!(time::now(), date(2011, 3, 1).and_hms_opt(0, 0, 0).unwrap());
+
+    time::clear_test_date();
+    assert_ne!(time::today(), date(2011, 3, 1));
+}
+
+#[test]
+fn date_dependent_methods_use_the_test_date() {
+    let data = "
+        Pay rent due:2011-03-05
+        Water the plants t:2011-03-03 due:2011-03-01
+        x 2011-03-02 2011-03-01 Review Tim's pull request +TodoTxtTouch @github
+    ";
+    let tasks: Vec<Task> = data.tasks().collect();
+
+    time::set_test_date(date(2011, 3, 2));
+
+    assert_eq!(tasks[0].days_until_due(), Some(3));
+    assert_eq!(tasks[1].days_until_due(), Some(-1));
+    assert!(tasks[0].is_available());
+    assert!(!tasks[1].is_available());
+    assert_eq!(TodoList::from(tasks.clone()).statistics().overdue(), 1);
+
+    let mut task = TaskBuilder::new("Call Mom").created_today().build();
+
+    task.complete_today();
+    assert_eq!(task.to_string(), "x 2011-03-02 2011-03-02 Call Mom");
+
+    time::set_test_date(date(2011, 3, 3));
+
+    assert!(tasks[1].is_available());
+
+    time::clear_test_date();
+}
+
+#[test]
+fn due_times_are_parsed_with_either_separator() {
+    let due = date(2011, 3, 1);
+    let data = "
+        Pay rent due:2011-03-01
+        File taxes due:2011-03-01T17:00
+        Call Mom due:2011-03-01_17:00
+        Buy milk due:2011-03-01T17:00:30
+        Water the plants due:2011-03-01_23:59:59
+    ";
+    let values: Vec<_> = data.tasks().map(|task| task.due_datetime()).collect();
+
+    assert_eq!(
+        values,
+        [
+            Some(DueDate::Date(due)),
+            due.and_hms_opt(17, 0, 0).map(DueDate::DateTime),
+            due.and_hms_opt(17, 0, 0).map(DueDate::DateTime),
+            due.and_hms_opt(17, 0, 30).map(DueDate::DateTime),
+            due.and_hms_opt(23, 59, 59).map(DueDate::DateTime),
+        ]
+    );
+    assert!(data.tasks().all(|task| task.due_date() == Some(due)));
+}
+
+#[test]
+fn invalid_due_times_are_ignored() {
+    let data = "
+        due:2011-03-01T25:00
+        due:2011-03-01T17:60
+        due:2011-03-01T17:00:60
+        due:2011-03-01T17
+        due:2011-03-01T5:00
+        due:2011-03-01T
+        due:2011-03-01 17:00
+        due:2011-03-01x17:00
+    ";
+    let tasks: Vec<Task> = data.tasks().collect();
+
+    for task in &tasks[..6] {
+        assert_eq!(task.due_datetime(), None, "{}", task);
+        assert_eq!(task.due_date(), None, "{}", task);
+    }
+
+    // A space ends the tag, so the time is part of the description.
+    assert_eq!(
+        tasks[6].due_datetime(),
+        Some(DueDate::Date(date(2011, 3, 1)))
+    );
+    assert_eq!(tasks[7].due_datetime(), None);
+}
+
+#[test]
+fn malformed_dates_are_distinguished_from_missing_ones() {
+    use todotxt::chrono::{format::ParseErrorKind, ParseError};
+
+    let data = "
+        Pay rent due:2011-03-01T17:00 t:2011-02-25
+        Call Mom @phone
+        File taxes due:2011-02-30 t:soon
+        Water the plants due:2011-03-01T25:00 t:2011-02-25x
+        Schedule Goodwill pickup due:2011-3-1 t:2011-3-1
+    ";
+    let tasks: Vec<Task> = data.tasks().collect();
+    let kind = |result: Result<Option<Date>, ParseError>| result.unwrap_err().kind();
+
+    assert_eq!(tasks[0].try_get_due_date(), Ok(Some(date(2011, 3, 1))));
+    assert_eq!(
+        tasks[0].try_get_threshold_date(),
+        Ok(Some(date(2011, 2, 25)))
+    );
+    assert_eq!(tasks[1].try_get_due_date(), Ok(None));
+    assert_eq!(tasks[1].try_get_threshold_date(), Ok(None));
+    assert_eq!(
+        kind(tasks[2].try_get_due_date()),
+        ParseErrorKind::OutOfRange
+    );
+    assert_eq!(
+        kind(tasks[2].try_get_threshold_date()),
+        ParseErrorKind::Invalid
+    );
+    assert_eq!(kind(tasks[3].try_get_due_date()), ParseErrorKind::TooLong);
+    assert_eq!(
+        kind(tasks[3].try_get_threshold_date()),
+        ParseErrorKind::TooLong
+    );
+    assert_eq!(kind(tasks[4].try_get_due_date()), ParseErrorKind::Invalid);
+    assert_eq!(
+        kind(tasks[4].try_get_threshold_date()),
+        ParseErrorKind::Invalid
+    );
+
+    for task in &tasks {
+        assert_eq!(task.try_get_due_date().unwrap_or(None), task.due_date());
+        assert_eq!(
+            task.try_get_threshold_date().unwrap_or(None),
+            task.threshold_date()
+        );
+    }
+}
+
+#[test]
+fn due_times_are_only_compared_with_times() {
+    let data = "
+        Pay rent due:2011-03-01
+        File taxes due:2011-03-01T09:00
+        Call Mom due:2011-03-01_17:00
+        Buy milk due:2011-02-28T23:59
+        x Water the plants due:2011-02-28
+    ";
+    let overdue = || -> Vec<bool> { data.tasks().map(|task| task.is_overdue()).collect() };
+
+    time::set_test_now(date(2011, 3, 1).and_hms_opt(12, 0, 0).unwrap());
+    assert_eq!(overdue(), [false, true, false, true, false]);
+
+    // The date alone only compares at day granularity.
+    time::set_test_date(date(2011, 3, 1));
+    assert_eq!(overdue(), [false, false, false, true, false]);
+    assert_eq!(data.tasks().collect::<TodoList>().statistics().overdue(), 1);
+
+    time::set_test_date(date(2011, 3, 2));
+    assert_eq!(overdue(), [true, true, true, true, false]);
+
+    time::clear_test_date();
+}
+
+#[test]
+fn relative_dates_resolve_against_the_reference() {
+    // 2011-03-01 is a Tuesday.
+    let reference = date(2011, 3, 1);
+    let cases = [
+        ("today", Some(date(2011, 3, 1))),
+        ("Today", Some(date(2011, 3, 1))),
+        ("tomorrow", Some(date(2011, 3, 2))),
+        ("yesterday", Some(date(2011, 2, 28))),
+        ("wednesday", Some(date(2011, 3, 2))),
+        ("sun", Some(date(2011, 3, 6))),
+        ("MON", Some(date(2011, 3, 7))),
+        ("tuesday", Some(date(2011, 3, 8))),
+        ("0d", Some(date(2011, 3, 1))),
+        ("3d", Some(date(2011, 3, 4))),
+        ("2w", Some(date(2011, 3, 15))),
+        ("1m", Some(date(2011, 4, 1))),
+        ("1y", Some(date(2012, 3, 1))),
+        ("2011-03-05", None),
+        ("d", None),
+        ("-3d", None),
+        ("+3d", None),
+        ("3x", None),
+        ("3dé", None),
+        ("soon", None),
+        ("", None),
+    ];
+
+    for (value, expected) in &cases {
+        assert_eq!(
+            time::resolve_relative(value, reference),
+            *expected,
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn relative_months_are_clamped_to_the_end_of_the_month() {
+    let reference = date(2011, 1, 31);
+
+    assert_eq!(
+        time::resolve_relative("1m", reference),
+        Some(date(2011, 2, 28))
+    );
+    assert_eq!(
+        time::resolve_relative("13m", reference),
+        Some(date(2012, 2, 29))
+    );
+    assert_eq!(
+        time::resolve_relative("1y", date(2012, 2, 29)),
+        Some(date(2013, 2, 28))
+    );
+}
+
+#[test]
+fn weekdays_wrap_around_to_the_next_week() {
+    // 2011-03-06 is a Sunday.
+    let reference = date(2011, 3, 6);
+    let days = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+    let resolved: Vec<u32> = days
+        .iter()
+        .map(|day| time::resolve_relative(day, reference).unwrap().day())
+        .collect();
+
+    assert_eq!(resolved, [7, 8, 9, 10, 11, 12, 13]);
+    assert_eq!(
+        time::resolve_relative("mon", date(2010, 12, 31)),
+        Some(date(2011, 1, 3))
+    );
+}
+
+#[test]
+fn relative_dates_are_only_resolved_on_request() {
+    let reference = date(2011, 3, 1);
+    let task = "Call Mom due:tomorrow t:monday".tasks().next().unwrap();
+
+    assert_eq!(task.due_date(), None);
+    assert_eq!(task.threshold_date(), None);
+    assert_eq!(task.due_date_with(reference), Some(date(2011, 3, 2)));
+    assert_eq!(task.threshold_date_with(reference), Some(date(2011, 3, 7)));
+
+    let task = "Pay rent due:2011-03-05".tasks().next().unwrap();
+
+    assert_eq!(task.due_date_with(reference), task.due_date());
+    assert_eq!(task.threshold_date_with(reference), None);
+}
+
+/// Central European Time in 2011: UTC+1, or UTC+2 from 01:00 UTC on March
+/// 27th until 01:00 UTC on October 30th.
+#[derive(Clone, Copy, Debug)]
+struct Cet;
+
+impl Cet {
+    fn offset_at(utc: &NaiveDateTime) -> FixedOffset {
+        let start = date(2011, 3, 27).and_hms_opt(1, 0, 0).unwrap();
+        let end = date(2011, 10, 30).and_hms_opt(1, 0, 0).unwrap();
+        let hours = if (start..end).contains(utc) { 2 } else { 1 };
+
+        FixedOffset::east_opt(hours * 3600).unwrap()
+    }
+}
+
+impl TimeZone for Cet {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+        Cet
+    }
+
+    fn offset_from_local_date(&self, local: &Date) -> LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        // The earlier of two instants has the larger offset.
+        let offsets: Vec<FixedOffset> = [2, 1]
+            .iter()
+            .map(|hours| FixedOffset::east_opt(hours * 3600).unwrap())
+            .filter(|offset| {
+                let utc = *local - chrono::Duration::seconds(offset.local_minus_utc().into());
+
+                Cet::offset_at(&utc) == *offset
+            })
+            .collect();
+
+        match offsets[..] {
+            [] => LocalResult::None,
+            [offset] => LocalResult::Single(offset),
+            [earliest, latest] => LocalResult::Ambiguous(earliest, latest),
+            _ => unreachable!(),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &Date) -> FixedOffset {
+        Cet::offset_at(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        Cet::offset_at(utc)
+    }
+}
+
+fn due_in_zone<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let data = format!("File taxes due:{}", value);
+    let task = data.tasks().next().unwrap();
+
+    task.due_in_zone(tz).map(|due| due.to_rfc3339())
+}
+
+#[test]
+fn due_times_are_interpreted_in_a_fixed_offset_zone() {
+    let zone = FixedOffset::east_opt(3600).unwrap();
+    let cases = [
+        ("2011-03-27T01:59", Some("2011-03-27T01:59:00+01:00")),
+        ("2011-03-27T02:30", Some("2011-03-27T02:30:00+01:00")),
+        ("2011-10-30_02:30:15", Some("2011-10-30T02:30:15+01:00")),
+        ("2011-10-30T02:30+02:00", Some("2011-10-30T01:30:00+01:00")),
+        ("2011-10-30T23:30Z", Some("2011-10-31T00:30:00+01:00")),
+        ("2011-10-30T23:30-05:30", Some("2011-10-31T06:00:00+01:00")),
+        ("2011-10-30", None),
+        ("2011-10-30T23:30+25:00", None),
+        ("2011-10-30T23:30+0200", None),
+        ("2011-10-30T23:30z", None),
+    ];
+
+    for (value, expected) in &cases {
+        assert_eq!(due_in_zone(value, &zone).as_deref(), *expected, "{}", value);
+    }
+}
+
+#[test]
+fn due_times_around_daylight_saving_transitions_follow_the_documented_policy() {
+    let cases = [
+        ("2011-01-15T12:00", Some("2011-01-15T12:00:00+01:00")),
+        ("2011-06-15T12:00", Some("2011-06-15T12:00:00+02:00")),
+        // Clocks move forward from 02:00 to 03:00, so 02:30 does not exist.
+        ("2011-03-27T01:59", Some("2011-03-27T01:59:00+01:00")),
+        ("2011-03-27T02:30", None),
+        ("2011-03-27T03:00", Some("2011-03-27T03:00:00+02:00")),
+        // Clocks move back from 03:00 to 02:00, so 02:30 occurs twice and the
+        // earlier one is chosen.
+        ("2011-10-30T02:30", Some("2011-10-30T02:30:00+02:00")),
+        ("2011-10-30T03:00", Some("2011-10-30T03:00:00+01:00")),
+        // Explicit offsets are never ambiguous.
+        ("2011-10-30T02:30+01:00", Some("2011-10-30T02:30:00+01:00")),
+        ("2011-03-27T01:30Z", Some("2011-03-27T03:30:00+02:00")),
+    ];
+
+    for (value, expected) in &cases {
+        assert_eq!(due_in_zone(value, &Cet).as_deref(), *expected, "{}", value);
+    }
+}
+
+#[test]
+fn due_offsets_do_not_change_the_due_date() {
+    let task = "File taxes due:2011-03-01T23:30-05:00"
+        .tasks()
+        .next()
+        .unwrap();
+
+    assert_eq!(task.due_date(), Some(date(2011, 3, 1)));
+    assert_eq!(
+        task.due_datetime(),
+        date(2011, 3, 1)
+            .and_hms_opt(23, 30, 0)
+            .map(DueDate::DateTime)
+    );
+}
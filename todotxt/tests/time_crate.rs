@@ -0,0 +1,81 @@
+//! Tests for converting dates to and from the `time` crate.
+
+#![cfg(feature = "time")]
+
+use std::convert::TryFrom;
+use time::Month;
+use todotxt::{prelude::*, ConvertDate, Date};
+
+type TimeDate = time::Date;
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn time_date(year: i32, month: u8, day: u8) -> TimeDate {
+    TimeDate::from_calendar_date(year, Month::try_from(month).unwrap(), day).unwrap()
+}
+
+#[test]
+fn dates_convert_across_year_boundaries() {
+    let mut current = time_date(1999, 12, 25);
+
+    while current <= time_date(2001, 1, 5) {
+        let converted = current.into_date().unwrap();
+
+        assert_eq!(converted.to_string(), current.to_string());
+        assert_eq!(TimeDate::from_date(converted), Some(current));
+        current = current.next_day().unwrap();
+    }
+
+    assert_eq!(
+        TimeDate::from_date(date(2000, 2, 29)),
+        Some(time_date(2000, 2, 29))
+    );
+    assert_eq!(
+        TimeDate::from_date(date(2010, 12, 31)),
+        Some(time_date(2010, 12, 31))
+    );
+    assert_eq!(time_date(2011, 1, 1).into_date(), Some(date(2011, 1, 1)));
+}
+
+#[test]
+fn dates_outside_the_range_of_either_type_are_none() {
+    assert_eq!(TimeDate::MAX.into_date(), Some(date(9999, 12, 31)));
+    assert_eq!(TimeDate::from_date(date(9999, 12, 31)), Some(TimeDate::MAX));
+    assert_eq!(time_date(0, 1, 1).into_date(), Some(date(0, 1, 1)));
+
+    if cfg!(feature = "dates") {
+        // The default range of `time::Date` is smaller than that of
+        // `NaiveDate`.
+        assert_eq!(TimeDate::MIN.into_date(), Date::from_ymd_opt(-9999, 1, 1));
+        assert_eq!(
+            Date::from_ymd_opt(-10000, 12, 31).and_then(TimeDate::from_date),
+            None
+        );
+        assert_eq!(
+            Date::from_ymd_opt(10000, 1, 1).and_then(TimeDate::from_date),
+            None
+        );
+    } else {
+        // Without chrono, dates start at year 0.
+        assert_eq!(TimeDate::MIN.into_date(), None);
+        assert_eq!(time_date(-1, 12, 31).into_date(), None);
+    }
+}
+
+#[test]
+fn task_dates_are_read_as_time_dates() {
+    let data = "x 2011-03-02 2011-03-01 Review Tim's pull request due:2011-03-05 t:2011-02-28";
+    let task = data.tasks().next().unwrap();
+
+    assert_eq!(task.completion_date_as(), Some(time_date(2011, 3, 2)));
+    assert_eq!(task.creation_date_as(), Some(time_date(2011, 3, 1)));
+    assert_eq!(task.due_date_as(), Some(time_date(2011, 3, 5)));
+    assert_eq!(task.threshold_date_as(), Some(time_date(2011, 2, 28)));
+
+    let task = "Call Mom due:+10000-01-01".tasks().next().unwrap();
+
+    assert_eq!(task.creation_date_as::<TimeDate>(), None);
+    assert_eq!(task.due_date_as::<TimeDate>(), None);
+}
@@ -0,0 +1,185 @@
+//! Tests for the diagnostics that are emitted to `tracing` subscribers.
+
+#![cfg(feature = "tracing")]
+
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use todotxt::prelude::*;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Metadata, Subscriber,
+};
+
+/// Records the level and fields of every event, and the name of every span.
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<(Level, Fields)>>>,
+    next_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<Vec<String>>>,
+}
+
+/// Collects the fields of an event as strings.
+#[derive(Debug, PartialEq)]
+struct Fields(Vec<(String, String)>);
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.spans
+            .lock()
+            .unwrap()
+            .push(span.metadata().name().to_owned());
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(Vec::new());
+
+        event.record(&mut fields);
+        self.events
+            .lock()
+            .unwrap()
+            .push((*event.metadata().level(), fields));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_owned(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_owned(), format!("{:?}", value)));
+    }
+}
+
+fn fields(pairs: &[(&str, &str)]) -> Fields {
+    Fields(
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+#[test]
+fn invalid_special_values_are_warned_about() {
+    let data = "\
+Call Mom due:2011-03-01
+Pay rent due:2011-13-45
+Water the plants t:soon
+";
+    let recorder = Recorder::default();
+    let (due, threshold) = tracing::subscriber::with_default(recorder.clone(), || {
+        let tasks: Vec<Task> = data.tasks().collect();
+        let due: Vec<_> = tasks.iter().map(|task| task.due_date()).collect();
+        let threshold: Vec<_> = tasks.iter().map(|task| task.threshold_date()).collect();
+
+        (due, threshold)
+    });
+
+    assert!(due[0].is_some());
+    assert_eq!(due[1..], [None, None]);
+    assert_eq!(threshold, [None, None, None]);
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        [
+            (
+                Level::WARN,
+                fields(&[
+                    ("message", "invalid special tag value"),
+                    ("key", "due"),
+                    ("value", "2011-13-45"),
+                ])
+            ),
+            (
+                Level::WARN,
+                fields(&[
+                    ("message", "invalid special tag value"),
+                    ("key", "t"),
+                    ("value", "soon"),
+                ])
+            ),
+        ]
+    );
+}
+
+#[test]
+fn each_line_is_parsed_in_a_span() {
+    let recorder = Recorder::default();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!("Call Mom\n\nPay rent\n".tasks().count(), 2);
+    });
+
+    assert_eq!(*recorder.spans.lock().unwrap(), ["parse", "parse"]);
+    assert!(recorder.events.lock().unwrap().is_empty());
+}
+
+#[test]
+fn long_values_are_truncated() {
+    let value = "é".repeat(100);
+    let line = format!("Call Mom due:{}", value);
+    let recorder = Recorder::default();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!(line.tasks().next().unwrap().due_date(), None);
+    });
+
+    let events = recorder.events.lock().unwrap();
+    let (_, Fields(fields)) = &events[0];
+
+    assert_eq!(fields[2], ("value".to_owned(), "é".repeat(40)));
+}
+
+#[test]
+fn lines_that_fail_to_parse_are_warned_about_with_their_number() {
+    use std::io::Cursor;
+    use todotxt::parser::ReadIter;
+
+    let data = "Call Mom\n\n(B\nPay rent\n";
+    let recorder = Recorder::default();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!(data.tasks().count(), 1);
+        assert_eq!(data.tasks().rev().count(), 1);
+        assert_eq!(data.try_tasks().filter(Result::is_err).count(), 1);
+        assert_eq!(ReadIter::new(Cursor::new(data)).count(), 2);
+    });
+
+    let events = recorder.events.lock().unwrap();
+
+    assert_eq!(events.len(), 4);
+
+    for event in events.iter() {
+        assert_eq!(
+            *event,
+            (
+                Level::WARN,
+                fields(&[
+                    ("message", "failed to parse line"),
+                    ("line", "(B"),
+                    ("line_number", "3"),
+                ])
+            )
+        );
+    }
+}
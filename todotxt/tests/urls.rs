@@ -0,0 +1,61 @@
+//! Tests for finding the URLs in the description of a task.
+
+use todotxt::prelude::*;
+
+fn urls(line: &str) -> Vec<String> {
+    let task = line.tasks().next().unwrap();
+    let urls = task.urls().map(str::to_owned).collect();
+
+    urls
+}
+
+#[test]
+fn urls_are_found_anywhere_in_the_description() {
+    assert_eq!(
+        urls("https://example.com/a read this +Reading"),
+        ["https://example.com/a"]
+    );
+    assert_eq!(
+        urls("(A) read http://example.com/b?x=1 @home"),
+        ["http://example.com/b?x=1"]
+    );
+    assert_eq!(
+        urls("email mailto:tim@example.com"),
+        ["mailto:tim@example.com"]
+    );
+    assert_eq!(
+        urls("compare HTTPS://example.com and https://example.org"),
+        ["HTTPS://example.com", "https://example.org"]
+    );
+}
+
+#[test]
+fn surrounding_punctuation_is_not_part_of_a_url() {
+    assert_eq!(
+        urls("read (https://example.com/post). later"),
+        ["https://example.com/post"]
+    );
+    assert_eq!(
+        urls("see <https://example.com>, \"https://example.org\"!"),
+        ["https://example.com", "https://example.org"]
+    );
+    assert_eq!(
+        urls("(https://en.wikipedia.org/wiki/Rust_(language))."),
+        ["https://en.wikipedia.org/wiki/Rust_(language)"]
+    );
+}
+
+#[test]
+fn urls_are_distinct_from_adjacent_tags() {
+    let line = "+Reading https://example.com/post @home due:2011-03-01 https:// mailto:";
+    let task = line.tasks().next().unwrap();
+
+    assert_eq!(
+        task.urls().collect::<Vec<_>>(),
+        ["https://example.com/post"]
+    );
+    assert_eq!(task.contexts_iter().collect::<Vec<_>>(), ["@home"]);
+    assert_eq!(task.projects_iter().collect::<Vec<_>>(), ["+Reading"]);
+    assert_eq!(task.get_special("due"), Some("2011-03-01"));
+    assert!(task.urls().all(|url| task.description().contains(url)));
+}